@@ -0,0 +1,136 @@
+use std::{fs, path::PathBuf, sync::Arc};
+
+use anyhow::{bail, Context, Error};
+use clap::{Args, Subcommand};
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{
+    api::{diff, ApiReport, Severity},
+    loader::{DefaultFileLoader, ModuleLoader},
+    Checker,
+};
+use swc_common::{errors::Handler, FileName, Globals, SourceMap, GLOBALS};
+use swc_ecma_ast::EsVersion;
+
+use crate::load_libs;
+
+/// Commands for the public api of a package.
+#[derive(Debug, Subcommand)]
+pub enum ApiCommand {
+    /// Print exported names of a module with their types, sorted by name.
+    Report(ApiReportCommand),
+    /// Compare the public api of two entry files, and fail if there's a
+    /// breaking change.
+    Diff(ApiDiffCommand),
+}
+
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct ApiReportCommand {
+    #[clap(name = "file")]
+    pub file: String,
+
+    /// Write the report to this file instead of stdout.
+    #[clap(long)]
+    pub out: Option<PathBuf>,
+
+    /// The builtin libraries to load. Defaults to `es5`.
+    #[clap(long)]
+    pub libs: Option<Vec<String>>,
+}
+
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct ApiDiffCommand {
+    /// Entry file of the previous version. This can be a report generated by
+    /// `stc api report`, saved with `.d.ts` extension.
+    #[clap(name = "old")]
+    pub old: String,
+
+    /// Entry file of the new version.
+    #[clap(name = "new")]
+    pub new: String,
+
+    /// The builtin libraries to load. Defaults to `es5`.
+    #[clap(long)]
+    pub libs: Option<Vec<String>>,
+}
+
+impl ApiCommand {
+    pub fn run(self, cm: Arc<SourceMap>, handler: Arc<Handler>, globals: Arc<Globals>) -> Result<(), Error> {
+        match self {
+            ApiCommand::Report(cmd) => {
+                let env = create_env(&globals, cmd.libs.as_deref());
+
+                let report = GLOBALS.set(&globals, || {
+                    let checker = create_checker(cm, handler, env);
+
+                    load_report(&checker, &cmd.file)
+                })?;
+
+                match cmd.out {
+                    Some(out) => {
+                        fs::write(&out, report.to_text()).with_context(|| format!("failed to write api report to {}", out.display()))?;
+                    }
+                    None => {
+                        print!("{}", report.to_text());
+                    }
+                }
+
+                Ok(())
+            }
+            ApiCommand::Diff(cmd) => {
+                let env = create_env(&globals, cmd.libs.as_deref());
+
+                let changes = GLOBALS.set(&globals, || -> Result<_, Error> {
+                    // Both versions are analyzed by one checker, so that types can be compared.
+                    let checker = create_checker(cm, handler, env);
+
+                    let old = load_report(&checker, &cmd.old)?;
+                    let new = load_report(&checker, &cmd.new)?;
+
+                    Ok(diff(&old, &new, |l, r| checker.is_assignable(l, r)))
+                })?;
+
+                for change in &changes {
+                    println!("{}", change);
+                }
+
+                let breaking = changes.iter().filter(|c| c.severity == Severity::Breaking).count();
+                if breaking > 0 {
+                    bail!("found {} breaking change(s)", breaking)
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+fn create_env(globals: &Arc<Globals>, libs: Option<&[String]>) -> Env {
+    let libs = load_libs(libs);
+
+    GLOBALS.set(globals, || {
+        Env::simple(Rule { ..Default::default() }, EsVersion::latest(), ModuleConfig::None, &libs)
+    })
+}
+
+fn create_checker(cm: Arc<SourceMap>, handler: Arc<Handler>, env: Env) -> Checker {
+    Checker::new(
+        cm.clone(),
+        handler,
+        env.clone(),
+        None,
+        Box::new(ModuleLoader::new(cm, env, NodeResolver::new(), DefaultFileLoader)),
+    )
+}
+
+fn load_report(checker: &Checker, file: &str) -> Result<ApiReport, Error> {
+    let path = fs::canonicalize(file).with_context(|| format!("failed to find `{}`", file))?;
+
+    match ApiReport::load(checker, Arc::new(FileName::Real(path))) {
+        Some(report) => Ok(report),
+        None => bail!("failed to analyze `{}`", file),
+    }
+}
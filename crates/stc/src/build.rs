@@ -0,0 +1,65 @@
+use std::{path::PathBuf, sync::Arc, time::Instant};
+
+use anyhow::{bail, Error};
+use clap::Args;
+use stc_ts_type_checker::build::{BuildStatus, Builder};
+use swc_common::{errors::Handler, Globals, SourceMap, GLOBALS};
+
+/// Build a project and the projects referenced by it, in the order of
+/// `references` of `tsconfig.json`.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct BuildCommand {
+    /// Path to `tsconfig.json`, or a directory containing it.
+    #[clap(name = "project", default_value = "tsconfig.json")]
+    pub project: PathBuf,
+
+    /// Build all projects, even if they are up to date.
+    #[clap(long)]
+    pub force: bool,
+}
+
+impl BuildCommand {
+    pub fn run(self, cm: Arc<SourceMap>, handler: Arc<Handler>, globals: Arc<Globals>) -> Result<(), Error> {
+        let builder = Builder::new(cm, handler.clone(), &self.project)?;
+
+        let start = Instant::now();
+        let results = GLOBALS.set(&globals, || builder.build(self.force));
+        let end = Instant::now();
+
+        log::info!("Building {} project(s) took {:?}", results.len(), end - start);
+
+        let mut failed = 0;
+        for result in &results {
+            for err in &result.errors {
+                err.emit(&handler);
+            }
+
+            match result.status {
+                BuildStatus::UpToDate => {
+                    log::info!("Project `{}` is up to date", result.config_path.display());
+                }
+                BuildStatus::Built => {
+                    log::info!("Built project `{}`", result.config_path.display());
+                }
+                BuildStatus::Failed => {
+                    failed += 1;
+                    log::info!("Project `{}` has {} error(s)", result.config_path.display(), result.errors.len());
+                }
+                BuildStatus::Skipped => {
+                    failed += 1;
+                    log::info!(
+                        "Skipping build of `{}` because a referenced project has errors",
+                        result.config_path.display()
+                    );
+                }
+            }
+        }
+
+        if failed > 0 {
+            bail!("failed to build {} project(s)", failed)
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+use anyhow::{Context, Error};
+use clap::Subcommand;
+use stc_utils::disk_cache::cache_dir;
+
+/// Subdirectories of the cache directory created by stc.
+///
+/// - `builtin`: Builtin libraries.
+/// - `modules`: Analysis results of `stc check --incremental`.
+const CACHE_SUBDIRS: &[&str] = &["builtin", "modules"];
+
+/// Commands for the on-disk cache.
+#[derive(Debug, Subcommand)]
+pub enum CacheCommand {
+    /// Remove all cached builtin libraries and analysis results.
+    Clean,
+    /// Print the path of the cache directory.
+    Dir,
+}
+
+impl CacheCommand {
+    pub fn run(self) -> Result<(), Error> {
+        let dir = cache_dir();
+
+        match self {
+            CacheCommand::Clean => {
+                clean(dir)?;
+
+                log::info!("Removed cached files in `{}`", dir.display());
+            }
+            CacheCommand::Dir => {
+                println!("{}", dir.display());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Removes the subdirectories created by stc, and `dir` itself if it becomes
+/// empty. Other files are kept, as `dir` can be configured by users.
+fn clean(dir: &Path) -> Result<(), Error> {
+    for name in CACHE_SUBDIRS {
+        let path = dir.join(name);
+
+        match fs::remove_dir_all(&path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err).with_context(|| format!("failed to remove `{}`", path.display())),
+        }
+    }
+
+    // This fails if the directory is not empty.
+    let _ = fs::remove_dir(dir);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn clean_keeps_other_files() {
+        let dir = env::temp_dir().join("stc-cache-clean").join("keeps_other_files");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("builtin")).unwrap();
+        fs::create_dir_all(dir.join("modules")).unwrap();
+        fs::write(dir.join("builtin").join("a.rmp"), "").unwrap();
+        fs::write(dir.join("modules").join("b.rmp"), "").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        clean(&dir).unwrap();
+
+        assert!(!dir.join("builtin").exists());
+        assert!(!dir.join("modules").exists());
+        assert!(dir.join("notes.txt").exists());
+    }
+
+    #[test]
+    fn clean_removes_empty_dir() {
+        let dir = env::temp_dir().join("stc-cache-clean").join("removes_empty_dir");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("modules")).unwrap();
+
+        clean(&dir).unwrap();
+
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn clean_missing_dir() {
+        let dir = env::temp_dir().join("stc-cache-clean").join("missing_dir");
+        let _ = fs::remove_dir_all(&dir);
+
+        clean(&dir).unwrap();
+    }
+}
@@ -1,4 +1,26 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    sync::Arc,
+    thread::sleep,
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::{Context, Error};
 use clap::Args;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{
+    incremental::ModuleCache,
+    loader::{DefaultFileLoader, ModuleLoader},
+    Checker,
+};
+use stc_utils::disk_cache::cache_dir;
+use swc_common::{errors::Handler, FileName, Globals, SourceMap, Spanned, GLOBALS};
+use swc_ecma_ast::EsVersion;
+
+use crate::load_libs;
 
 /// Perform type checking, but this command is not public api and is only used
 /// for testing.
@@ -16,3 +38,250 @@ pub struct TestCommand {
     #[clap(long)]
     pub types: Option<Vec<String>>,
 }
+
+/// Type check a file and its dependencies.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct CheckCommand {
+    #[clap(name = "file")]
+    pub file: String,
+
+    /// The builtin libraries to load. Defaults to `es5`.
+    #[clap(long)]
+    pub libs: Option<Vec<String>>,
+
+    /// Directory name of typings to load.
+    #[clap(long)]
+    pub types: Option<Vec<String>>,
+
+    /// Keep running, and check modified files and their dependents again.
+    #[clap(long)]
+    pub watch: bool,
+
+    /// Store analysis results in the cache directory, and skip analysis of
+    /// files which are not changed since the last run.
+    #[clap(long)]
+    pub incremental: bool,
+
+    /// Interval of polling for modified files, in milliseconds.
+    #[clap(long, default_value = "300")]
+    pub poll_interval: u64,
+}
+
+impl CheckCommand {
+    pub fn run(self, cm: Arc<SourceMap>, handler: Arc<Handler>, globals: Arc<Globals>) -> Result<(), Error> {
+        let libs = load_libs(self.libs.as_deref());
+
+        let env = GLOBALS.set(&globals, || {
+            Env::simple(Rule { ..Default::default() }, EsVersion::latest(), ModuleConfig::None, &libs)
+        });
+
+        let path = fs::canonicalize(&self.file).with_context(|| format!("failed to find `{}`", self.file))?;
+        let entry = Arc::new(FileName::Real(path.clone()));
+
+        GLOBALS.set(&globals, || {
+            let mut checker = Checker::new(
+                cm.clone(),
+                handler.clone(),
+                env.clone(),
+                None,
+                Box::new(ModuleLoader::new(cm.clone(), env, NodeResolver::new(), DefaultFileLoader)),
+            );
+
+            if self.incremental {
+                checker.set_module_cache(ModuleCache::new(cache_dir().join("modules")));
+            }
+
+            checker.load_typings(&path, None, self.types.as_deref());
+
+            let mut diagnostics = Diagnostics::default();
+
+            let start = Instant::now();
+            checker.check(entry.clone());
+            diagnostics.add(&cm, checker.take_errors());
+
+            let end = Instant::now();
+            log::info!("Checking took {:?}", end - start);
+
+            diagnostics.emit(&handler);
+
+            if !self.watch {
+                return Ok(());
+            }
+
+            let mut last_modified = modified_times(&checker);
+
+            loop {
+                sleep(Duration::from_millis(self.poll_interval));
+
+                let current = modified_times(&checker);
+                let changed = changed_files(&last_modified, &current);
+                last_modified = current;
+
+                if changed.is_empty() {
+                    continue;
+                }
+
+                let start = Instant::now();
+
+                recheck(&mut checker, &cm, &entry, &changed, &mut diagnostics);
+
+                let end = Instant::now();
+                log::info!("Checking {} modified file(s) took {:?}", changed.len(), end - start);
+
+                diagnostics.emit(&handler);
+
+                // New dependencies may be loaded by the check.
+                last_modified = modified_times(&checker);
+            }
+        })
+    }
+}
+
+/// Checks `changed` files and files depending on them again, and replaces
+/// their errors in `diagnostics`.
+fn recheck(checker: &mut Checker, cm: &SourceMap, entry: &Arc<FileName>, changed: &[Arc<FileName>], diagnostics: &mut Diagnostics) {
+    for file in checker.invalidate(changed) {
+        diagnostics.remove(&file);
+    }
+
+    checker.check(entry.clone());
+    diagnostics.add(cm, checker.take_errors());
+}
+
+/// Removed files are also treated as modified.
+fn changed_files(last: &HashMap<Arc<FileName>, SystemTime>, current: &HashMap<Arc<FileName>, SystemTime>) -> Vec<Arc<FileName>> {
+    let mut changed = current
+        .iter()
+        .filter(|(file, time)| last.get(*file) != Some(*time))
+        .map(|(file, _)| file.clone())
+        .chain(last.keys().filter(|file| !current.contains_key(*file)).cloned())
+        .collect::<Vec<_>>();
+    changed.sort();
+    changed
+}
+
+/// Errors grouped by file, so errors of unchanged files can be reported again
+/// in watch mode.
+#[derive(Default)]
+struct Diagnostics {
+    errors: BTreeMap<String, Vec<stc_ts_errors::Error>>,
+}
+
+impl Diagnostics {
+    fn add(&mut self, cm: &SourceMap, errors: Vec<stc_ts_errors::Error>) {
+        for err in errors {
+            let span = err.span();
+            let file = if span.is_dummy() {
+                String::new()
+            } else {
+                cm.span_to_filename(span).to_string()
+            };
+
+            self.errors.entry(file).or_default().push(err);
+        }
+    }
+
+    fn remove(&mut self, file: &FileName) {
+        self.errors.remove(&file.to_string());
+    }
+
+    fn emit(&self, handler: &Handler) {
+        let mut count = 0;
+        for err in self.errors.values().flatten() {
+            err.emit(handler);
+            count += 1;
+        }
+
+        log::info!("Found {} errors", count);
+    }
+}
+
+fn modified_times(checker: &Checker) -> HashMap<Arc<FileName>, SystemTime> {
+    checker
+        .module_loader()
+        .files()
+        .into_iter()
+        .filter_map(|file| {
+            let time = match &*file {
+                FileName::Real(path) => fs::metadata(path).and_then(|m| m.modified()).ok()?,
+                _ => return None,
+            };
+
+            Some((file, time))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, time::UNIX_EPOCH};
+
+    use stc_ts_builtin_types::Lib;
+
+    use super::*;
+
+    fn time(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn changed_files_includes_removed_files() {
+        let a = Arc::new(FileName::Real("a.ts".into()));
+        let b = Arc::new(FileName::Real("b.ts".into()));
+        let c = Arc::new(FileName::Real("c.ts".into()));
+        let d = Arc::new(FileName::Real("d.ts".into()));
+
+        let last = HashMap::from([(a.clone(), time(1)), (b.clone(), time(1)), (c.clone(), time(1))]);
+        let current = HashMap::from([(a, time(1)), (b.clone(), time(2)), (d.clone(), time(1))]);
+
+        assert_eq!(changed_files(&last, &current), vec![b, c, d]);
+    }
+
+    #[test]
+    fn recheck_keeps_errors_of_unchanged_files() {
+        let dir = env::temp_dir().join("stc-watch");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.ts"), "export const a: number = 1;").unwrap();
+        fs::write(dir.join("b.ts"), "export const b: string = 1;").unwrap();
+        fs::write(
+            dir.join("index.ts"),
+            "import { a } from './a';\nimport './b';\nconst s: string = a;",
+        )
+        .unwrap();
+        let dir = dir.canonicalize().unwrap();
+        let entry = Arc::new(FileName::Real(dir.join("index.ts")));
+
+        testing::run_test2(false, |cm, handler| {
+            let env = Env::simple(Default::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es5"));
+            let mut checker = Checker::new(
+                cm.clone(),
+                Arc::new(handler),
+                env.clone(),
+                None,
+                Box::new(ModuleLoader::new(cm.clone(), env, NodeResolver::new(), DefaultFileLoader)),
+            );
+
+            let mut diagnostics = Diagnostics::default();
+            checker.check(entry.clone());
+            diagnostics.add(&cm, checker.take_errors());
+            assert_eq!(diagnostics.errors.values().flatten().count(), 2);
+
+            fs::write(dir.join("a.ts"), "export const a: string = '';").unwrap();
+            let changed = vec![Arc::new(FileName::Real(dir.join("a.ts")))];
+            recheck(&mut checker, &cm, &entry, &changed, &mut diagnostics);
+
+            let files = diagnostics
+                .errors
+                .iter()
+                .filter(|(_, errors)| !errors.is_empty())
+                .map(|(file, _)| file.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(files, vec![dir.join("b.ts").display().to_string()]);
+
+            Ok(())
+        })
+        .unwrap();
+    }
+}
@@ -3,7 +3,7 @@ extern crate swc_node_base;
 use std::{path::PathBuf, sync::Arc, time::Instant};
 
 use anyhow::Error;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use stc_ts_builtin_types::Lib;
 use stc_ts_env::{Env, ModuleConfig, Rule};
 use stc_ts_file_analyzer::env::EnvFactory;
@@ -13,6 +13,7 @@ use stc_ts_type_checker::{
     loader::{DefaultFileLoader, ModuleLoader},
     Checker,
 };
+use stc_utils::disk_cache::set_cache_dir;
 use swc_common::{
     errors::{ColorConfig, EmitterWriter, Handler},
     FileName, Globals, SourceMap, GLOBALS,
@@ -20,15 +21,58 @@ use swc_common::{
 use swc_ecma_ast::EsVersion;
 use tracing_subscriber::EnvFilter;
 
-use crate::check::TestCommand;
+use crate::{
+    api::ApiCommand,
+    build::BuildCommand,
+    cache::CacheCommand,
+    check::{CheckCommand, TestCommand},
+};
 
+mod api;
+mod build;
+mod cache;
 mod check;
 
 #[derive(Debug, Parser)]
 #[command(name = "stc", about = "Super fast type checker for typescript", author, rename_all = "camel")]
+struct Cli {
+    /// Directory for cached builtin libraries and analysis results. Defaults
+    /// to `$STC_CACHE_DIR`, or `stc` in the cache directory of the user.
+    #[arg(long, global = true)]
+    cache_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
 enum Command {
     Test(TestCommand),
+    Check(CheckCommand),
+    Build(BuildCommand),
     Lsp(LspCommand),
+    #[command(subcommand)]
+    Api(ApiCommand),
+    #[command(subcommand)]
+    Cache(CacheCommand),
+}
+
+/// Loads builtin libraries. Defaults to `es5`.
+fn load_libs(libs: Option<&[String]>) -> Vec<Lib> {
+    let start = Instant::now();
+
+    let mut libs = match libs {
+        Some(libs) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
+        None => Lib::load("es5"),
+    };
+    libs.sort();
+    libs.dedup();
+
+    let end = Instant::now();
+
+    log::info!("Loading builtin libraries took {:?}", end - start);
+
+    libs
 }
 
 #[tokio::main]
@@ -47,7 +91,11 @@ async fn main() -> Result<(), Error> {
 
     tracing::subscriber::set_global_default(sub).unwrap();
 
-    let command = Command::parse();
+    let Cli { cache_dir, command } = Cli::parse();
+
+    if let Some(dir) = cache_dir {
+        set_cache_dir(dir);
+    }
 
     let cm = Arc::new(SourceMap::default());
     let handler = {
@@ -67,22 +115,7 @@ async fn main() -> Result<(), Error> {
 
     match command {
         Command::Test(cmd) => {
-            let libs = {
-                let start = Instant::now();
-
-                let mut libs = match cmd.libs {
-                    Some(libs) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
-                    None => Lib::load("es5"),
-                };
-                libs.sort();
-                libs.dedup();
-
-                let end = Instant::now();
-
-                log::info!("Loading builtin libraries took {:?}", end - start);
-
-                libs
-            };
+            let libs = load_libs(cmd.libs.as_deref());
 
             let env = GLOBALS.set(&globals, || {
                 Env::simple(Rule { ..Default::default() }, EsVersion::latest(), ModuleConfig::None, &libs)
@@ -98,7 +131,7 @@ async fn main() -> Result<(), Error> {
                     handler.clone(),
                     env.clone(),
                     None,
-                    Box::new(ModuleLoader::new(cm.clone(), env.clone(), NodeResolver, DefaultFileLoader)),
+                    Box::new(ModuleLoader::new(cm.clone(), env.clone(), NodeResolver::new(), DefaultFileLoader)),
                 );
 
                 checker.load_typings(&path, None, cmd.types.as_deref());
@@ -117,7 +150,7 @@ async fn main() -> Result<(), Error> {
                     handler.clone(),
                     env.clone(),
                     None,
-                    Box::new(ModuleLoader::new(cm, env, NodeResolver, DefaultFileLoader)),
+                    Box::new(ModuleLoader::new(cm, env, NodeResolver::new(), DefaultFileLoader)),
                 );
 
                 checker.check(Arc::new(FileName::Real(path)));
@@ -142,9 +175,21 @@ async fn main() -> Result<(), Error> {
                 log::info!("Error reporting took {:?}", end - start);
             }
         }
+        Command::Check(cmd) => {
+            cmd.run(cm, handler, globals)?;
+        }
+        Command::Build(cmd) => {
+            cmd.run(cm, handler, globals)?;
+        }
+        Command::Cache(cmd) => {
+            cmd.run()?;
+        }
         Command::Lsp(cmd) => {
             cmd.run().await?;
         }
+        Command::Api(cmd) => {
+            cmd.run(cm, handler, globals)?;
+        }
     }
 
     let end = Instant::now();
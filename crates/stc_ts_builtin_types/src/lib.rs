@@ -30,6 +30,16 @@ impl Lib {
         lib.load_deps()
     }
 
+    /// Returns `self` and all libraries referenced by `self`.
+    pub fn with_deps(self) -> Vec<Self> {
+        self.load_deps()
+    }
+
+    /// Content of the `.d.ts` file.
+    pub fn source(self) -> &'static str {
+        self.content()
+    }
+
     fn body(self) -> &'static TsNamespaceDecl {
         static CACHE: Lazy<RwLock<FxHashMap<Lib, &'static TsNamespaceDecl>>> = Lazy::new(Default::default);
 
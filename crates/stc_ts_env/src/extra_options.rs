@@ -0,0 +1,117 @@
+use std::{fs, path::Path};
+
+use json_comments::StripComments;
+use serde::Deserialize;
+
+/// Options of `compilerOptions` which are not supported by
+/// [tsconfig::CompilerOptions].
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraCompilerOptions {
+    #[serde(default)]
+    pub verbatim_module_syntax: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTsConfig {
+    /// Can be an array since TypeScript 5.0.
+    #[serde(default)]
+    extends: Option<serde_json::Value>,
+    #[serde(default)]
+    compiler_options: Option<ExtraCompilerOptions>,
+}
+
+impl ExtraCompilerOptions {
+    /// Reads the options from the content of a `tsconfig.json`.
+    pub fn parse_str(json: &str) -> Self {
+        parse(json).and_then(|config| config.compiler_options).unwrap_or_default()
+    }
+
+    /// Reads the options from a `tsconfig.json`, including the ones inherited
+    /// using a relative path in `extends`.
+    pub fn parse_file(path: &Path) -> Self {
+        Self::parse_file_inner(path, 0)
+    }
+
+    fn parse_file_inner(path: &Path, depth: usize) -> Self {
+        // Prevent infinite recursion for cyclic `extends`.
+        if depth > 32 {
+            return Default::default();
+        }
+
+        let config = match fs::read_to_string(path).ok().and_then(|s| parse(&s)) {
+            Some(v) => v,
+            None => return Default::default(),
+        };
+        let options = config.compiler_options.unwrap_or_default();
+
+        let base = match (config.extends.as_ref().and_then(|v| v.as_str()), path.parent()) {
+            (Some(extends), Some(dir)) if extends.starts_with('.') => {
+                let mut base = dir.join(extends);
+                if base.extension().is_none() {
+                    base.set_extension("json");
+                }
+                Self::parse_file_inner(&base, depth + 1)
+            }
+            _ => Default::default(),
+        };
+
+        Self {
+            verbatim_module_syntax: options.verbatim_module_syntax.or(base.verbatim_module_syntax),
+        }
+    }
+}
+
+fn parse(json: &str) -> Option<RawTsConfig> {
+    serde_json::from_reader(StripComments::new(json.as_bytes())).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn parse_str_with_comments() {
+        let options = ExtraCompilerOptions::parse_str(
+            r#"{
+                // Comment
+                "compilerOptions": {
+                    /* Comment */
+                    "verbatimModuleSyntax": true
+                }
+            }"#,
+        );
+
+        assert_eq!(options.verbatim_module_syntax, Some(true));
+    }
+
+    #[test]
+    fn parse_file_with_extends() {
+        let dir = env::temp_dir().join("stc-extra-options");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("base.json"), r#"{ "compilerOptions": { "verbatimModuleSyntax": true } }"#).unwrap();
+        fs::write(
+            dir.join("tsconfig.json"),
+            r#"{ "extends": "./base", "compilerOptions": { "strict": true } }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("tsconfig.override.json"),
+            r#"{ "extends": "./base.json", "compilerOptions": { "verbatimModuleSyntax": false } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ExtraCompilerOptions::parse_file(&dir.join("tsconfig.json")).verbatim_module_syntax,
+            Some(true)
+        );
+        assert_eq!(
+            ExtraCompilerOptions::parse_file(&dir.join("tsconfig.override.json")).verbatim_module_syntax,
+            Some(false)
+        );
+    }
+}
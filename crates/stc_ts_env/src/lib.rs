@@ -3,29 +3,49 @@ use std::{str::FromStr, sync::Arc};
 use parking_lot::Mutex;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use stc_ts_builtin_types::Lib;
 use stc_ts_errors::{Error, ErrorKind};
 use stc_ts_type_ops::Fix;
-use stc_ts_types::{Id, Type};
+use stc_ts_types::{Id, ModuleTypeData, Type};
 use stc_utils::{cache::Freeze, dev_span};
 use string_enum::StringEnum;
 use swc_atoms::JsWord;
-use swc_common::{Span, Spanned, DUMMY_SP};
+use swc_common::{Span, DUMMY_SP};
 use swc_ecma_ast::EsVersion;
 use tsconfig::{CompilerOptions, Jsx, Module};
 
-pub use self::marks::{MarkExt, Marks};
+pub use self::{
+    extra_options::ExtraCompilerOptions,
+    marks::{MarkExt, Marks},
+};
 
+mod extra_options;
 mod marks;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct BuiltIn {
     vars: FxHashMap<JsWord, Type>,
     types: FxHashMap<JsWord, Type>,
+    /// Libraries used to create this.
+    #[serde(skip)]
+    libs: Vec<Lib>,
 }
 
 impl BuiltIn {
     pub fn new(vars: FxHashMap<JsWord, Type>, types: FxHashMap<JsWord, Type>) -> Self {
-        BuiltIn { vars, types }
+        BuiltIn {
+            vars,
+            types,
+            libs: Default::default(),
+        }
+    }
+
+    pub fn libs(&self) -> &[Lib] {
+        &self.libs
+    }
+
+    pub fn set_libs(&mut self, libs: Vec<Lib>) {
+        self.libs = libs;
     }
 }
 
@@ -70,6 +90,24 @@ impl Env {
         self.rule
     }
 
+    pub fn builtin(&self) -> &Arc<BuiltIn> {
+        &self.builtin
+    }
+
+    /// Returns a new environment using `builtin`.
+    ///
+    /// Global declarations of `self` are merged with the previous builtin
+    /// types, so the returned environment starts without them. Files declaring
+    /// them should be analyzed again.
+    pub fn with_builtin(&self, builtin: Arc<BuiltIn>) -> Self {
+        Self {
+            builtin,
+            global_types: Default::default(),
+            global_vars: Default::default(),
+            ..self.clone()
+        }
+    }
+
     pub fn declare_global_var(&mut self, name: JsWord, ty: Type) {
         ty.assert_clone_cheap();
 
@@ -81,18 +119,41 @@ impl Env {
     pub fn declare_global_type(&mut self, name: JsWord, ty: Type) {
         ty.assert_clone_cheap();
 
-        match self.get_global_type(ty.span(), &name) {
-            Ok(prev_ty) => {
-                self.global_types
-                    .lock()
-                    .insert(name, Type::new_intersection(DUMMY_SP, vec![prev_ty, ty]).fixed().freezed());
-            }
-            Err(_) => {
-                self.global_types.lock().insert(name, ty);
-            }
+        // Global declarations may come from multiple files, so the lock is held
+        // while merging to prevent losing a declaration.
+        let mut global_types = self.global_types.lock();
+
+        let prev_ty = global_types.get(&name).or_else(|| self.builtin.types.get(&name)).cloned();
+        let ty = match prev_ty {
+            Some(prev_ty) => merge_global_types(prev_ty, ty),
+            None => ty,
+        };
+
+        global_types.insert(name, ty);
+    }
+
+    /// Returns global types and variables declared by files, excluding the
+    /// builtin ones.
+    pub fn global_declarations(&self) -> ModuleTypeData {
+        ModuleTypeData {
+            types: self
+                .global_types
+                .lock()
+                .iter()
+                .map(|(name, ty)| (name.clone(), vec![ty.clone()]))
+                .collect(),
+            vars: self.global_vars.lock().clone(),
+            ..Default::default()
         }
     }
 
+    /// Removes global declarations of all environments sharing them with
+    /// `self`, so that they can be declared again.
+    pub fn clear_global_declarations(&self) {
+        self.global_types.lock().clear();
+        self.global_vars.lock().clear();
+    }
+
     pub fn get_global_var(&self, span: Span, name: &JsWord) -> Result<Type, Error> {
         let _tracing = dev_span!("get_global_var");
 
@@ -134,6 +195,22 @@ impl Env {
     }
 }
 
+/// Merges two declarations of a global type.
+///
+/// Namespaces are merged into a single module so that their members can be
+/// accessed, and other declarations are merged using an intersection.
+fn merge_global_types(prev: Type, ty: Type) -> Type {
+    match (prev.normalize(), ty.normalize()) {
+        (Type::Module(prev), Type::Module(new)) => {
+            let mut exports = prev.exports.clone();
+            exports.merge((*new.exports).clone());
+
+            Type::Module(stc_ts_types::Module { exports, ..prev.clone() }).freezed()
+        }
+        _ => Type::new_intersection(DUMMY_SP, vec![prev, ty]).fixed().freezed(),
+    }
+}
+
 /// Stuffs which are not changed regardless
 #[derive(Clone, Debug)]
 pub struct StableEnv {
@@ -191,12 +268,18 @@ pub struct Rule {
     pub always_strict: bool,
     pub strict_null_checks: bool,
     pub strict_function_types: bool,
+    pub use_unknown_in_catch_variables: bool,
+    pub strict_property_initialization: bool,
 
     pub allow_unreachable_code: bool,
-    pub allow_unused_labels: bool,
+    /// Unused labels are reported only if this is `Some(false)`, like `tsc`.
+    pub allow_unused_labels: Option<bool>,
     pub no_fallthrough_cases_in_switch: bool,
     pub no_implicit_returns: bool,
+    pub no_implicit_override: bool,
     pub no_unchecked_indexed_access: bool,
+    pub no_property_access_from_index_signature: bool,
+    pub exact_optional_property_types: bool,
     pub suppress_excess_property_errors: bool,
     pub suppress_implicit_any_index_errors: bool,
     pub no_strict_generic_checks: bool,
@@ -204,6 +287,14 @@ pub struct Rule {
     pub no_unused_parameters: bool,
     pub use_define_property_for_class_fields: bool,
     pub no_lib: bool,
+    pub allow_js: bool,
+    /// Report errors in JavaScript files, even without `// @ts-check`.
+    pub check_js: bool,
+    pub isolated_modules: bool,
+    pub verbatim_module_syntax: bool,
+    /// Check decorators with the legacy signatures, instead of the ones of
+    /// TC39 decorators.
+    pub experimental_decorators: bool,
 
     pub jsx: JsxMode,
 }
@@ -218,25 +309,46 @@ impl From<&CompilerOptions> for Rule {
             always_strict: v.always_strict.unwrap_or(strict),
             strict_null_checks: v.strict_null_checks.unwrap_or(strict),
             strict_function_types: v.strict_function_types.unwrap_or(strict),
+            use_unknown_in_catch_variables: v.use_unknown_in_catch_variables.unwrap_or(strict),
+            strict_property_initialization: v.strict_property_initialization.unwrap_or(strict),
 
             allow_unreachable_code: v.allow_unreachable_code.unwrap_or_default(),
-            allow_unused_labels: v.allow_unused_labels.unwrap_or_default(),
+            allow_unused_labels: v.allow_unused_labels,
             no_fallthrough_cases_in_switch: v.no_fallthrough_cases_in_switch.unwrap_or_default(),
             no_implicit_returns: v.no_implicit_returns.unwrap_or_default(),
-            no_unchecked_indexed_access: false,
+            no_implicit_override: v.no_implicit_override.unwrap_or_default(),
+            no_unchecked_indexed_access: v.no_unchecked_indexed_access.unwrap_or_default(),
+            no_property_access_from_index_signature: v.no_property_access_from_index_signature.unwrap_or_default(),
+            exact_optional_property_types: v.exact_optional_property_types.unwrap_or_default(),
             suppress_excess_property_errors: v.suppress_excess_property_errors.unwrap_or_default(),
             suppress_implicit_any_index_errors: v.suppress_implicit_any_index_errors.unwrap_or_default(),
             no_strict_generic_checks: v.no_strict_generic_checks.unwrap_or_default(),
             no_unused_locals: v.no_unused_locals.unwrap_or_default(),
-            no_unused_parameters: v.no_unused_locals.unwrap_or_default(),
+            no_unused_parameters: v.no_unused_parameters.unwrap_or_default(),
             use_define_property_for_class_fields: v.use_define_for_class_fields.unwrap_or_default(),
             no_lib: v.no_lib.unwrap_or_default(),
+            allow_js: v.allow_js.unwrap_or_default(),
+            check_js: v.check_js.unwrap_or_default(),
+            isolated_modules: v.isolated_modules.unwrap_or_default(),
+            // Not supported by `tsconfig`. See [Rule::with_extra_options].
+            verbatim_module_syntax: false,
+            experimental_decorators: v.experimental_decorators.unwrap_or_default(),
 
             jsx: v.jsx.map(From::from).unwrap_or_default(),
         }
     }
 }
 
+impl Rule {
+    /// Applies the options which are not supported by [CompilerOptions].
+    pub fn with_extra_options(self, options: ExtraCompilerOptions) -> Self {
+        Self {
+            verbatim_module_syntax: options.verbatim_module_syntax.unwrap_or(self.verbatim_module_syntax),
+            ..self
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub enum JsxMode {
     #[default]
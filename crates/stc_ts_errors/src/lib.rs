@@ -17,7 +17,7 @@ use fmt::Formatter;
 use scoped_tls::scoped_thread_local;
 use static_assertions::assert_eq_size;
 use stc_ts_types::{name::Name, Id, Key, ModuleId, Type, TypeElement, TypeParamInstantiation};
-use stc_utils::stack::StackOverflowError;
+use stc_utils::{instantiation::InstantiationTooDeepError, stack::StackOverflowError};
 use swc_atoms::JsWord;
 use swc_common::{
     errors::{DiagnosticId, Handler},
@@ -83,9 +83,14 @@ impl Error {
     pub fn emit(&self, h: &Handler) {
         let span = self.span();
 
+        let msg = match &*self.inner {
+            ErrorKind::Restored { message, .. } => message.to_string(),
+            _ => format!("{:#?}", self),
+        };
+
         let mut err = h.struct_span_err_with_code(
             span,
-            &format!("{:#?}", self),
+            &msg,
             DiagnosticId::Error(format!("TS{}", ErrorKind::normalize_error_code(self.code()))),
         );
 
@@ -231,6 +236,16 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS18033
+    BigIntInEnum {
+        span: Span,
+    },
+
+    /// TS2473
+    EnumConstMismatch {
+        span: Span,
+    },
+
     /// TS7027
     UnreachableCode {
         span: Span,
@@ -310,6 +325,46 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS1343
+    ImportMetaNotAllowed {
+        span: Span,
+    },
+
+    /// TS1206
+    DecoratorNotValidHere {
+        span: Span,
+    },
+
+    /// TS1238
+    InvalidClassDecorator {
+        span: Span,
+    },
+
+    /// TS1239
+    InvalidParamDecorator {
+        span: Span,
+    },
+
+    /// TS1240
+    InvalidPropertyDecorator {
+        span: Span,
+    },
+
+    /// TS1241
+    InvalidMethodDecorator {
+        span: Span,
+    },
+
+    /// TS1270
+    DecoratorReturnTypeMismatch {
+        span: Span,
+    },
+
+    /// TS1271
+    DecoratorReturnTypeNotVoid {
+        span: Span,
+    },
+
     /// TS2767
     ReturnPropertyOfIteratorMustBeMethod {
         span: Span,
@@ -1126,6 +1181,22 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS2375
+    ///
+    /// `undefined` is assigned to an optional property which does not accept
+    /// `undefined` with `exactOptionalPropertyTypes`.
+    ExactOptionalPropertyTypeMismatch {
+        span: Span,
+    },
+
+    /// TS2412
+    ///
+    /// `undefined` is written to an optional property which does not accept
+    /// `undefined` with `exactOptionalPropertyTypes`.
+    ExactOptionalPropertyWriteMismatch {
+        span: Span,
+    },
+
     ObjectAssignFailed {
         span: Span,
         errors: Vec<Error>,
@@ -1575,6 +1646,21 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS4112
+    OverrideInClassWithoutSuper {
+        span: Span,
+    },
+
+    /// TS4114
+    MissingOverrideModifier {
+        span: Span,
+    },
+
+    /// TS4115
+    ParamPropMissingOverrideModifier {
+        span: Span,
+    },
+
     /// TS2428
     InterfaceNonIdenticalTypeParams {
         span: Span,
@@ -1589,6 +1675,108 @@ pub enum ErrorKind {
     RestParamMustBeLast {
         span: Span,
     },
+
+    /// TS1196
+    InvalidCatchVarTypeAnn {
+        span: Span,
+    },
+
+    /// TS2578
+    UnusedTsExpectError {
+        span: Span,
+    },
+
+    /// TS1205
+    ReExportTypeWithoutExportType {
+        span: Span,
+    },
+
+    /// TS1208
+    GlobalScriptWithIsolatedModules {
+        span: Span,
+    },
+
+    /// TS2748
+    AmbientConstEnumWithIsolatedModules {
+        span: Span,
+    },
+
+    /// TS1484
+    TypeImportWithoutImportType {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS2636
+    VarianceAnnotationMismatch {
+        span: Span,
+    },
+
+    /// TS2637
+    VarianceAnnotationOnNonObjectType {
+        span: Span,
+    },
+
+    /// TS2589
+    InstantiationTooDeep {
+        span: Span,
+    },
+
+    /// TS2775
+    AssertionTargetWithoutTypeAnn {
+        span: Span,
+    },
+
+    /// TS2776
+    InvalidAssertionTarget {
+        span: Span,
+    },
+
+    /// TS4111
+    PropertyAccessFromIndexSignature {
+        span: Span,
+        prop: JsWord,
+    },
+
+    /// TS6133
+    UnusedDeclaration {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS6196
+    UnusedTypeDeclaration {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS6198
+    AllDestructuredElementsUnused {
+        span: Span,
+    },
+
+    /// TS7028
+    UnusedLabel {
+        span: Span,
+    },
+
+    /// TS7029
+    FallthroughCaseInSwitch {
+        span: Span,
+    },
+
+    /// TS7030
+    NotAllCodePathsReturnValue {
+        span: Span,
+    },
+
+    /// An error loaded from the incremental cache.
+    Restored {
+        span: Span,
+        code: usize,
+        /// Message of the original error.
+        message: Box<str>,
+    },
 }
 
 #[cfg(target_pointer_width = "64")]
@@ -1809,6 +1997,10 @@ impl ErrorKind {
             | ErrorKind::InvalidOpAssign { .. }
             | ErrorKind::TupleAssignError { .. } => 2322,
 
+            ErrorKind::ExactOptionalPropertyTypeMismatch { .. } => 2375,
+
+            ErrorKind::ExactOptionalPropertyWriteMismatch { .. } => 2412,
+
             ErrorKind::NonOverlappingTypeCast { .. } => 2352,
 
             ErrorKind::SuperInClassWithoutSuper { .. } => 2335,
@@ -1858,6 +2050,7 @@ impl ErrorKind {
             ErrorKind::CannotAssignAbstractConstructorToNonAbstractConstructor { .. } => 2322,
             ErrorKind::CannotCreateInstanceOfAbstractClass { .. } => 2511,
             ErrorKind::NotSatisfyConstraint { .. } => 2344,
+            ErrorKind::WrongArgType { inner, .. } if inner.is_exact_optional_property_type_mismatch() => 2379,
             ErrorKind::WrongArgType { .. } => 2345,
 
             ErrorKind::ComputedMemberInEnumWithStrMember { .. } => 2553,
@@ -2091,6 +2284,16 @@ impl ErrorKind {
 
             ErrorKind::InvalidUsageOfNewTarget { .. } => 17013,
 
+            ErrorKind::ImportMetaNotAllowed { .. } => 1343,
+
+            ErrorKind::DecoratorNotValidHere { .. } => 1206,
+            ErrorKind::InvalidClassDecorator { .. } => 1238,
+            ErrorKind::InvalidParamDecorator { .. } => 1239,
+            ErrorKind::InvalidPropertyDecorator { .. } => 1240,
+            ErrorKind::InvalidMethodDecorator { .. } => 1241,
+            ErrorKind::DecoratorReturnTypeMismatch { .. } => 1270,
+            ErrorKind::DecoratorReturnTypeNotVoid { .. } => 1271,
+
             ErrorKind::AssignFailedBecauseTupleLengthDiffers { .. } => 2322,
 
             ErrorKind::ClassMemberNotCompatibleWithStringIndexSignature { .. } => 2411,
@@ -2143,6 +2346,10 @@ impl ErrorKind {
 
             ErrorKind::ConstEnumMemberHasNaNAsInit { .. } => 2478,
 
+            ErrorKind::BigIntInEnum { .. } => 18033,
+
+            ErrorKind::EnumConstMismatch { .. } => 2473,
+
             ErrorKind::OnlyOneEnumCanOmitInit { .. } => 2432,
 
             ErrorKind::CannotUseTypeAsIndexIndex { .. } => 2538,
@@ -2195,12 +2402,56 @@ impl ErrorKind {
 
             ErrorKind::NotDeclaredInSuperClass { .. } => 4113,
 
+            ErrorKind::OverrideInClassWithoutSuper { .. } => 4112,
+
+            ErrorKind::MissingOverrideModifier { .. } => 4114,
+
+            ErrorKind::ParamPropMissingOverrideModifier { .. } => 4115,
+
             ErrorKind::InterfaceNonIdenticalTypeParams { .. } => 2428,
 
             ErrorKind::ThisNotAllowedInAccessor { .. } => 2784,
 
             ErrorKind::RestParamMustBeLast { .. } => 1014,
 
+            ErrorKind::InvalidCatchVarTypeAnn { .. } => 1196,
+
+            ErrorKind::UnusedTsExpectError { .. } => 2578,
+
+            ErrorKind::ReExportTypeWithoutExportType { .. } => 1205,
+
+            ErrorKind::GlobalScriptWithIsolatedModules { .. } => 1208,
+
+            ErrorKind::AmbientConstEnumWithIsolatedModules { .. } => 2748,
+
+            ErrorKind::TypeImportWithoutImportType { .. } => 1484,
+
+            ErrorKind::VarianceAnnotationMismatch { .. } => 2636,
+
+            ErrorKind::VarianceAnnotationOnNonObjectType { .. } => 2637,
+
+            ErrorKind::InstantiationTooDeep { .. } => 2589,
+
+            ErrorKind::AssertionTargetWithoutTypeAnn { .. } => 2775,
+
+            ErrorKind::InvalidAssertionTarget { .. } => 2776,
+
+            ErrorKind::PropertyAccessFromIndexSignature { .. } => 4111,
+
+            ErrorKind::UnusedDeclaration { .. } => 6133,
+
+            ErrorKind::UnusedTypeDeclaration { .. } => 6196,
+
+            ErrorKind::AllDestructuredElementsUnused { .. } => 6198,
+
+            ErrorKind::UnusedLabel { .. } => 7028,
+
+            ErrorKind::FallthroughCaseInSwitch { .. } => 7029,
+
+            ErrorKind::NotAllCodePathsReturnValue { .. } => 7030,
+
+            ErrorKind::Restored { code, .. } => *code,
+
             _ => 0,
         }
     }
@@ -2226,6 +2477,23 @@ impl ErrorKind {
         self.code() == 2540
     }
 
+    /// Returns `true` if `self` is caused only by `exactOptionalPropertyTypes`,
+    /// i.e. the assignment would succeed if optional properties accepted
+    /// `undefined`.
+    pub fn is_exact_optional_property_type_mismatch(&self) -> bool {
+        match self {
+            ErrorKind::ExactOptionalPropertyTypeMismatch { .. } => true,
+            ErrorKind::AssignFailed { cause: errors, .. }
+            | ErrorKind::ObjectAssignFailed { errors, .. }
+            | ErrorKind::SimpleAssignFailedWithCause { cause: errors, .. }
+            | ErrorKind::Errors { errors, .. } => {
+                !errors.is_empty() && errors.iter().all(|err| err.is_exact_optional_property_type_mismatch())
+            }
+            ErrorKind::SimpleAssignFailed { cause: Some(cause), .. } => cause.is_exact_optional_property_type_mismatch(),
+            _ => false,
+        }
+    }
+
     pub fn is_assign_failure(&self) -> bool {
         self.code() == 2322
     }
@@ -2355,3 +2623,15 @@ impl From<StackOverflowError> for Error {
         ErrorKind::from(e).into()
     }
 }
+
+impl From<InstantiationTooDeepError> for ErrorKind {
+    fn from(e: InstantiationTooDeepError) -> Self {
+        ErrorKind::InstantiationTooDeep { span: e.span }
+    }
+}
+
+impl From<InstantiationTooDeepError> for Error {
+    fn from(e: InstantiationTooDeepError) -> Self {
+        ErrorKind::from(e).into()
+    }
+}
@@ -34,6 +34,7 @@ mod tests;
 mod tpl;
 mod type_el;
 mod unions;
+mod variance;
 
 /// Context used for `=` assignments.
 #[derive(Debug, Clone, Copy, Default)]
@@ -445,6 +446,13 @@ impl Analyzer<'_, '_> {
         Err(ErrorKind::AssignOpCannotBeApplied { span, op }.into())
     }
 
+    /// Returns `true` if `right` is assignable to `left`.
+    ///
+    /// This is used by tools which compare types, and errors are not reported.
+    pub fn is_assignable(&mut self, span: Span, left: &Type, right: &Type) -> bool {
+        self.assign(span, &mut Default::default(), left, right).is_ok()
+    }
+
     /// Assign `right` to `left`. You can just use default for [AssignData].
     pub(crate) fn assign(&mut self, span: Span, data: &mut AssignData, left: &Type, right: &Type) -> VResult<()> {
         self.assign_with_opts(
@@ -484,6 +492,7 @@ impl Analyzer<'_, '_> {
         }
 
         res.convert_err(|err| match err {
+            _ if err.is_exact_optional_property_type_mismatch() => ErrorKind::ExactOptionalPropertyTypeMismatch { span: opts.span },
             ErrorKind::AssignFailed { .. }
             | ErrorKind::Errors { .. }
             | ErrorKind::Unimplemented { .. }
@@ -913,6 +922,10 @@ impl Analyzer<'_, '_> {
                     if left.type_name.type_eq(&right.type_name) && left.type_args.type_eq(&right.type_args) {
                         return Ok(());
                     }
+
+                    if let Some(res) = self.assign_ref_using_variance(data, left, right, opts) {
+                        return res;
+                    }
                 }
 
                 let new_lhs = self.expand_top_ref(span, Cow::Borrowed(to), Default::default())?.freezed();
@@ -2601,17 +2614,31 @@ impl Analyzer<'_, '_> {
             ) => fail!(),
 
             (
-                Type::Optional(..),
+                Type::Optional(l_opt),
                 Type::Keyword(KeywordType {
                     kind: TsKeywordTypeKind::TsUndefinedKeyword,
                     ..
                 }),
-            ) => return Ok(()),
+            ) => {
+                if self.rule().exact_optional_property_types && !self.is_assignable(span, &l_opt.ty, rhs) {
+                    return Err(ErrorKind::ExactOptionalPropertyTypeMismatch { span }.into());
+                }
+
+                return Ok(());
+            }
 
             (Type::Optional(l_opt), _) => {
+                // With `exactOptionalPropertyTypes`, an optional element may be missing, but it
+                // can't be `undefined`.
+                if self.rule().exact_optional_property_types {
+                    return self
+                        .assign_inner(data, &l_opt.ty, rhs, opts)
+                        .context("tried to assign to an optional type with exactOptionalPropertyTypes");
+                }
+
                 return self
                     .assign_inner(data, &l_opt.ty.clone().union_with_undefined(span), rhs, opts)
-                    .context("tried to assign to an optional type")
+                    .context("tried to assign to an optional type");
             }
 
             (_, Type::Readonly(r_readonly)) => {
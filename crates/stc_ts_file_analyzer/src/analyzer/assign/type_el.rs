@@ -1219,6 +1219,18 @@ impl Analyzer<'_, '_> {
                                             }
                                         }
 
+                                        if lp.optional && !opts.for_castablity && self.rule().exact_optional_property_types {
+                                            if let (Some(l_ty), Some(r_ty)) = (&lp.type_ann, &rp.type_ann) {
+                                                let undefined = Type::undefined(span, Default::default());
+
+                                                if r_ty.iter_union().any(|ty| ty.is_undefined())
+                                                    && !self.is_assignable(span, l_ty, &undefined)
+                                                {
+                                                    return Err(ErrorKind::ExactOptionalPropertyTypeMismatch { span }.into());
+                                                }
+                                            }
+                                        }
+
                                         // Allow assigning undefined to optional properties.
                                         (|| {
                                             if opts.for_castablity {
@@ -0,0 +1,169 @@
+//! Variance annotations of type parameters, like `in T` and `out T`.
+
+use fxhash::FxHashMap;
+use stc_ts_ast_rnode::{RTsEntityName, RTsType};
+use stc_ts_errors::{DebugExt, ErrorKind};
+use stc_ts_types::{Alias, Id, Interface, Ref, Type, TypeLit, TypeParam, TypeParamDecl};
+use swc_common::TypeEq;
+
+use crate::{
+    analyzer::{
+        assign::{AssignData, AssignOpts},
+        Analyzer,
+    },
+    VResult,
+};
+
+impl Analyzer<'_, '_> {
+    /// Assigns `r` to `l` by comparing type arguments with the declared
+    /// variance, if both of them are references to the same generic type and
+    /// all type parameters of it have variance annotations.
+    pub(super) fn assign_ref_using_variance(&mut self, data: &mut AssignData, l: &Ref, r: &Ref, opts: AssignOpts) -> Option<VResult<()>> {
+        if !l.type_name.type_eq(&r.type_name) {
+            return None;
+        }
+
+        let (l_args, r_args) = match (&l.type_args, &r.type_args) {
+            (Some(l_args), Some(r_args)) if l_args.params.len() == r_args.params.len() => (l_args, r_args),
+            _ => return None,
+        };
+
+        let name = match &l.type_name {
+            RTsEntityName::Ident(i) => Id::from(i),
+            _ => return None,
+        };
+
+        let variance = self.declared_variance(&name)?;
+        if variance.len() != l_args.params.len() {
+            return None;
+        }
+
+        Some(self.assign_type_args_using_variance(data, &variance, &l_args.params, &r_args.params, opts))
+    }
+
+    fn assign_type_args_using_variance(
+        &mut self,
+        data: &mut AssignData,
+        variance: &[(bool, bool)],
+        l_args: &[Type],
+        r_args: &[Type],
+        opts: AssignOpts,
+    ) -> VResult<()> {
+        for (&(is_in, is_out), (l, r)) in variance.iter().zip(l_args.iter().zip(r_args.iter())) {
+            if is_out {
+                self.assign_with_opts(data, l, r, opts)
+                    .context("tried to assign a type argument for an `out` type parameter")?;
+            }
+
+            if is_in {
+                self.assign_with_opts(data, r, l, opts)
+                    .context("tried to assign a type argument for an `in` type parameter")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `(is_in, is_out)` for each type parameter of the type named
+    /// `name`, or [None] if any of them does not have a variance annotation.
+    fn declared_variance(&mut self, name: &Id) -> Option<Vec<(bool, bool)>> {
+        let types = self.find_type(name).ok()??;
+
+        for ty in types {
+            if let Some(decl) = ty.get_type_param_decl() {
+                if decl.params.iter().all(|p| p.metadata.is_in || p.metadata.is_out) {
+                    return Some(decl.params.iter().map(|p| (p.metadata.is_in, p.metadata.is_out)).collect());
+                }
+
+                return None;
+            }
+        }
+
+        None
+    }
+
+    /// Reports `TS2637` if a type alias with variance annotations is not an
+    /// object, function, constructor or mapped type, and `TS2636` if the
+    /// annotations do not match the usages of the type parameters.
+    pub(crate) fn report_error_for_variance_annotations_of_alias(&mut self, type_ann: &RTsType, alias: &Alias) {
+        let type_params = match &alias.type_params {
+            Some(type_params) if type_params.params.iter().any(|p| p.metadata.is_in || p.metadata.is_out) => type_params,
+            _ => return,
+        };
+
+        if !matches!(
+            type_ann,
+            RTsType::TsTypeLit(..) | RTsType::TsFnOrConstructorType(..) | RTsType::TsMappedType(..)
+        ) {
+            for param in type_params.params.iter().filter(|p| p.metadata.is_in || p.metadata.is_out) {
+                self.storage
+                    .report(ErrorKind::VarianceAnnotationOnNonObjectType { span: param.span }.into());
+            }
+            return;
+        }
+
+        self.report_error_for_inconsistent_variance(type_params, &alias.ty);
+    }
+
+    /// Reports `TS2636` if the variance annotations of `i` do not match the
+    /// usages of the type parameters.
+    pub(crate) fn report_error_for_variance_annotations_of_interface(&mut self, i: &Interface) {
+        let type_params = match &i.type_params {
+            Some(type_params) if type_params.params.iter().any(|p| p.metadata.is_in || p.metadata.is_out) => type_params,
+            _ => return,
+        };
+
+        let ty = Type::TypeLit(TypeLit {
+            span: i.span,
+            members: i.body.clone(),
+            metadata: Default::default(),
+            tracker: Default::default(),
+        });
+
+        self.report_error_for_inconsistent_variance(type_params, &ty);
+    }
+
+    /// Instantiates `ty` with a marker type and a subtype of it, and checks if
+    /// the instantiations are assignable in the direction implied by the
+    /// variance annotation.
+    fn report_error_for_inconsistent_variance(&mut self, type_params: &TypeParamDecl, ty: &Type) {
+        for param in type_params.params.iter().filter(|p| p.metadata.is_in || p.metadata.is_out) {
+            let super_marker = TypeParam {
+                span: param.span,
+                name: Id::new(format!("super-{}", param.name.sym()).into(), param.name.ctxt()),
+                constraint: None,
+                default: None,
+                metadata: Default::default(),
+                tracker: Default::default(),
+            };
+            let sub_marker = TypeParam {
+                name: Id::new(format!("sub-{}", param.name.sym()).into(), param.name.ctxt()),
+                constraint: Some(Box::new(Type::Param(super_marker.clone()))),
+                ..super_marker.clone()
+            };
+
+            let mut instantiate = |marker: TypeParam| {
+                let mut map = FxHashMap::default();
+                map.insert(param.name.clone(), Type::Param(marker));
+                self.expand_type_params(&map, ty.clone(), Default::default())
+            };
+            let (sub, sup) = match (instantiate(sub_marker), instantiate(super_marker)) {
+                (Ok(sub), Ok(sup)) => (sub, sup),
+                _ => continue,
+            };
+
+            let opts = AssignOpts {
+                span: param.span,
+                ..Default::default()
+            };
+
+            let is_valid = (!param.metadata.is_out || self.assign_with_opts(&mut Default::default(), &sup, &sub, opts).is_ok())
+                && (!param.metadata.is_in || self.assign_with_opts(&mut Default::default(), &sub, &sup, opts).is_ok());
+
+            if !is_valid {
+                self.storage
+                    .report(ErrorKind::VarianceAnnotationMismatch { span: param.span }.into());
+            }
+        }
+    }
+}
@@ -0,0 +1,385 @@
+//! Decorators of classes and class members.
+
+use std::borrow::Cow;
+
+use rnode::NodeId;
+use stc_arc_cow::ArcCow;
+use stc_ts_ast_rnode::{
+    RBindingIdent, RClass, RClassMember, RDecorator, RIdent, RNumber, RParamOrTsParamProp, RPat, RStr, RTsEntityName, RTsLit,
+};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::{Class, ClassDef, ClassMember, FnParam, Function, Key, LitType, Ref, Type, TypeParamInstantiation};
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::{MethodKind, TsKeywordTypeKind};
+
+use crate::{
+    analyzer::{assign::AssignOpts, util::ResultExt, Analyzer},
+    validator::ValidateWith,
+};
+
+/// Used to select the error code for a decorator which cannot be called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DecoratorTarget {
+    Class,
+    Method,
+    Property,
+    Param,
+}
+
+/// Returns `true` if `m` or one of its parameters has a decorator.
+pub(super) fn has_decorators(m: &RClassMember) -> bool {
+    match m {
+        RClassMember::Method(m) => !m.function.decorators.is_empty() || m.function.params.iter().any(|p| !p.decorators.is_empty()),
+        RClassMember::PrivateMethod(m) => !m.function.decorators.is_empty() || m.function.params.iter().any(|p| !p.decorators.is_empty()),
+        RClassMember::ClassProp(p) => !p.decorators.is_empty(),
+        RClassMember::PrivateProp(p) => !p.decorators.is_empty(),
+        RClassMember::AutoAccessor(p) => !p.decorators.is_empty(),
+        RClassMember::Constructor(c) => c.params.iter().any(|p| match p {
+            RParamOrTsParamProp::TsParamProp(p) => !p.decorators.is_empty(),
+            RParamOrTsParamProp::Param(p) => !p.decorators.is_empty(),
+        }),
+        _ => false,
+    }
+}
+
+impl Analyzer<'_, '_> {
+    /// Validates decorators of `c`, which is validated as `class`.
+    pub(super) fn validate_decorators_of_class(&mut self, c: &RClass, class: &ArcCow<ClassDef>) {
+        if c.decorators.is_empty() {
+            return;
+        }
+
+        let span = c.span;
+        let class_ty = Type::ClassDef(class.clone());
+
+        let args = if self.rule().experimental_decorators {
+            Some(vec![class_ty.clone()])
+        } else {
+            self.decorator_type_ref(span, "ClassDecoratorContext", vec![class_ty.clone()])
+                .map(|context| vec![class_ty.clone(), context])
+        };
+        let sig = args.map(|args| (args, Type::new_union(span, vec![class_ty, Type::void(span, Default::default())])));
+
+        for d in &c.decorators {
+            self.validate_decorator(d, DecoratorTarget::Class, sig.clone());
+        }
+    }
+
+    /// Validates decorators of class members and their parameters.
+    ///
+    /// `members` are validated members of `c`, with their indices in
+    /// `c.body`.
+    pub(super) fn validate_decorators_of_class_members(&mut self, c: &RClass, members: &[(usize, ClassMember)], class: &ArcCow<ClassDef>) {
+        for (index, member) in members {
+            let (is_static, kind, decorators, params) = match &c.body[*index] {
+                RClassMember::Method(m) => (m.is_static, Some(m.kind), &*m.function.decorators, &*m.function.params),
+                RClassMember::PrivateMethod(m) => (m.is_static, Some(m.kind), &*m.function.decorators, &*m.function.params),
+                RClassMember::ClassProp(p) => (p.is_static, None, &*p.decorators, &[][..]),
+                RClassMember::PrivateProp(p) => (p.is_static, None, &*p.decorators, &[][..]),
+                RClassMember::AutoAccessor(p) => (p.is_static, None, &*p.decorators, &[][..]),
+                RClassMember::Constructor(cons) => {
+                    let class_ty = Type::ClassDef(class.clone());
+
+                    for (idx, param) in cons.params.iter().enumerate() {
+                        let decorators = match param {
+                            RParamOrTsParamProp::TsParamProp(p) => &p.decorators,
+                            RParamOrTsParamProp::Param(p) => &p.decorators,
+                        };
+                        let key_ty = Type::undefined(cons.span, Default::default());
+
+                        self.validate_param_decorators(decorators, &class_ty, &key_ty, idx);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let span = member.span();
+            let this_ty = if is_static {
+                Type::ClassDef(class.clone())
+            } else {
+                Type::Class(Class {
+                    span: class.span,
+                    def: class.clone(),
+                    metadata: Default::default(),
+                    tracker: Default::default(),
+                })
+            };
+
+            let value_ty = match member {
+                ClassMember::Method(m) => Type::Function(Function {
+                    span: m.span,
+                    type_params: m.type_params.clone(),
+                    params: m.params.clone(),
+                    ret_ty: m.ret_ty.clone(),
+                    metadata: Default::default(),
+                    tracker: Default::default(),
+                }),
+                ClassMember::Property(p) => p.value.as_deref().cloned().unwrap_or_else(|| Type::any(span, Default::default())),
+                _ => continue,
+            };
+
+            let key = member.key().map(Cow::into_owned);
+            let key_ty = match &key {
+                Some(Key::Normal { span, sym }) => Type::Lit(LitType {
+                    span: *span,
+                    lit: RTsLit::Str(RStr {
+                        span: *span,
+                        value: sym.clone(),
+                        raw: None,
+                    }),
+                    metadata: Default::default(),
+                    tracker: Default::default(),
+                }),
+                Some(Key::Num(n)) => Type::Lit(LitType {
+                    span: n.span,
+                    lit: RTsLit::Number(n.clone()),
+                    metadata: Default::default(),
+                    tracker: Default::default(),
+                }),
+                Some(Key::Computed(key)) => *key.ty.clone(),
+                _ => Type::any(span, Default::default()),
+            };
+
+            if self.rule().experimental_decorators {
+                if let Some(Key::Private(..)) = key {
+                    for d in decorators {
+                        self.storage.report(ErrorKind::DecoratorNotValidHere { span: d.span }.into());
+                        d.validate_with(self).report(&mut self.storage);
+                    }
+                } else if kind.is_some() {
+                    let descriptor = self.decorator_type_ref(span, "TypedPropertyDescriptor", vec![value_ty]);
+                    let sig = descriptor.map(|descriptor| {
+                        (
+                            vec![this_ty.clone(), key_ty.clone(), descriptor.clone()],
+                            Type::new_union(span, vec![descriptor, Type::void(span, Default::default())]),
+                        )
+                    });
+
+                    for d in decorators {
+                        self.validate_decorator(d, DecoratorTarget::Method, sig.clone());
+                    }
+                } else {
+                    let sig = (vec![this_ty.clone(), key_ty.clone()], Type::void(span, Default::default()));
+
+                    for d in decorators {
+                        self.validate_decorator(d, DecoratorTarget::Property, Some(sig.clone()));
+                    }
+                }
+
+                for (idx, param) in params.iter().enumerate() {
+                    self.validate_param_decorators(&param.decorators, &this_ty, &key_ty, idx);
+                }
+
+                continue;
+            }
+
+            let (target, sig) = match (&c.body[*index], kind) {
+                (_, Some(kind)) => {
+                    let (value_ty, context_name) = match kind {
+                        MethodKind::Method => (value_ty, "ClassMethodDecoratorContext"),
+                        MethodKind::Getter => (fn_type(span, vec![], value_ty), "ClassGetterDecoratorContext"),
+                        MethodKind::Setter => (
+                            fn_type(span, vec![value_ty], Type::void(span, Default::default())),
+                            "ClassSetterDecoratorContext",
+                        ),
+                    };
+
+                    let sig = self
+                        .decorator_type_ref(span, context_name, vec![this_ty, value_ty.clone()])
+                        .map(|context| {
+                            (
+                                vec![value_ty.clone(), context],
+                                Type::new_union(span, vec![value_ty, Type::void(span, Default::default())]),
+                            )
+                        });
+
+                    (DecoratorTarget::Method, sig)
+                }
+                (RClassMember::AutoAccessor(..), None) => {
+                    let target = self.decorator_type_ref(span, "ClassAccessorDecoratorTarget", vec![this_ty.clone(), value_ty.clone()]);
+                    let context = self.decorator_type_ref(span, "ClassAccessorDecoratorContext", vec![this_ty.clone(), value_ty.clone()]);
+                    let result = self.decorator_type_ref(span, "ClassAccessorDecoratorResult", vec![this_ty, value_ty]);
+
+                    let sig = match (target, context, result) {
+                        (Some(target), Some(context), Some(result)) => Some((
+                            vec![target, context],
+                            Type::new_union(span, vec![result, Type::void(span, Default::default())]),
+                        )),
+                        _ => None,
+                    };
+
+                    (DecoratorTarget::Property, sig)
+                }
+                (_, None) => {
+                    let initializer = fn_type(span, vec![value_ty.clone()], value_ty.clone());
+
+                    let sig = self
+                        .decorator_type_ref(span, "ClassFieldDecoratorContext", vec![this_ty, value_ty])
+                        .map(|context| {
+                            (
+                                vec![Type::undefined(span, Default::default()), context],
+                                Type::new_union(span, vec![initializer, Type::void(span, Default::default())]),
+                            )
+                        });
+
+                    (DecoratorTarget::Property, sig)
+                }
+            };
+
+            for d in decorators {
+                self.validate_decorator(d, target, sig.clone());
+            }
+
+            // Parameter decorators are not a part of TC39 decorators.
+            for param in params {
+                for d in &param.decorators {
+                    self.storage.report(ErrorKind::DecoratorNotValidHere { span: d.span }.into());
+                    d.validate_with(self).report(&mut self.storage);
+                }
+            }
+        }
+    }
+
+    /// Validates decorators of a parameter at `idx`.
+    fn validate_param_decorators(&mut self, decorators: &[RDecorator], target: &Type, key_ty: &Type, idx: usize) {
+        for d in decorators {
+            if !self.rule().experimental_decorators {
+                self.storage.report(ErrorKind::DecoratorNotValidHere { span: d.span }.into());
+                d.validate_with(self).report(&mut self.storage);
+                continue;
+            }
+
+            let index_ty = Type::Lit(LitType {
+                span: d.span,
+                lit: RTsLit::Number(RNumber {
+                    span: d.span,
+                    value: idx as f64,
+                    raw: None,
+                }),
+                metadata: Default::default(),
+                tracker: Default::default(),
+            });
+
+            let sig = (
+                vec![target.clone(), key_ty.clone(), index_ty],
+                Type::void(d.span, Default::default()),
+            );
+
+            self.validate_decorator(d, DecoratorTarget::Param, Some(sig));
+        }
+    }
+
+    /// Checks if `d` can be called with `args` and returns a type assignable to
+    /// `ret_ty`, where `sig` is `(args, ret_ty)`.
+    ///
+    /// If `sig` is [None], the decorator is validated without checking the
+    /// signature.
+    fn validate_decorator(&mut self, d: &RDecorator, target: DecoratorTarget, sig: Option<(Vec<Type>, Type)>) {
+        let span = d.expr.span();
+
+        let decorator_ty = match d.validate_with(self) {
+            Ok(ty) => ty,
+            Err(err) => {
+                self.storage.report(err);
+                return;
+            }
+        };
+
+        let (args, ret_ty) = match sig {
+            Some(sig) => sig,
+            None => return,
+        };
+
+        if decorator_ty.is_any() {
+            return;
+        }
+
+        let opts = AssignOpts {
+            span,
+            ..Default::default()
+        };
+
+        let expected = fn_type(span, args, Type::any(span, Default::default()));
+        if self
+            .assign_with_opts(&mut Default::default(), &expected, &decorator_ty, opts)
+            .is_err()
+        {
+            let err = match target {
+                DecoratorTarget::Class => ErrorKind::InvalidClassDecorator { span },
+                DecoratorTarget::Method => ErrorKind::InvalidMethodDecorator { span },
+                DecoratorTarget::Property => ErrorKind::InvalidPropertyDecorator { span },
+                DecoratorTarget::Param => ErrorKind::InvalidParamDecorator { span },
+            };
+            self.storage.report(err.into());
+            return;
+        }
+
+        // We don't infer type arguments here, so the return type is checked only for
+        // non-generic decorators.
+        let actual_ret_ty = match self.normalize(Some(span), Cow::Borrowed(&decorator_ty), Default::default()) {
+            Ok(ty) => match ty.normalize() {
+                Type::Function(f) if f.type_params.is_none() => *f.ret_ty.clone(),
+                _ => return,
+            },
+            Err(..) => return,
+        };
+
+        if actual_ret_ty.is_any() {
+            return;
+        }
+
+        if self
+            .assign_with_opts(&mut Default::default(), &ret_ty, &actual_ret_ty, opts)
+            .is_err()
+        {
+            if ret_ty.is_kwd(TsKeywordTypeKind::TsVoidKeyword) {
+                self.storage.report(ErrorKind::DecoratorReturnTypeNotVoid { span }.into());
+            } else {
+                self.storage.report(ErrorKind::DecoratorReturnTypeMismatch { span }.into());
+            }
+        }
+    }
+
+    /// Returns a reference to a global type used by decorators, or [None] if
+    /// it's not declared because of `lib`.
+    fn decorator_type_ref(&mut self, span: Span, name: &str, type_args: Vec<Type>) -> Option<Type> {
+        let name = JsWord::from(name);
+        self.env.get_global_type(span, &name).ok()?;
+
+        Some(Type::Ref(Ref {
+            span,
+            type_name: RTsEntityName::Ident(RIdent::new(name, DUMMY_SP)),
+            type_args: Some(Box::new(TypeParamInstantiation { span, params: type_args })),
+            metadata: Default::default(),
+            tracker: Default::default(),
+        }))
+    }
+}
+
+fn fn_type(span: Span, params: Vec<Type>, ret_ty: Type) -> Type {
+    let params = params
+        .into_iter()
+        .enumerate()
+        .map(|(idx, ty)| FnParam {
+            span,
+            required: true,
+            pat: RPat::Ident(RBindingIdent {
+                node_id: NodeId::invalid(),
+                id: RIdent::new(format!("__arg{}", idx).into(), span.with_ctxt(SyntaxContext::empty())),
+                type_ann: None,
+            }),
+            ty: Box::new(ty),
+        })
+        .collect();
+
+    Type::Function(Function {
+        span,
+        type_params: None,
+        params,
+        ret_ty: Box::new(ret_ty),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
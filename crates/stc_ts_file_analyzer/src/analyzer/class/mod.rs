@@ -23,7 +23,7 @@ use swc_common::{iter::IdentifyLast, EqIgnoreSpan, Span, Spanned, SyntaxContext,
 use swc_ecma_ast::*;
 use swc_ecma_utils::private_ident;
 
-use self::type_param::StaticTypeParamValidator;
+use self::{decorator::has_decorators, type_param::StaticTypeParamValidator};
 use super::{expr::AccessPropertyOpts, pat::PatMode};
 use crate::{
     analyzer::{
@@ -40,7 +40,10 @@ use crate::{
     VResult,
 };
 
+mod decorator;
 mod order;
+mod overrides;
+mod prop_init;
 mod type_param;
 
 #[derive(Debug, Default)]
@@ -70,35 +73,6 @@ impl Analyzer<'_, '_> {
         };
 
         if !self.config.is_builtin {
-            // Disabled because of false positives when the constructor initializes the
-            // field.
-            #[allow(clippy::overly_complex_bool_expr)]
-            if false && self.rule().strict_null_checks {
-                if value.is_none() {
-                    if let Some(ty) = &ty {
-                        if self
-                            .assign_with_opts(
-                                &mut Default::default(),
-                                ty,
-                                &Type::Keyword(KeywordType {
-                                    span,
-                                    kind: TsKeywordTypeKind::TsUndefinedKeyword,
-                                    metadata: Default::default(),
-                                    tracker: Default::default(),
-                                }),
-                                AssignOpts {
-                                    span,
-                                    ..Default::default()
-                                },
-                            )
-                            .is_err()
-                        {
-                            self.storage.report(ErrorKind::ClassPropNotInitialized { span }.into())
-                        }
-                    }
-                }
-            }
-
             // Report error if type is not found.
             if let Some(ty) = &ty {
                 self.normalize(Some(span), Cow::Borrowed(ty), Default::default())
@@ -414,6 +388,7 @@ impl Analyzer<'_, '_> {
                     let p: FnParam = {
                         let ctx = Ctx {
                             in_constructor_param: true,
+                            in_class_member_params: true,
                             pat_mode: PatMode::Decl,
                             ..child.ctx
                         };
@@ -603,7 +578,13 @@ impl Analyzer<'_, '_> {
                         child.storage.report(ErrorKind::TS1094 { span: key_span }.into())
                     }
 
-                    let params = c.function.params.validate_with(child)?;
+                    let params = {
+                        let ctx = Ctx {
+                            in_class_member_params: true,
+                            ..child.ctx
+                        };
+                        c.function.params.validate_with(&mut *child.with_ctx(ctx))?
+                    };
 
                     let declared_ret_ty = try_opt!(c.function.return_type.validate_with(child));
 
@@ -709,27 +690,6 @@ impl Analyzer<'_, '_> {
         let c_span = c.span();
         let key_span = c.key.span();
 
-        if c.is_override {
-            if let Some(super_class) = &self.scope.get_super_class(false) {
-                if self
-                    .access_property(
-                        key_span,
-                        super_class,
-                        &key,
-                        TypeOfMode::RValue,
-                        IdCtx::Var,
-                        AccessPropertyOpts {
-                            allow_access_abstract_method: true,
-                            ..Default::default()
-                        },
-                    )
-                    .is_err()
-                {
-                    return Err(ErrorKind::NotDeclaredInSuperClass { span: key_span }.into());
-                }
-            }
-        }
-
         let (params, type_params, declared_ret_ty, inferred_ret_ty) =
             self.with_child(
                 ScopeKind::Method { is_static: c.is_static },
@@ -794,7 +754,11 @@ impl Analyzer<'_, '_> {
                         let ids: Vec<Id> = find_ids_in_pat(&c.function.params);
                         child.scope.declaring_parameters.extend(ids);
 
-                        let res = c.function.params.validate_with(child);
+                        let ctx = Ctx {
+                            in_class_member_params: true,
+                            ..child.ctx
+                        };
+                        let res = c.function.params.validate_with(&mut *child.with_ctx(ctx));
 
                         child.scope.declaring_parameters.truncate(prev_len);
 
@@ -830,6 +794,19 @@ impl Analyzer<'_, '_> {
                             None => None,
                         };
 
+                    if let Some(body) = &c.function.body {
+                        if !is_generator {
+                            child.report_implicit_return(
+                                c_span,
+                                c.function.return_type.as_deref(),
+                                &body.stmts,
+                                is_async,
+                                declared_ret_ty.as_ref(),
+                                inferred_ret_ty.as_ref(),
+                            );
+                        }
+                    }
+
                     Ok((params, type_params, declared_ret_ty, inferred_ret_ty))
                 },
             )?;
@@ -1653,7 +1630,6 @@ impl Analyzer<'_, '_> {
             has_body: !self.ctx.in_declare,
         };
 
-        c.decorators.visit_with(self);
         let name = self.scope.this_class_name.take();
         if let Some(i) = &name {
             match &**i.sym() {
@@ -1677,7 +1653,7 @@ impl Analyzer<'_, '_> {
         let mut additional_members = vec![];
 
         // Scope is required because of type parameters.
-        let c = self.with_child(ScopeKind::Class, Default::default(), |child: &mut Analyzer| -> VResult<_> {
+        let class = self.with_child(ScopeKind::Class, Default::default(), |child: &mut Analyzer| -> VResult<_> {
             child.ctx.super_references_super_class = true;
             child.ctx.in_class_with_super = c.super_class.is_some();
 
@@ -1854,6 +1830,7 @@ impl Analyzer<'_, '_> {
             child.report_errors_for_duplicate_class_members(c).report(&mut child.storage);
 
             child.scope.super_class = super_class.clone();
+            child.report_errors_for_override(c);
             {
                 // Validate constructors
                 let constructors_with_body = c
@@ -2106,8 +2083,17 @@ impl Analyzer<'_, '_> {
                 take(&mut child.scope.this_class_members)
             };
 
+            // Decorators are validated after creating the class, as they refer to it.
+            let decorated_members = body
+                .iter()
+                .filter(|(index, _)| has_decorators(&c.body[*index]))
+                .cloned()
+                .collect_vec();
+
             let body = child.combine_class_properties(body);
 
+            child.report_errors_for_uninitialized_props(c, &body);
+
             if !additional_members.is_empty() {
                 // Add private parameter properties to .d.ts file
 
@@ -2142,14 +2128,18 @@ impl Analyzer<'_, '_> {
             child.report_errors_for_wrong_implementations_of_class(None, &class);
             child.report_errors_for_conflicting_interfaces(&class.implements);
 
+            child.validate_decorators_of_class_members(c, &decorated_members, &class);
+
             Ok(class)
         })?;
 
+        self.validate_decorators_of_class(c, &class);
+
         for (i, ty) in types_to_register {
             self.register_type(i, ty);
         }
 
-        Ok(c)
+        Ok(class)
     }
 }
 
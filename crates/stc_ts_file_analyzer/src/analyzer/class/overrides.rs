@@ -0,0 +1,147 @@
+//! `override` modifiers and `noImplicitOverride`.
+
+use stc_ts_ast_rnode::{RClass, RClassMember, RParamOrTsParamProp, RPat, RPropName, RTsParamPropParam};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::{ClassMember, IdCtx, Key, Type};
+use swc_common::{Span, Spanned, TypeEq};
+
+use crate::analyzer::{
+    expr::{AccessPropertyOpts, TypeOfMode},
+    Analyzer,
+};
+
+impl Analyzer<'_, '_> {
+    /// Should be called after registering the super class to the scope.
+    pub(super) fn report_errors_for_override(&mut self, c: &RClass) {
+        if self.config.is_builtin {
+            return;
+        }
+
+        for member in &c.body {
+            match member {
+                RClassMember::Method(m) => {
+                    self.report_error_for_override_of_member(c, &m.key, m.is_static, m.is_override, false);
+                }
+                RClassMember::ClassProp(p) => {
+                    self.report_error_for_override_of_member(c, &p.key, p.is_static, p.is_override, false);
+                }
+                // Auto-accessors are skipped because the parser does not store their `override`
+                // modifier, so we can't know if one is missing.
+                RClassMember::Constructor(cons) => {
+                    for param in &cons.params {
+                        let p = match param {
+                            RParamOrTsParamProp::TsParamProp(p) => p,
+                            _ => continue,
+                        };
+
+                        let id = match &p.param {
+                            RTsParamPropParam::Ident(i) => &i.id,
+                            RTsParamPropParam::Assign(a) => match &*a.left {
+                                RPat::Ident(i) => &i.id,
+                                _ => continue,
+                            },
+                        };
+
+                        self.report_error_for_override_of_member(c, &RPropName::Ident(id.clone()), false, p.is_override, true);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn report_error_for_override_of_member(
+        &mut self,
+        c: &RClass,
+        key: &RPropName,
+        is_static: bool,
+        is_override: bool,
+        is_param_prop: bool,
+    ) {
+        let span = key.span();
+
+        if c.super_class.is_none() {
+            if is_override {
+                self.storage.report(ErrorKind::OverrideInClassWithoutSuper { span }.into());
+            }
+            return;
+        }
+
+        let no_implicit_override = self.rule().no_implicit_override && !self.ctx.in_declare && !self.config.is_dts;
+        if !is_override && !no_implicit_override {
+            return;
+        }
+
+        // Members with dynamic names are not checked.
+        let key = match key {
+            RPropName::Ident(i) => Key::Normal { span, sym: i.sym.clone() },
+            RPropName::Str(s) => Key::Normal {
+                span,
+                sym: s.value.clone(),
+            },
+            RPropName::Num(n) => Key::Num(n.clone()),
+            RPropName::BigInt(n) => Key::BigInt(n.clone()),
+            RPropName::Computed(..) => return,
+        };
+
+        let super_class = match self.scope.get_super_class(is_static) {
+            Some(v) => v,
+            None => return,
+        };
+
+        let declared_in_super_class = self
+            .access_property(
+                span,
+                &super_class,
+                &key,
+                TypeOfMode::RValue,
+                IdCtx::Var,
+                AccessPropertyOpts {
+                    allow_access_abstract_method: true,
+                    ..Default::default()
+                },
+            )
+            .is_ok();
+
+        if is_override {
+            if !declared_in_super_class {
+                self.storage.report(ErrorKind::NotDeclaredInSuperClass { span }.into());
+            }
+            return;
+        }
+
+        // Implementing an abstract member does not require `override`.
+        if declared_in_super_class && !is_abstract_member(&super_class, &key) {
+            if is_param_prop {
+                self.storage.report(ErrorKind::ParamPropMissingOverrideModifier { span }.into());
+            } else {
+                self.storage.report(ErrorKind::MissingOverrideModifier { span }.into());
+            }
+        }
+    }
+}
+
+/// Returns `true` if `key` is declared as an abstract member of `ty` or its
+/// super classes.
+fn is_abstract_member(ty: &Type, key: &Key) -> bool {
+    let def = match ty.normalize() {
+        Type::Class(c) => &c.def,
+        Type::ClassDef(def) => def,
+        Type::Intersection(i) => return i.types.iter().any(|ty| is_abstract_member(ty, key)),
+        _ => return false,
+    };
+
+    let found = def.body.iter().find_map(|m| match m {
+        ClassMember::Method(m) if m.key.type_eq(key) => Some(m.is_abstract),
+        ClassMember::Property(p) if p.key.type_eq(key) => Some(p.is_abstract),
+        _ => None,
+    });
+
+    match found {
+        Some(is_abstract) => is_abstract,
+        None => def
+            .super_class
+            .as_deref()
+            .map_or(false, |super_class| is_abstract_member(super_class, key)),
+    }
+}
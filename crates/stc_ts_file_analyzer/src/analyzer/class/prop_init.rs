@@ -0,0 +1,79 @@
+//! `strictPropertyInitialization`.
+
+use stc_ts_ast_rnode::{RClass, RClassMember, RPropName};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::ClassMember;
+
+use crate::{analyzer::Analyzer, util::definite_assignment::find_assigned_this_props};
+
+impl Analyzer<'_, '_> {
+    /// Reports `TS2564` for instance properties which are not initialized
+    /// and not definitely assigned in the constructor.
+    ///
+    /// `body` is the validated class members, with the index of the
+    /// corresponding member of `c`.
+    pub(super) fn report_errors_for_uninitialized_props(&mut self, c: &RClass, body: &[(usize, ClassMember)]) {
+        let rule = self.rule();
+        if !rule.strict_property_initialization
+            || !rule.strict_null_checks
+            || self.config.is_builtin
+            || self.config.is_dts
+            || self.ctx.in_declare
+        {
+            return;
+        }
+
+        let assigned = c
+            .body
+            .iter()
+            .find_map(|member| match member {
+                RClassMember::Constructor(c) => c.body.as_ref(),
+                _ => None,
+            })
+            .map(|body| find_assigned_this_props(&body.stmts));
+
+        for (index, member) in body {
+            let ty = match member {
+                ClassMember::Property(p) => match &p.value {
+                    Some(ty) => ty,
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            let (sym, span) = match &c.body[*index] {
+                RClassMember::ClassProp(p)
+                    if p.type_ann.is_some()
+                        && p.value.is_none()
+                        && !p.is_static
+                        && !p.is_optional
+                        && !p.is_abstract
+                        && !p.declare
+                        && !p.definite =>
+                {
+                    match &p.key {
+                        RPropName::Ident(i) => (&i.sym, i.span),
+                        RPropName::Str(s) => (&s.value, s.span),
+                        _ => continue,
+                    }
+                }
+                RClassMember::PrivateProp(p)
+                    if p.type_ann.is_some() && p.value.is_none() && !p.is_static && !p.is_optional && !p.definite =>
+                {
+                    (&p.key.id.sym, p.key.span)
+                }
+                _ => continue,
+            };
+
+            if assigned.as_ref().map_or(false, |assigned| assigned.contains(sym)) {
+                continue;
+            }
+
+            if self.can_be_uninitialized(span, ty) {
+                continue;
+            }
+
+            self.storage.report(ErrorKind::ClassPropNotInitialized { span }.into());
+        }
+    }
+}
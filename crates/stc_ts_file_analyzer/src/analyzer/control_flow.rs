@@ -9,7 +9,7 @@ use std::{
 use fxhash::FxHashMap;
 use rnode::{NodeId, VisitWith};
 use stc_ts_ast_rnode::{
-    RBinExpr, RBindingIdent, RCondExpr, RExpr, RIdent, RIfStmt, RMemberExpr, RObjectPatProp, RPat, RPatOrExpr, RStmt, RSwitchCase,
+    RBinExpr, RBindingIdent, RCondExpr, RExpr, RIdent, RIfStmt, RMemberExpr, RNumber, RObjectPatProp, RPat, RPatOrExpr, RStmt, RSwitchCase,
     RSwitchStmt,
 };
 use stc_ts_errors::{DebugExt, ErrorKind};
@@ -37,7 +37,7 @@ use crate::{
     },
     ty::Type,
     type_facts::TypeFacts,
-    util::EndsWithRet,
+    util::{reachability::can_complete_normally, EndsWithRet},
     validator,
     validator::ValidateWith,
     VResult,
@@ -677,6 +677,11 @@ impl Analyzer<'_, '_> {
                 Ok(())
             })?;
 
+            // Empty cases are grouped with the next case.
+            if self.rule().no_fallthrough_cases_in_switch && !last && !cons.is_empty() && can_complete_normally(cons) {
+                self.storage.report(ErrorKind::FallthroughCaseInSwitch { span: case.span }.into());
+            }
+
             if ends_with_ret || last {
                 false_facts += false_facts_created_by_case.clone();
                 base_true_facts += false_facts_created_by_case;
@@ -732,6 +737,35 @@ impl Analyzer<'_, '_> {
         false
     }
 
+    /// Returns `true` if `rhs_ty` contains `undefined` and `expr` is an
+    /// optional property which does not accept `undefined` because of
+    /// `exactOptionalPropertyTypes`.
+    fn is_exact_optional_prop(&mut self, expr: &RExpr, lhs_ty: &Type, rhs_ty: &Type) -> bool {
+        if !self.rule().exact_optional_property_types
+            || !matches!(expr, RExpr::Member(..))
+            || !rhs_ty.iter_union().any(|ty| ty.is_undefined())
+        {
+            return false;
+        }
+
+        // Reading an optional property results in `T | undefined`, while writing to it
+        // requires `T`.
+        let ctx = Ctx {
+            ignore_errors: true,
+            ..self.ctx
+        };
+        let rvalue_ty = self
+            .with_ctx(ctx)
+            .with_child(ScopeKind::Flow, Default::default(), |child: &mut Analyzer| {
+                expr.validate_with_args(child, (TypeOfMode::RValue, None, None))
+            });
+
+        match rvalue_ty {
+            Ok(rvalue_ty) => rvalue_ty.iter_union().any(|ty| ty.is_undefined()) && !lhs_ty.iter_union().any(|ty| ty.is_undefined()),
+            Err(..) => false,
+        }
+    }
+
     pub(super) fn try_assign(&mut self, span: Span, op: AssignOp, lhs: &RPatOrExpr, rhs_ty: &Type) -> Type {
         rhs_ty.assert_valid();
 
@@ -746,7 +780,7 @@ impl Analyzer<'_, '_> {
                     lhs_ty.freeze();
 
                     if op == op!("=") {
-                        self.assign_with_opts(
+                        let res = self.assign_with_opts(
                             &mut Default::default(),
                             &lhs_ty,
                             rhs_ty,
@@ -755,7 +789,16 @@ impl Analyzer<'_, '_> {
                                 left_ident_span: Some(lhs.span()),
                                 ..Default::default()
                             },
-                        )?
+                        );
+                        let is_write_to_exact_optional_prop = res.is_err() && self.is_exact_optional_prop(expr, &lhs_ty, rhs_ty);
+
+                        res.convert_err(|err| {
+                            if is_write_to_exact_optional_prop {
+                                ErrorKind::ExactOptionalPropertyWriteMismatch { span }
+                            } else {
+                                err
+                            }
+                        })?
                     } else {
                         self.assign_with_operator(span, op, &lhs_ty, rhs_ty)?;
                     }
@@ -1040,6 +1083,19 @@ impl Analyzer<'_, '_> {
                             .get_element_from_iterator(span, Cow::Borrowed(&ty), i)
                             .context("tried to get an element of type to assign with an array pattern")
                             .report(&mut self.storage)
+                            .map(|elem_ty| {
+                                let elem_ty = elem_ty.into_owned();
+                                if let RPat::Assign(..) = elem {
+                                    return elem_ty;
+                                }
+
+                                let key = Key::Num(RNumber {
+                                    span: elem.span(),
+                                    value: i as f64,
+                                    raw: None,
+                                });
+                                self.add_undefined_for_unchecked_index(span, &ty, &key, elem_ty)
+                            })
                             .freezed();
                         if let Some(elem_ty) = elem_ty {
                             self.try_assign_pat_with_opts(span, elem, &elem_ty, opts)
@@ -1070,6 +1126,10 @@ impl Analyzer<'_, '_> {
                                         ..Default::default()
                                     },
                                 )
+                                .map(|prop_ty| match &*kv.value {
+                                    RPat::Assign(..) => prop_ty,
+                                    _ => self.add_undefined_for_unchecked_index(span, ty, &key, prop_ty),
+                                })
                                 .unwrap_or_else(|_| Type::any(span, Default::default()));
 
                             self.try_assign_pat_with_opts(span, &kv.value, &prop_ty, opts)
@@ -1093,6 +1153,10 @@ impl Analyzer<'_, '_> {
                                         ..Default::default()
                                     },
                                 )
+                                .map(|prop_ty| match a.value {
+                                    Some(..) => prop_ty,
+                                    None => self.add_undefined_for_unchecked_index(span, ty, &key, prop_ty),
+                                })
                                 .unwrap_or_else(|_| Type::any(span, Default::default()))
                                 .freezed();
 
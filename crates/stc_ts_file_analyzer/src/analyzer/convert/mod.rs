@@ -19,7 +19,7 @@ use stc_ts_types::{
     Interface, IntrinsicKind, Key, KeywordType, KeywordTypeMetadata, LitType, LitTypeMetadata, Mapped, MethodSignature, OptionalType,
     Predicate, PropertySignature, QueryExpr, QueryType, Readonly, Ref, RefMetadata, RestType, StringMapping, Symbol, ThisType, TplElem,
     TplType, TsExpr, Tuple, TupleElement, TupleMetadata, Type, TypeElement, TypeLit, TypeLitMetadata, TypeParam, TypeParamDecl,
-    TypeParamInstantiation, Union, Unique,
+    TypeParamInstantiation, TypeParamMetadata, Union, Unique,
 };
 use stc_ts_utils::{find_ids_in_pat, PatExt};
 use stc_utils::{cache::Freeze, dev_span, FxHashSet};
@@ -85,7 +85,12 @@ impl Analyzer<'_, '_> {
                         name,
                         constraint: None,
                         default: None,
-                        metadata: Default::default(),
+                        metadata: TypeParamMetadata {
+                            is_const: param.is_const,
+                            is_in: param.is_in,
+                            is_out: param.is_out,
+                            ..Default::default()
+                        },
                         tracker: Default::default(),
                     })
                     .freezed(),
@@ -140,7 +145,12 @@ impl Analyzer<'_, '_> {
             name: p.name.clone().into(),
             constraint,
             default,
-            metadata: Default::default(),
+            metadata: TypeParamMetadata {
+                is_const: p.is_const,
+                is_in: p.is_in,
+                is_out: p.is_out,
+                ..Default::default()
+            },
             tracker: Default::default(),
         };
         self.register_type(param.name.clone(), param.clone().into());
@@ -299,6 +309,10 @@ impl Analyzer<'_, '_> {
 
         self.register_type(d.id.clone().into(), alias.clone());
 
+        if let Type::Alias(a) = alias.normalize() {
+            self.report_error_for_variance_annotations_of_alias(&d.type_ann, a);
+        }
+
         // Exclude literals
         if !span.is_dummy() {
             self.dump_type(d.id.span, &alias);
@@ -345,6 +359,10 @@ impl Analyzer<'_, '_> {
         // TODO(kdy1): Recover
         self.register_type(d.id.clone().into(), ty.clone());
 
+        if let Type::Interface(i) = ty.normalize() {
+            self.report_error_for_variance_annotations_of_interface(i);
+        }
+
         Ok(ty)
     }
 }
@@ -601,6 +619,8 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, e: &RTsExprWithTypeArgs) -> VResult<TsExpr> {
+        self.mark_expr_as_used(&e.expr);
+
         Ok(TsExpr {
             span: e.span,
             expr: e.expr.clone(),
@@ -824,6 +844,8 @@ impl Analyzer<'_, '_> {
 impl Analyzer<'_, '_> {
     fn validate(&mut self, t: &RTsTypeRef) -> VResult<Type> {
         let span = t.span;
+        self.mark_entity_as_used(&t.type_name);
+
         let type_args = try_opt!(t.type_params.validate_with(self)).map(Box::new).freezed();
         let mut contains_infer = false;
 
@@ -959,7 +981,10 @@ impl Analyzer<'_, '_> {
         let span = t.span();
 
         Ok(match t {
-            RTsTypeQueryExpr::TsEntityName(t) => t.clone().into(),
+            RTsTypeQueryExpr::TsEntityName(t) => {
+                self.mark_entity_as_used(t);
+                t.clone().into()
+            }
             RTsTypeQueryExpr::Import(i) => i.validate_with(self)?.into(),
         })
     }
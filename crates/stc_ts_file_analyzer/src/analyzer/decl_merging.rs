@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use fxhash::FxHashMap;
 use stc_ts_errors::{debug::dump_type_as_string, DebugExt, ErrorKind};
-use stc_ts_types::{ClassDef, ClassMember, ClassProperty, Id, Interface, Method, Type, TypeElement, TypeParam, TypeParamDecl};
+use stc_ts_types::{ClassDef, ClassMember, ClassProperty, Enum, Id, Interface, Method, Type, TypeElement, TypeParam, TypeParamDecl};
 use stc_utils::cache::Freeze;
 use swc_common::{Span, Spanned};
 use tracing::info;
@@ -216,6 +216,22 @@ impl Analyzer<'_, '_> {
                 }
             }
 
+            (Type::Enum(a), Type::Enum(b)) => {
+                let mut members = a.members.clone();
+                members.extend(b.members.iter().cloned());
+
+                return Ok(Some(Type::Enum(
+                    Enum {
+                        has_num: a.has_num || b.has_num,
+                        has_str: a.has_str || b.has_str,
+                        declare: a.declare && b.declare,
+                        members,
+                        ..(**a).clone()
+                    }
+                    .into(),
+                )));
+            }
+
             _ => {}
         }
 
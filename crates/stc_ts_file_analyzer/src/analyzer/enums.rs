@@ -2,7 +2,8 @@ use fxhash::FxHashMap;
 use rnode::{NodeId, Visit, VisitWith};
 use stc_arc_cow::ArcCow;
 use stc_ts_ast_rnode::{
-    RBinExpr, RBindingIdent, RExpr, RIdent, RLit, RNumber, RPat, RStr, RTsEnumDecl, RTsEnumMember, RTsEnumMemberId, RTsLit,
+    RBinExpr, RBindingIdent, RComputedPropName, RExpr, RIdent, RLit, RMemberExpr, RMemberProp, RNumber, RPat, RStr, RTsEnumDecl,
+    RTsEnumMember, RTsEnumMemberId, RTsLit,
 };
 use stc_ts_errors::{ErrorKind, Errors};
 use stc_ts_file_analyzer_macros::validator;
@@ -122,7 +123,7 @@ impl Analyzer<'_, '_> {
                         .or_else(|err| match &m.init {
                             None => Err(err),
                             Some(v) => {
-                                if e.is_const {
+                                if e.is_const || matches!(*err, ErrorKind::BigIntInEnum { .. }) {
                                     self.storage.report(err);
                                 }
                                 Ok(Box::new(Type::any(m.span, Default::default())))
@@ -149,7 +150,7 @@ impl Analyzer<'_, '_> {
                 span: e.span,
                 has_num: members.iter().any(|m| m.val.is_num_lit()),
                 has_str,
-                declare: e.declare,
+                declare: e.declare || self.ctx.in_declare || self.config.is_dts,
                 is_const: e.is_const,
                 id: e.id.clone().into(),
                 members,
@@ -168,7 +169,17 @@ impl Analyzer<'_, '_> {
             .unwrap_or_else(|| Type::any(span, Default::default()));
         stored_ty.assert_clone_cheap();
 
-        self.register_type(name.clone(), stored_ty.clone());
+        if let Ok(Some(prev)) = self.find_type(&name) {
+            let is_mismatch = prev
+                .into_iter()
+                .any(|prev| matches!(prev.normalize(), Type::Enum(prev) if prev.is_const != e.is_const));
+            if is_mismatch {
+                self.storage.report(ErrorKind::EnumConstMismatch { span: e.id.span }.into());
+            }
+        }
+
+        // Merged with previous declarations of the enum.
+        let stored_ty = self.register_type(name.clone(), stored_ty);
 
         self.declare_var(e.span, VarKind::Enum, name, Some(stored_ty.clone()), None, true, true, false, false)
             .report(&mut self.storage);
@@ -195,6 +206,7 @@ impl Evaluator<'_> {
             match expr {
                 RExpr::Lit(RLit::Str(s)) => return Ok(RTsLit::Str(s.clone())),
                 RExpr::Lit(RLit::Num(s)) => return Ok(RTsLit::Number(s.clone())),
+                RExpr::Lit(RLit::BigInt(v)) => return Err(ErrorKind::BigIntInEnum { span: v.span }.into()),
                 RExpr::Bin(ref bin) => {
                     let v = self.compute_bin(analyzer, span, bin)?;
 
@@ -223,20 +235,33 @@ impl Evaluator<'_> {
                         }
                     }
 
-                    if let Some(v) = self.values.get(&id.sym) {
-                        return Ok(v.clone());
+                    if let Some(v) = self.compute_member(analyzer, span, &id.sym) {
+                        return v;
                     }
-                    //
-                    for m in self.e.members.iter() {
-                        match m.id {
-                            RTsEnumMemberId::Str(RStr { value: ref sym, .. }) | RTsEnumMemberId::Ident(RIdent { ref sym, .. }) => {
-                                if *sym == id.sym {
-                                    return self.compute(analyzer, span, None, m.init.as_deref());
+
+                    return self.compute_from_type(analyzer, span, expr);
+                }
+                RExpr::Member(RMemberExpr { obj, prop, .. }) => {
+                    if let RExpr::Ident(obj) = &**obj {
+                        let name = match prop {
+                            RMemberProp::Ident(i) => Some(&i.sym),
+                            RMemberProp::Computed(RComputedPropName { expr, .. }) => match &**expr {
+                                RExpr::Lit(RLit::Str(s)) => Some(&s.value),
+                                _ => None,
+                            },
+                            _ => None,
+                        };
+
+                        if let Some(name) = name {
+                            if obj.sym == self.e.id.sym {
+                                if let Some(v) = self.compute_member(analyzer, span, name) {
+                                    return v;
                                 }
                             }
                         }
                     }
-                    return Err(ErrorKind::InvalidEnumInit { span }.into());
+
+                    return self.compute_from_type(analyzer, span, expr);
                 }
                 RExpr::Unary(ref expr) => {
                     let v = self.compute(analyzer, span, None, Some(&expr.arg))?;
@@ -263,24 +288,36 @@ impl Evaluator<'_> {
                         RTsLit::Str(_) => {}
                         RTsLit::Bool(_) => {}
                         RTsLit::Tpl(_) => {}
-                        RTsLit::BigInt(_) => unimplemented!("BigInt in enum"),
+                        RTsLit::BigInt(_) => return Err(ErrorKind::BigIntInEnum { span: expr.span }.into()),
                     }
                 }
 
-                RExpr::Tpl(ref t) if t.exprs.is_empty() => {
-                    if let Some(v) = &t.quasis[0].cooked {
-                        return Ok(v.clone().into());
-                    }
-                }
+                RExpr::Tpl(ref t) => {
+                    let mut value = String::new();
 
-                _ => {
-                    let res = expr.validate_with_default(analyzer)?;
-                    let res = analyzer.expand_enum_variant(res)?;
+                    for (idx, quasi) in t.quasis.iter().enumerate() {
+                        match &quasi.cooked {
+                            Some(cooked) => value.push_str(cooked),
+                            None => return Err(ErrorKind::InvalidEnumInit { span }.into()),
+                        }
 
-                    if let Type::Lit(ty) = res.normalize() {
-                        return Ok(ty.lit.clone());
+                        if let Some(expr) = t.exprs.get(idx) {
+                            match self.compute(analyzer, span, None, Some(expr))? {
+                                RTsLit::Str(s) => value.push_str(&s.value),
+                                RTsLit::Number(n) => value.push_str(&number_to_string(n.value)),
+                                _ => return Err(ErrorKind::InvalidEnumInit { span }.into()),
+                            }
+                        }
                     }
+
+                    return Ok(RTsLit::Str(RStr {
+                        span: t.span,
+                        value: value.into(),
+                        raw: None,
+                    }));
                 }
+
+                _ => return self.compute_from_type(analyzer, span, expr),
             }
         } else {
             if let Some(value) = default {
@@ -295,6 +332,58 @@ impl Evaluator<'_> {
         Err(ErrorKind::InvalidEnumInit { span }.into())
     }
 
+    /// Computes the value of a member of the enum named `sym`, including
+    /// members from previous declarations of the enum.
+    fn compute_member(&mut self, analyzer: &mut Analyzer, span: Span, sym: &JsWord) -> Option<VResult<RTsLit>> {
+        if let Some(v) = self.values.get(sym) {
+            return Some(Ok(v.clone()));
+        }
+
+        for m in self.e.members.iter() {
+            match m.id {
+                RTsEnumMemberId::Str(RStr { value: ref name, .. }) | RTsEnumMemberId::Ident(RIdent { sym: ref name, .. }) => {
+                    if name == sym {
+                        return Some(self.compute(analyzer, span, None, m.init.as_deref()));
+                    }
+                }
+            }
+        }
+
+        let types = analyzer.find_type(&Id::from(&self.e.id)).ok()??;
+        for ty in types {
+            if let Type::Enum(e) = ty.normalize() {
+                for m in &e.members {
+                    match &m.id {
+                        RTsEnumMemberId::Str(RStr { value: name, .. }) | RTsEnumMemberId::Ident(RIdent { sym: name, .. }) => {
+                            if name == sym {
+                                if let Type::Lit(ty) = m.val.normalize() {
+                                    return Some(Ok(ty.lit.clone()));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Uses the type of `expr`, for constants declared outside of the enum,
+    /// like members of other enums, which may be imported from other modules.
+    fn compute_from_type(&mut self, analyzer: &mut Analyzer, span: Span, expr: &RExpr) -> VResult<RTsLit> {
+        let ty = expr.validate_with_default(analyzer)?;
+        let ty = analyzer.expand_enum_variant(ty)?;
+
+        match ty.normalize() {
+            Type::Lit(LitType {
+                lit: RTsLit::BigInt(..), ..
+            }) => Err(ErrorKind::BigIntInEnum { span: expr.span() }.into()),
+            Type::Lit(ty) => Ok(ty.lit.clone()),
+            _ => Err(ErrorKind::InvalidEnumInit { span }.into()),
+        }
+    }
+
     fn compute_bin(&mut self, analyzer: &mut Analyzer, span: Span, expr: &RBinExpr) -> VResult<RTsLit> {
         let l = self.compute(analyzer, span, None, Some(&expr.left))?;
         let r = self.compute(analyzer, span, None, Some(&expr.right))?;
@@ -331,12 +420,12 @@ impl Evaluator<'_> {
             }),
             (RTsLit::Number(l), RTsLit::Str(r)) if expr.op == op!(bin, "+") => RTsLit::Str(RStr {
                 span,
-                value: format!("{}{}", l.value, r.value).into(),
+                value: format!("{}{}", number_to_string(l.value), r.value).into(),
                 raw: None,
             }),
             (RTsLit::Str(l), RTsLit::Number(r)) if expr.op == op!(bin, "+") => RTsLit::Str(RStr {
                 span,
-                value: format!("{}{}", l.value, r.value).into(),
+                value: format!("{}{}", l.value, number_to_string(r.value)).into(),
                 raw: None,
             }),
             _ => Err(ErrorKind::InvalidEnumInit { span })?,
@@ -625,7 +714,7 @@ impl Visit<RExpr> for LitValidator<'_> {
                 // }
             }
             RExpr::Member(..) => {}
-            RExpr::Unary(..) | RExpr::Bin(..) | RExpr::Paren(..) => {
+            RExpr::Unary(..) | RExpr::Bin(..) | RExpr::Paren(..) | RExpr::Tpl(..) => {
                 e.visit_children_with(self);
             }
 
@@ -635,3 +724,41 @@ impl Visit<RExpr> for LitValidator<'_> {
         }
     }
 }
+
+/// Converts `v` to a string like `Number.prototype.toString` of JavaScript.
+fn number_to_string(v: f64) -> String {
+    if v.is_nan() {
+        return "NaN".into();
+    }
+    if v == 0.0 {
+        return "0".into();
+    }
+    if v.is_infinite() {
+        return if v > 0.0 { "Infinity".into() } else { "-Infinity".into() };
+    }
+    if v < 0.0 {
+        return format!("-{}", number_to_string(-v));
+    }
+
+    // Shortest digits which round-trip, like `1.5e-7`.
+    let s = format!("{:e}", v);
+    let (mantissa, exp) = s.split_once('e').unwrap();
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let n = exp.parse::<i32>().unwrap() + 1;
+
+    if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        if k == 1 {
+            format!("{}e{}{}", digits, sign, (n - 1).abs())
+        } else {
+            format!("{}.{}e{}{}", &digits[..1], &digits[1..], sign, (n - 1).abs())
+        }
+    }
+}
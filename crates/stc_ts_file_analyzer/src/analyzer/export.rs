@@ -452,6 +452,10 @@ impl Analyzer<'_, '_> {
                                 RModuleExportName::Str(v) => v.value.clone(),
                             };
 
+                            if !node.type_only && !named.is_type_only && base != dep {
+                                self.report_error_for_re_export_of_type(span, Some(&data), &Id::word(orig_sym.clone()));
+                            }
+
                             let var_result = self.access_property(
                                 span,
                                 &data,
@@ -505,6 +509,10 @@ impl Analyzer<'_, '_> {
                                 RModuleExportName::Ident(v) => v.clone(),
                                 RModuleExportName::Str(v) => unreachable!(),
                             };
+
+                            if !node.type_only && !named.is_type_only {
+                                self.report_error_for_re_export_of_type(named.span, None, &Id::from(&orig));
+                            }
                             //
                             let var_result = self.type_of_var(&orig, TypeOfMode::RValue, None);
                             let type_result = self.type_of_ts_entity_name(span, &RExpr::Ident(orig.clone()), None);
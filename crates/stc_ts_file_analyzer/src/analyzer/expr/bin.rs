@@ -1340,7 +1340,9 @@ impl Analyzer<'_, '_> {
             }
         }
 
-        if orig_ty.is_any() {
+        // `unknown` is narrowed like `any`, which is important for catch variables with
+        // `useUnknownInCatchVariables`.
+        if orig_ty.is_any() || orig_ty.is_unknown() {
             if ty.is_interface() || ty.is_type_lit() {
                 if let Ok(result) = self.access_property(
                     span,
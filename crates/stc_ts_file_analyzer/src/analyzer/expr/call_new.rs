@@ -6,7 +6,7 @@ use itertools::Itertools;
 use rnode::{Fold, FoldWith, NodeId, VisitMut, VisitMutWith, VisitWith};
 use stc_ts_ast_rnode::{
     RArrayPat, RBindingIdent, RCallExpr, RCallee, RComputedPropName, RExpr, RExprOrSpread, RIdent, RInvalid, RLit, RMemberExpr,
-    RMemberProp, RNewExpr, RObjectPat, RPat, RRestPat, RStr, RTaggedTpl, RTsAsExpr, RTsEntityName, RTsKeywordType, RTsLit,
+    RMemberProp, RNewExpr, RObjectPat, RParenExpr, RPat, RRestPat, RStr, RTaggedTpl, RTsAsExpr, RTsEntityName, RTsKeywordType, RTsLit,
     RTsThisTypeOrIdent, RTsType, RTsTypeAnn, RTsTypeParamInstantiation, RTsTypeRef, RTsUnionOrIntersectionType, RTsUnionType,
 };
 use stc_ts_env::MarkExt;
@@ -3067,7 +3067,7 @@ impl Analyzer<'_, '_> {
             ty.freeze();
 
             if kind == ExtractKind::Call {
-                self.add_call_facts(&expanded_param_types, args, &mut ty);
+                self.add_call_facts(expr, &expanded_param_types, args, &mut ty);
             }
 
             return Ok(ty);
@@ -3088,7 +3088,7 @@ impl Analyzer<'_, '_> {
         ret_ty.freeze();
 
         if kind == ExtractKind::Call {
-            self.add_call_facts(&params, args, &mut ret_ty);
+            self.add_call_facts(expr, &params, args, &mut ret_ty);
         }
 
         Ok(ret_ty)
@@ -3451,42 +3451,160 @@ impl Analyzer<'_, '_> {
     ///
     /// should make type of `subscriber` `SafeSubscriber`, not `Subscriber`.
     /// I (kdy1) don't know why.
-    fn add_call_facts(&mut self, params: &[FnParam], args: &[RExprOrSpread], ret_ty: &mut Type) {
-        if !self.ctx.in_cond {
+    fn add_call_facts(&mut self, expr: ReEvalMode, params: &[FnParam], args: &[RExprOrSpread], ret_ty: &mut Type) {
+        let p = match ret_ty.normalize() {
+            Type::Predicate(p) => p,
+            _ => return,
+        };
+
+        // Type guards narrow branches of a condition, and assertion functions
+        // narrow the code after the call.
+        if p.asserts == self.ctx.in_cond {
             return;
         }
 
-        if let Type::Predicate(p) = ret_ty.normalize() {
-            let ctx = Ctx {
-                is_type_predicate: true,
-                ..self.ctx
-            };
+        // Only a call which is an expression statement is an assertion.
+        if p.asserts && !matches!(expr, ReEvalMode::Call(call) if self.ctx.expr_stmt_call_span == Some(call.span)) {
+            return;
+        }
 
-            let ty = match &p.ty {
-                Some(v) => v,
-                None => return,
-            };
+        let callee_obj = match expr {
+            ReEvalMode::Call(RCallExpr {
+                callee: RCallee::Expr(callee),
+                ..
+            }) => {
+                if p.asserts && !self.validate_assertion_call_target(callee) {
+                    return;
+                }
 
-            match &p.param_name {
-                RTsThisTypeOrIdent::TsThisType(this) => {
-                    //
-                    self.store_call_fact_for_var(this.span, Id::word("this".into()), &ty.clone().freezed());
+                match &**callee {
+                    RExpr::Member(RMemberExpr {
+                        obj: box RExpr::Ident(obj),
+                        ..
+                    }) => Some(obj),
+                    _ => None,
                 }
-                RTsThisTypeOrIdent::Ident(arg_id) => {
+            }
+            _ => None,
+        };
+
+        let ctx = Ctx {
+            is_type_predicate: true,
+            ..self.ctx
+        };
+
+        let ty = match &p.ty {
+            Some(v) => v,
+            None => {
+                // `asserts cond`
+                if let RTsThisTypeOrIdent::Ident(arg_id) = &p.param_name {
                     for (idx, param) in params.iter().enumerate() {
                         match &param.pat {
                             RPat::Ident(i) if i.id.sym == arg_id.sym => {
-                                // TODO(kdy1): Check length of args.
-                                let arg = &args[idx];
-                                if let RExpr::Ident(var_name) = &*arg.expr {
-                                    let ty = ty.clone().freezed();
-                                    self.with_ctx(ctx).store_call_fact_for_var(var_name.span, var_name.into(), &ty);
+                                if let Some(arg) = args.get(idx) {
+                                    self.add_facts_for_asserted_arg(&arg.expr);
                                 }
                             }
                             _ => {}
                         }
                     }
                 }
+                return;
+            }
+        };
+
+        match &p.param_name {
+            RTsThisTypeOrIdent::TsThisType(this) => match callee_obj {
+                Some(obj) => {
+                    let ty = ty.clone().freezed();
+                    self.with_ctx(ctx).store_call_fact_for_var(obj.span, obj.into(), &ty);
+                }
+                None => {
+                    self.store_call_fact_for_var(this.span, Id::word("this".into()), &ty.clone().freezed());
+                }
+            },
+            RTsThisTypeOrIdent::Ident(arg_id) => {
+                for (idx, param) in params.iter().enumerate() {
+                    match &param.pat {
+                        RPat::Ident(i) if i.id.sym == arg_id.sym => {
+                            // TODO(kdy1): Check length of args.
+                            let arg = &args[idx];
+                            if let RExpr::Ident(var_name) = &*arg.expr {
+                                let ty = ty.clone().freezed();
+                                self.with_ctx(ctx).store_call_fact_for_var(var_name.span, var_name.into(), &ty);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Narrows the code after `assert(cond)` as if it's `if (!cond) throw`.
+    fn add_facts_for_asserted_arg(&mut self, arg: &RExpr) {
+        let ctx = Ctx {
+            in_cond: true,
+            should_store_truthy_for_access: true,
+            ignore_errors: true,
+            ..self.ctx
+        };
+
+        let prev_facts = self.cur_facts.take();
+        let facts = self
+            .with_ctx(ctx)
+            .with_child(ScopeKind::Flow, prev_facts.true_facts.clone(), |child: &mut Analyzer| {
+                arg.validate_with_default(child)?;
+
+                Ok(child.cur_facts.take())
+            });
+        self.cur_facts = prev_facts;
+
+        if let Ok(facts) = facts {
+            self.cur_facts.true_facts += facts.true_facts;
+        }
+    }
+
+    /// Reports `TS2775` if a name in `callee` is declared without a type
+    /// annotation, and `TS2776` if `callee` is not a (dotted) name.
+    ///
+    /// Returns `true` if `callee` is valid.
+    fn validate_assertion_call_target(&mut self, callee: &RExpr) -> bool {
+        let mut cur = callee;
+
+        loop {
+            match cur {
+                RExpr::Ident(i) => {
+                    if self.data.vars_without_type_ann.contains(&Id::from(i)) {
+                        self.storage
+                            .report(ErrorKind::AssertionTargetWithoutTypeAnn { span: callee.span() }.into());
+                        return false;
+                    }
+
+                    return true;
+                }
+
+                RExpr::This(..) | RExpr::SuperProp(..) => return true,
+
+                RExpr::Member(RMemberExpr {
+                    obj,
+                    prop:
+                        RMemberProp::Ident(..)
+                        | RMemberProp::PrivateName(..)
+                        | RMemberProp::Computed(RComputedPropName {
+                            expr: box RExpr::Lit(RLit::Str(..)),
+                            ..
+                        }),
+                    ..
+                }) => cur = &**obj,
+
+                RExpr::Paren(RParenExpr { expr, .. }) => cur = &**expr,
+
+                _ => {
+                    self.storage
+                        .report(ErrorKind::InvalidAssertionTarget { span: callee.span() }.into());
+                    return false;
+                }
             }
         }
     }
@@ -114,6 +114,19 @@ impl Analyzer<'_, '_> {
                 ty
             });
 
+            if let RBlockStmtOrExpr::BlockStmt(body) = &*f.body {
+                if !f.is_generator {
+                    child.report_implicit_return(
+                        f.span,
+                        f.return_type.as_deref(),
+                        &body.stmts,
+                        f.is_async,
+                        declared_ret_ty.as_ref(),
+                        inferred_return_type.as_ref(),
+                    );
+                }
+            }
+
             if let Some(ref declared) = declared_ret_ty {
                 let span = inferred_return_type.span();
                 if let Some(ref inferred) = inferred_return_type {
@@ -0,0 +1,146 @@
+use std::borrow::Cow;
+
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::{IndexSignature, Key, Type, TypeElement};
+use swc_common::{Span, Spanned};
+use swc_ecma_ast::TsKeywordTypeKind;
+
+use crate::{
+    analyzer::{types::NormalizeTypeOpts, Analyzer},
+    VResult,
+};
+
+impl Analyzer<'_, '_> {
+    /// Returns `true` if reading `key` from `obj` is resolved by an index
+    /// signature or by an element of an array (or the rest of a tuple),
+    /// instead of a declared property.
+    ///
+    /// Such reads are `T | undefined` with `noUncheckedIndexedAccess`.
+    pub(crate) fn is_read_via_index_signature(&mut self, span: Span, obj: &Type, key: &Key) -> VResult<bool> {
+        if let Key::Computed(key) = key {
+            if key.ty.is_symbol_like() {
+                return Ok(false);
+            }
+        }
+
+        let obj = self.normalize(
+            Some(span),
+            Cow::Borrowed(obj),
+            NormalizeTypeOpts {
+                preserve_union: true,
+                ..Default::default()
+            },
+        )?;
+
+        match obj.normalize() {
+            Type::Array(..) => Ok(is_numeric_key(key)),
+
+            Type::Tuple(tuple) => match key {
+                Key::Num(n) => {
+                    let has_rest = tuple.elems.last().map_or(false, |e| e.ty.is_rest());
+                    Ok(has_rest && n.value >= (tuple.elems.len() - 1) as f64)
+                }
+                Key::Computed(key) => Ok(key.ty.is_kwd(TsKeywordTypeKind::TsNumberKeyword)),
+                _ => Ok(false),
+            },
+
+            // `T[K]` may be resolved by an index signature of `T`.
+            Type::IndexedAccessType(..) => Ok(true),
+
+            Type::Param(param) => match &param.constraint {
+                Some(constraint) => self.is_read_via_index_signature(span, constraint, key),
+                None => Ok(false),
+            },
+
+            Type::Union(u) => {
+                for ty in &u.types {
+                    if self.is_read_via_index_signature(span, ty, key)? {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+
+            Type::TypeLit(..) | Type::Interface(..) | Type::Intersection(..) => {
+                let lit = match self.convert_type_to_type_lit(span, obj.clone(), Default::default())? {
+                    Some(lit) => lit,
+                    None => return Ok(false),
+                };
+
+                for member in &lit.members {
+                    if let Some(declared) = member.key() {
+                        if self.key_matches(span, declared, key, false) {
+                            return Ok(false);
+                        }
+                    }
+                }
+
+                Ok(lit.members.iter().any(|member| match member {
+                    TypeElement::Index(sig) => index_signature_applies(sig, key),
+                    _ => false,
+                }))
+            }
+
+            _ => Ok(false),
+        }
+    }
+
+    /// Adds `undefined` to `ty`, which is the type of `obj[key]`, if
+    /// `noUncheckedIndexedAccess` applies to the read.
+    pub(crate) fn add_undefined_for_unchecked_index(&mut self, span: Span, obj: &Type, key: &Key, ty: Type) -> Type {
+        if !self.rule().no_unchecked_indexed_access {
+            return ty;
+        }
+
+        match self.is_read_via_index_signature(span, obj, key) {
+            Ok(true) => ty.union_with_undefined(span),
+            _ => ty,
+        }
+    }
+
+    /// Reports `TS4111` if `obj.prop` resolves only via an index signature.
+    pub(crate) fn report_error_for_property_access_from_index_signature(&mut self, span: Span, obj: &Type, prop: &Key) {
+        if !self.rule().no_property_access_from_index_signature {
+            return;
+        }
+
+        let sym = match prop {
+            Key::Normal { sym, .. } => sym,
+            _ => return,
+        };
+
+        if let Ok(true) = self.is_read_via_index_signature(span, obj, prop) {
+            self.storage.report(
+                ErrorKind::PropertyAccessFromIndexSignature {
+                    span: prop.span(),
+                    prop: sym.clone(),
+                }
+                .into(),
+            );
+        }
+    }
+}
+
+fn is_numeric_key(key: &Key) -> bool {
+    match key {
+        Key::Num(..) => true,
+        Key::Normal { sym, .. } => sym.parse::<f64>().is_ok(),
+        Key::Computed(key) => key.ty.is_num(),
+        _ => false,
+    }
+}
+
+fn index_signature_applies(sig: &IndexSignature, key: &Key) -> bool {
+    let param = match sig.params.first() {
+        Some(param) => param,
+        None => return false,
+    };
+
+    match key {
+        Key::Private(..) => false,
+        _ if param.ty.is_kwd(TsKeywordTypeKind::TsStringKeyword) => true,
+        _ if param.ty.is_kwd(TsKeywordTypeKind::TsNumberKeyword) => is_numeric_key(key),
+        _ => false,
+    }
+}
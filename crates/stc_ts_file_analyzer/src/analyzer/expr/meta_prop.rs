@@ -1,4 +1,5 @@
 use stc_ts_ast_rnode::RMetaPropExpr;
+use stc_ts_env::ModuleConfig;
 use stc_ts_errors::ErrorKind;
 use stc_ts_file_analyzer_macros::validator;
 use stc_ts_types::Type;
@@ -19,8 +20,19 @@ impl Analyzer<'_, '_> {
                 Ok(Type::any(e.span, Default::default()))
             }
 
-            _ => {
-                todo!("Unsupported meta property {:?}", e)
+            MetaPropKind::ImportMeta => {
+                if !matches!(
+                    self.env.module(),
+                    ModuleConfig::Es2020 | ModuleConfig::Es2022 | ModuleConfig::EsNext | ModuleConfig::System
+                ) {
+                    self.storage.report(ErrorKind::ImportMetaNotAllowed { span: e.span() }.into())
+                }
+
+                // `ImportMeta` from the lib files, merged with `declare global` augmentations.
+                Ok(self
+                    .env
+                    .get_global_type(e.span, &"ImportMeta".into())
+                    .unwrap_or_else(|_| Type::any(e.span, Default::default())))
             }
         }
     }
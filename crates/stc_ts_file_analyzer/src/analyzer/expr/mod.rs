@@ -27,7 +27,7 @@ use stc_ts_types::{
     PropertySignature, QueryExpr, QueryType, QueryTypeMetadata, Readonly, StaticThis, ThisType, TplElem, TplType, TplTypeMetadata,
     TypeParamInstantiation,
 };
-use stc_utils::{cache::Freeze, dev_span, ext::TypeVecExt, panic_ctx, stack};
+use stc_utils::{cache::Freeze, dev_span, ext::TypeVecExt, instantiation, panic_ctx, stack};
 use swc_atoms::js_word;
 use swc_common::{SourceMapper, Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
 use swc_ecma_ast::{op, EsVersion, TruePlusMinus, TsKeywordTypeKind, VarDeclKind};
@@ -63,6 +63,7 @@ mod call_new;
 mod const_assertion;
 mod constraint_reducer;
 mod function;
+mod index_signature;
 mod jsx;
 mod meta_prop;
 mod misc;
@@ -105,6 +106,7 @@ impl Analyzer<'_, '_> {
         type_ann: Option<&Type>,
     ) -> VResult<Type> {
         let _stack = stack::start(64);
+        let _instantiation = instantiation::start_expr(e.span());
         let _ctx = panic_ctx!(format!(
             "validate {}\n{}\nExpr: {:?}",
             self.cm.span_to_string(e.span()),
@@ -975,7 +977,9 @@ impl Analyzer<'_, '_> {
                             }
 
                             if let Some(ref type_ann) = p.type_ann {
-                                if p.optional {
+                                // With `exactOptionalPropertyTypes`, an optional property can be missing
+                                // but can't be written as `undefined`.
+                                if p.optional && !(type_mode == TypeOfMode::LValue && self.rule().exact_optional_property_types) {
                                     matching_elements.push(type_ann.clone().union_with_undefined(span));
                                 } else {
                                     matching_elements.push(*type_ann.clone());
@@ -1983,6 +1987,10 @@ impl Analyzer<'_, '_> {
                             return Err(ErrorKind::EnumCannotBeLValue { span: prop.span() }.into());
                         }
 
+                        if id_ctx == IdCtx::Var {
+                            self.report_error_for_ambient_const_enum(span, e);
+                        }
+
                         return Ok(Type::EnumVariant(EnumVariant {
                             span: match type_mode {
                                 TypeOfMode::LValue => prop.span(),
@@ -3514,6 +3522,10 @@ impl Analyzer<'_, '_> {
         let id: Id = i.into();
         let name: Name = i.into();
 
+        if type_mode == TypeOfMode::RValue {
+            self.mark_as_used(id.clone());
+        }
+
         if self.scope.is_declaring_fn(&id) {
             // We will expand this type query to proper type while calculating returns types
             // of a function.
@@ -4292,6 +4304,14 @@ impl Analyzer<'_, '_> {
             )
             .context("tried to access property of an object to calculate type of a member expression")?;
 
+        if !computed {
+            self.report_error_for_property_access_from_index_signature(span, &obj_ty, &prop);
+        }
+
+        if type_mode == TypeOfMode::RValue {
+            ty = self.add_undefined_for_unchecked_index(span, &obj_ty, &prop, ty);
+        }
+
         if !self.config.is_builtin {
             if let Some(name) = name {
                 debug_assert_ne!(ty.span(), DUMMY_SP);
@@ -4355,23 +4375,6 @@ impl Analyzer<'_, '_> {
         if should_be_optional && include_optional_chaining_undefined {
             Ok(ty.union_with_undefined(span))
         } else {
-            if self.rule().no_unchecked_indexed_access {
-                let indexed_access = match obj_ty.normalize() {
-                    Type::IndexedAccessType(_) | Type::Array(_) => true,
-                    Type::TypeLit(ty) => ty.members.iter().any(|t| matches!(t, TypeElement::Index(ty))),
-                    _ => false,
-                };
-
-                let field_is_symbol = match &prop {
-                    Key::Computed(key) => key.ty.is_symbol_like(),
-                    _ => false,
-                };
-
-                if !field_is_symbol && indexed_access {
-                    return Ok(ty.union_with_undefined(span));
-                }
-            }
-
             if !self.config.is_builtin {
                 debug_assert_ne!(ty.span(), DUMMY_SP);
             }
@@ -278,7 +278,12 @@ impl Analyzer<'_, '_> {
                 match rhs {
                     Type::TypeLit(mut rhs) => {
                         remove_readonly(&mut rhs.members);
-                        lit.members.extend(rhs.members);
+
+                        if self.rule().exact_optional_property_types {
+                            merge_optional_props(span, &mut lit.members, rhs.members);
+                        } else {
+                            lit.members.extend(rhs.members);
+                        }
                         return Ok(to);
                     }
                     Type::Union(rhs) => {
@@ -407,6 +412,35 @@ impl Analyzer<'_, '_> {
     }
 }
 
+/// Appends `rhs` to `members`, while keeping properties of `members` if the
+/// property in `rhs` is optional.
+///
+/// With `exactOptionalPropertyTypes`, `{ ...{ a: 1 }, ...{ a?: string } }` is
+/// `{ a: number | string }`, because a missing property does not override the
+/// previous one.
+fn merge_optional_props(span: Span, members: &mut Vec<TypeElement>, rhs: Vec<TypeElement>) {
+    for member in rhs {
+        if let TypeElement::Property(rp) = &member {
+            if rp.optional && !rp.key.is_computed() {
+                let prev = members.iter_mut().rev().find_map(|m| match m {
+                    TypeElement::Property(lp) if lp.key.type_eq(&rp.key) => Some(lp),
+                    _ => None,
+                });
+
+                if let Some(lp) = prev {
+                    lp.type_ann = match (lp.type_ann.take(), &rp.type_ann) {
+                        (Some(l), Some(r)) => Some(Box::new(Type::new_union(span, vec![*l, *r.clone()]))),
+                        _ => None,
+                    };
+                    continue;
+                }
+            }
+        }
+
+        members.push(member);
+    }
+}
+
 fn remove_readonly(members: &mut [TypeElement]) {
     for member in members {
         match member {
@@ -244,6 +244,19 @@ impl Analyzer<'_, '_> {
 
             inferred_return_type.freeze();
 
+            if let Some(body) = &f.body {
+                if !is_generator {
+                    child.report_implicit_return(
+                        span,
+                        f.return_type.as_deref(),
+                        &body.stmts,
+                        is_async,
+                        declared_ret_ty.as_ref(),
+                        Some(&inferred_return_type),
+                    );
+                }
+            }
+
             if f.return_type.is_none() {
                 if let Some(m) = &mut child.mutations {
                     if m.for_fns.entry(f.node_id).or_default().ret_ty.is_none() {
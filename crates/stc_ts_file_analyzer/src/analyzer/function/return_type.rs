@@ -1,9 +1,14 @@
+use std::borrow::Cow;
+
 use rnode::{VisitMut, VisitMutWith};
-use stc_ts_ast_rnode::RTsEntityName;
+use stc_ts_ast_rnode::{RStmt, RTsEntityName, RTsTypeAnn};
+use stc_ts_errors::ErrorKind;
 use stc_ts_types::{QueryExpr, QueryType, Type};
+use swc_common::{Span, Spanned};
 
 use crate::{
     analyzer::{scope::VarInfo, Analyzer},
+    util::{reachability::can_complete_normally, unwrap_builtin_with_single_arg},
     VResult,
 };
 
@@ -16,6 +21,139 @@ impl Analyzer<'_, '_> {
         ret_ty.visit_mut_with(&mut FnReturnTypeHandler { analyzer: self });
         Ok(())
     }
+
+    /// Reports `TS7030` if the end of a function body with a non-void return
+    /// type is reachable.
+    ///
+    /// `body` should be validated by `visit_stmts_for_return` first, so that
+    /// calls returning `never` are taken into account.
+    ///
+    /// Generators should not be passed to this method.
+    pub(crate) fn report_implicit_return(
+        &mut self,
+        span: Span,
+        return_type: Option<&RTsTypeAnn>,
+        body: &[RStmt],
+        is_async: bool,
+        declared: Option<&Type>,
+        inferred: Option<&Type>,
+    ) {
+        if !self.rule().no_implicit_returns
+            || self.config.is_builtin
+            || self.config.is_dts
+            || !can_complete_normally(body)
+            || self.scope.end_of_body_is_unreachable
+        {
+            return;
+        }
+
+        let span = return_type.map(|ty| ty.type_ann.span()).unwrap_or(span);
+
+        match declared {
+            Some(declared) => {
+                let declared = if is_async {
+                    unwrap_builtin_with_single_arg(declared, "Promise").unwrap_or(declared)
+                } else {
+                    declared
+                };
+                let declared = match self.normalize(Some(span), Cow::Borrowed(declared), Default::default()) {
+                    Ok(ty) => ty,
+                    Err(..) => return,
+                };
+
+                // `TS2355` and `TS2534` are reported for those.
+                if declared.is_never() || !body.iter().any(has_return) {
+                    return;
+                }
+
+                if declared.is_any() || declared.iter_union().any(|ty| ty.is_void() || ty.is_undefined()) {
+                    return;
+                }
+
+                // `TS2366` is reported if `undefined` is not assignable to the return type.
+                if self.rule().strict_null_checks && !declared.is_unknown() {
+                    return;
+                }
+            }
+            None => {
+                if !body.iter().any(has_return) {
+                    return;
+                }
+
+                match inferred {
+                    Some(inferred) => {
+                        let inferred = if is_async {
+                            unwrap_builtin_with_single_arg(inferred, "Promise").unwrap_or(inferred)
+                        } else {
+                            inferred
+                        };
+
+                        if is_void_or_any(inferred) {
+                            return;
+                        }
+                    }
+                    None => return,
+                }
+            }
+        }
+
+        self.storage.report(ErrorKind::NotAllCodePathsReturnValue { span }.into());
+    }
+
+    /// Reports `TS7030` for `return;` in a function with a non-void return
+    /// type.
+    pub(crate) fn report_empty_return(&mut self, span: Span) {
+        if !self.rule().no_implicit_returns || self.config.is_builtin || self.ctx.in_generator {
+            return;
+        }
+
+        let declared = match self.scope.declared_return_type().cloned() {
+            Some(ty) => ty,
+            None => return,
+        };
+        let declared = if self.ctx.in_async {
+            unwrap_builtin_with_single_arg(&declared, "Promise").cloned().unwrap_or(declared)
+        } else {
+            declared
+        };
+        let declared = match self.normalize(Some(span), Cow::Owned(declared), Default::default()) {
+            Ok(ty) => ty,
+            Err(..) => return,
+        };
+
+        if is_void_or_any(&declared) {
+            return;
+        }
+
+        self.storage.report(ErrorKind::NotAllCodePathsReturnValue { span }.into());
+    }
+}
+
+fn is_void_or_any(ty: &Type) -> bool {
+    ty.is_any() || ty.is_unknown() || ty.iter_union().any(|ty| ty.is_void() || ty.is_undefined())
+}
+
+/// Returns `true` if `s` contains a return statement of the current function.
+fn has_return(s: &RStmt) -> bool {
+    match s {
+        RStmt::Return(..) => true,
+        RStmt::Block(s) => s.stmts.iter().any(has_return),
+        RStmt::With(s) => has_return(&s.body),
+        RStmt::Labeled(s) => has_return(&s.body),
+        RStmt::If(s) => has_return(&s.cons) || s.alt.as_deref().map_or(false, has_return),
+        RStmt::Switch(s) => s.cases.iter().any(|case| case.cons.iter().any(has_return)),
+        RStmt::Try(s) => {
+            s.block.stmts.iter().any(has_return)
+                || s.handler.as_ref().map_or(false, |h| h.body.stmts.iter().any(has_return))
+                || s.finalizer.as_ref().map_or(false, |f| f.stmts.iter().any(has_return))
+        }
+        RStmt::While(s) => has_return(&s.body),
+        RStmt::DoWhile(s) => has_return(&s.body),
+        RStmt::For(s) => has_return(&s.body),
+        RStmt::ForIn(s) => has_return(&s.body),
+        RStmt::ForOf(s) => has_return(&s.body),
+        _ => false,
+    }
 }
 
 struct FnReturnTypeHandler<'a, 'b, 'c> {
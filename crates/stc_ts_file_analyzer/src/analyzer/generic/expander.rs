@@ -7,7 +7,7 @@ use stc_ts_generics::{
 };
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{Id, Interface, KeywordType, Readonly, TypeElement, TypeParam, TypeParamDecl, TypeParamInstantiation};
-use stc_utils::{cache::Freeze, dev_span, ext::SpanExt};
+use stc_utils::{cache::Freeze, dev_span, ext::SpanExt, instantiation};
 use swc_common::{Span, Spanned, TypeEq};
 use swc_ecma_ast::*;
 use tracing::debug;
@@ -97,6 +97,7 @@ impl Analyzer<'_, '_> {
         T: for<'aa> FoldWith<GenericExpander<'aa>> + Fix,
     {
         let _tracing = dev_span!("expand_type_params");
+        let _instantiation = instantiation::track()?;
 
         for param in params.values() {
             param.assert_valid();
@@ -4,6 +4,7 @@ use std::{
     borrow::Cow,
     collections::{hash_map::Entry, HashMap},
     iter::once,
+    mem::replace,
 };
 
 use bitflags::bitflags;
@@ -12,7 +13,7 @@ use itertools::Itertools;
 use stc_ts_ast_rnode::{RBigInt, RBool, RNumber, RStr, RTsEntityName, RTsLit};
 use stc_ts_errors::{debug::dump_type_as_string, DebugExt};
 use stc_ts_generics::expander::InferTypeResult;
-use stc_ts_type_ops::{generalization::prevent_generalize, Fix};
+use stc_ts_type_ops::{generalization::prevent_generalize, tuple_to_array::prevent_tuple_to_array, Fix};
 use stc_ts_types::{
     Array, ArrayMetadata, Class, ClassDef, ClassMember, Function, Id, InferType, Interface, KeywordType, KeywordTypeMetadata, LitType,
     Readonly, Ref, TplElem, TplType, Type, TypeElement, TypeLit, TypeParam, TypeParamMetadata, Union,
@@ -1765,6 +1766,20 @@ impl Analyzer<'_, '_> {
         let _tracing = dev_span!("prevent_generalization_of_inferred_types");
 
         for type_param in type_params {
+            if type_param.metadata.is_const {
+                if let Some(ty) = inferred.type_params.get_mut(&type_param.name) {
+                    prevent_generalize(&mut ty.inferred_type);
+                    prevent_tuple_to_array(&mut ty.inferred_type);
+
+                    // `T extends unknown[]` accepts only mutable arrays.
+                    if !matches!(type_param.constraint.as_deref().map(Type::normalize), Some(Type::Array(..))) {
+                        make_readonly_for_const_type_param(&mut ty.inferred_type);
+                    }
+                    ty.inferred_type.freeze()
+                }
+                continue;
+            }
+
             if !inferred.skip_generalization {
                 match type_param.constraint.as_deref() {
                     Some(Type::Lit(..)) => {}
@@ -1786,6 +1801,39 @@ impl Analyzer<'_, '_> {
     }
 }
 
+/// Infers `readonly` tuples and properties for an argument of a `const` type
+/// parameter, like `as const` does.
+fn make_readonly_for_const_type_param(ty: &mut Type) {
+    match ty.normalize_mut() {
+        Type::Tuple(tuple) => {
+            for elem in tuple.elems.iter_mut() {
+                make_readonly_for_const_type_param(&mut elem.ty);
+            }
+        }
+        Type::TypeLit(lit) => {
+            for member in lit.members.iter_mut() {
+                if let TypeElement::Property(p) = member {
+                    p.readonly = true;
+                    if let Some(ty) = &mut p.type_ann {
+                        make_readonly_for_const_type_param(ty);
+                    }
+                }
+            }
+            return;
+        }
+        _ => return,
+    }
+
+    let span = ty.span();
+    let tuple = replace(ty, Type::any(span, Default::default()));
+    *ty = Type::Readonly(Readonly {
+        span,
+        ty: Box::new(tuple),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    });
+}
+
 fn should_prevent_generalization(constraint: &Type) -> bool {
     match constraint.normalize() {
         Type::Lit(LitType {
@@ -231,17 +231,22 @@ impl Analyzer<'_, '_> {
         for specifier in &node.specifiers {
             match specifier {
                 RImportSpecifier::Named(named) => {
-                    //
-                    match &named.imported {
-                        Some(imported) => {
-                            self.handle_import(named.span, base, dep, Id::from(imported), Id::from(&named.local));
-                        }
-                        None => {
-                            self.handle_import(named.span, base, dep, Id::from(&named.local), Id::from(&named.local));
-                        }
+                    let orig = match &named.imported {
+                        Some(imported) => Id::from(imported),
+                        None => Id::from(&named.local),
+                    };
+
+                    if !node.type_only && !named.is_type_only && base != dep {
+                        self.check_import_of_type(named.span, &data, orig.sym(), Id::from(&named.local));
                     }
+
+                    self.handle_import(named.span, base, dep, orig, Id::from(&named.local));
                 }
                 RImportSpecifier::Default(default) => {
+                    if !node.type_only && base != dep {
+                        self.check_import_of_type(default.span, &data, &js_word!("default"), Id::from(&default.local));
+                    }
+
                     self.handle_import(default.span, base, dep, Id::word(js_word!("default")), Id::from(&default.local));
                 }
                 RImportSpecifier::Namespace(ns) => {
@@ -301,8 +306,11 @@ where
         let ctxt = self.cur_ctxt;
         let deps = find_imports_in_comments(&self.comments, span);
 
-        self.to
-            .extend(deps.into_iter().map(|src| (ctxt, DepInfo { span, src: src.to_path() })));
+        self.to.extend(
+            deps.into_iter()
+                .filter_map(|dep| dep.to_path())
+                .map(|src| (ctxt, DepInfo { span, src })),
+        );
     }
 
     pub fn find_imports<T>(comments: C, module_span: Vec<(ModuleId, Span)>, storage: &'a Storage<'a>, node: &T) -> Vec<(ModuleId, DepInfo)>
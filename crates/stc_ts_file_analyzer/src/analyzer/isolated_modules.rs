@@ -0,0 +1,110 @@
+//! `isolatedModules` and `verbatimModuleSyntax`.
+//!
+//! Files are transpiled one by one with those options, so any construct
+//! which requires type information from other files to be emitted is an
+//! error.
+
+use stc_ts_ast_rnode::{RModule, RModuleItem};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::{Enum, Id, Type};
+use swc_atoms::JsWord;
+use swc_common::{FileName, Span, Spanned};
+
+use crate::analyzer::Analyzer;
+
+impl Analyzer<'_, '_> {
+    fn is_isolated(&self) -> bool {
+        let rule = self.rule();
+
+        (rule.isolated_modules || rule.verbatim_module_syntax) && !self.config.is_builtin && !self.config.is_dts
+    }
+
+    /// Reports `TS1208` if `m` does not have any import or export.
+    pub(super) fn report_error_for_global_script(&mut self, m: &RModule) {
+        if !self.is_isolated() {
+            return;
+        }
+
+        let path = self.storage.path(self.storage.module_id(0));
+        if let FileName::Real(path) = &*path {
+            if path
+                .extension()
+                .map_or(false, |ext| ext == "js" || ext == "jsx" || ext == "mjs" || ext == "cjs")
+            {
+                return;
+            }
+        }
+
+        if m.body.iter().any(|item| matches!(item, RModuleItem::ModuleDecl(..))) {
+            return;
+        }
+
+        if let Some(first) = m.body.first() {
+            self.storage.report(
+                ErrorKind::GlobalScriptWithIsolatedModules {
+                    span: first.span().shrink_to_lo(),
+                }
+                .into(),
+            );
+        }
+    }
+
+    /// Called for an import which is not marked as type-only.
+    ///
+    /// If the imported item is only a type, it's recorded so re-exports of it
+    /// can be checked, and `TS1484` is reported with `verbatimModuleSyntax`.
+    pub(super) fn check_import_of_type(&mut self, span: Span, data: &Type, orig: &JsWord, local: Id) {
+        if !self.is_isolated() || !is_type_only_export(data, orig) {
+            return;
+        }
+
+        if self.rule().verbatim_module_syntax {
+            self.storage.report(
+                ErrorKind::TypeImportWithoutImportType {
+                    span,
+                    name: local.sym().clone(),
+                }
+                .into(),
+            );
+        }
+
+        self.data.implicit_type_imports.insert(local);
+    }
+
+    /// Reports `TS1205` for `export { T }` and `export { T } from 'foo'`
+    /// without `export type`, where `T` is a type.
+    ///
+    /// `data` is the module type of the source module, if it's a re-export
+    /// from another module.
+    pub(super) fn report_error_for_re_export_of_type(&mut self, span: Span, data: Option<&Type>, orig: &Id) {
+        if !self.is_isolated() {
+            return;
+        }
+
+        let is_type = match data {
+            Some(data) => is_type_only_export(data, orig.sym()),
+            None => self.data.implicit_type_imports.contains(orig),
+        };
+
+        if is_type {
+            self.storage.report(ErrorKind::ReExportTypeWithoutExportType { span }.into());
+        }
+    }
+
+    /// Reports `TS2748` for a value access to an ambient const enum.
+    pub(crate) fn report_error_for_ambient_const_enum(&mut self, span: Span, e: &Enum) {
+        if !self.is_isolated() || !e.is_const || !e.declare {
+            return;
+        }
+
+        self.storage.report(ErrorKind::AmbientConstEnumWithIsolatedModules { span }.into());
+    }
+}
+
+/// Returns `true` if `sym` is exported from the module `data` only as a type.
+fn is_type_only_export(data: &Type, sym: &JsWord) -> bool {
+    match data.normalize() {
+        Type::Module(data) => !data.exports.vars.contains_key(sym) && data.exports.types.contains_key(sym),
+        _ => false,
+    }
+}
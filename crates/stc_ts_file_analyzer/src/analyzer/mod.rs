@@ -65,6 +65,7 @@ mod generalize;
 mod generic;
 mod hoisting;
 mod import;
+mod isolated_modules;
 mod pat;
 mod props;
 mod relation;
@@ -89,6 +90,10 @@ pub(crate) struct Ctx {
 
     in_constructor_param: bool,
 
+    /// `true` for parameters of class methods and constructors. Decorators of
+    /// them are validated with the class.
+    in_class_member_params: bool,
+
     disallow_unknown_object_property: bool,
 
     use_undefined_for_empty_array_lit: bool,
@@ -192,6 +197,9 @@ pub(crate) struct Ctx {
 
     in_module: bool,
 
+    /// `true` if the current file has an import or an export.
+    in_es_module: bool,
+
     checking_switch_discriminant_as_bin: bool,
 
     /// If true, obj of the expression statement is `super` keyword.
@@ -201,6 +209,11 @@ pub(crate) struct Ctx {
 
     is_type_predicate: bool,
 
+    /// Span of the call expression of the current expression statement.
+    /// Assertion functions narrow the following code only if they are called
+    /// there.
+    expr_stmt_call_span: Option<Span>,
+
     /// True if validating object properties that have get accessors
     get_accessor_prop: bool,
 
@@ -286,9 +299,24 @@ struct AnalyzerData {
 
     unresolved_imports: FxHashSet<Id>,
 
+    /// Imports resolved only to types, which are not marked as type-only.
+    implicit_type_imports: FxHashSet<Id>,
+
     /// Spans of declared variables.
     var_spans: FxHashMap<Id, Vec<(VarKind, Span)>>,
 
+    /// Variables declared without an initializer, which should be assigned
+    /// before being read. Used to report `TS2454`.
+    vars_requiring_assignment: FxHashSet<Id>,
+
+    /// Variables declared without a type annotation, which cannot be the
+    /// target of an assertion call. Used to report `TS2775`.
+    vars_without_type_ann: FxHashSet<Id>,
+
+    /// Bindings read while resolving identifiers and type references. Used to
+    /// report unused locals and parameters.
+    used_bindings: FxHashSet<Id>,
+
     /// Spans of functions **with body**.
     fn_impl_spans: FxHashMap<Id, Vec<Span>>,
 
@@ -487,6 +515,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
                 in_class_member: false,
                 in_const_assertion: false,
                 in_constructor_param: false,
+                in_class_member_params: false,
                 disallow_unknown_object_property: false,
                 use_undefined_for_empty_array_lit: false,
                 allow_module_var: false,
@@ -538,10 +567,12 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
                 is_not_topmost_type: false,
                 is_fn_param: false,
                 in_module: false,
+                in_es_module: false,
                 checking_switch_discriminant_as_bin: false,
                 obj_is_super: false,
                 use_properties_of_this_implicitly: false,
                 is_type_predicate: false,
+                expr_stmt_call_span: None,
                 get_accessor_prop: false,
                 set_accessor_prop: false,
             },
@@ -733,7 +764,11 @@ impl Load for NoopLoader {
         unreachable!()
     }
 
-    fn declare_module(&self, name: &JsWord, module: Type) -> ModuleId {
+    fn declare_module(&self, base: &Arc<FileName>, name: &JsWord, module: Type) -> ModuleId {
+        unreachable!()
+    }
+
+    fn augment_module(&self, base: &Arc<FileName>, dep: &str, module: Type) -> Option<ModuleId> {
         unreachable!()
     }
 }
@@ -754,6 +789,10 @@ impl Analyzer<'_, '_> {
 
         self.validate_stmts_with_hoisting(&items);
 
+        for m in modules {
+            self.report_unused(m);
+        }
+
         Ok(())
     }
 }
@@ -762,6 +801,7 @@ impl Analyzer<'_, '_> {
 impl Analyzer<'_, '_> {
     fn validate(&mut self, m: &RModule) {
         self.ctx.in_module = true;
+        self.ctx.in_es_module = m.body.iter().any(|item| matches!(item, RModuleItem::ModuleDecl(..)));
         let is_dts = self.config.is_dts;
 
         debug_assert!(GLOBALS.is_set(), "Analyzer requires swc_common::GLOBALS");
@@ -816,6 +856,10 @@ impl Analyzer<'_, '_> {
             m.body.visit_children_with(self);
         } else {
             self.validate_stmts_and_collect(&items_ref);
+
+            self.report_unused(m);
+            self.report_errors_for_unassigned_vars(m);
+            self.report_error_for_global_script(m);
         }
 
         Ok(())
@@ -838,10 +882,8 @@ impl Analyzer<'_, '_> {
 /// Done
 #[validator]
 impl Analyzer<'_, '_> {
-    fn validate(&mut self, d: &RDecorator) {
-        d.expr.validate_with_default(self).report(&mut self.storage);
-
-        Ok(())
+    fn validate(&mut self, d: &RDecorator) -> VResult<Type> {
+        d.expr.validate_with_default(self)
     }
 }
 
@@ -857,6 +899,8 @@ impl Analyzer<'_, '_> {
         self.with_ctx(ctx).with(|analyzer: &mut Analyzer| {
             let (var_ty, type_ty) = match node.module_ref {
                 RTsModuleRef::TsEntityName(ref e) => {
+                    analyzer.mark_entity_as_used(e);
+
                     let var_ty = analyzer.resolve_typeof(node.span, e).map(|ty| ty.freezed());
 
                     let type_ty = analyzer
@@ -1083,7 +1127,15 @@ impl Analyzer<'_, '_> {
                         }
                     }
 
-                    let module_id = self.loader.declare_module(&s.value, ty.clone());
+                    let base = self.storage.path(ctxt);
+
+                    // `declare module "foo"` in a module augments the module `foo` resolves to.
+                    let augmented = if self.ctx.in_es_module && !is_builtin {
+                        self.loader.augment_module(&base, &s.value, ty.clone())
+                    } else {
+                        None
+                    };
+                    let module_id = augmented.unwrap_or_else(|| self.loader.declare_module(&base, &s.value, ty.clone()));
 
                     self.insert_import_info(ctxt, module_id, ty.clone()).report(&mut self.storage);
                 }
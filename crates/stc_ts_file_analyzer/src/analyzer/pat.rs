@@ -5,10 +5,10 @@ use stc_ts_ast_rnode::{
 };
 use stc_ts_errors::{ErrorKind, Errors};
 use stc_ts_types::{
-    Array, ArrayMetadata, CommonTypeMetadata, Instance, Key, KeywordType, PropertySignature, RestType, Tuple, TupleElement, TypeElMetadata,
-    TypeElement, TypeLit, TypeLitMetadata,
+    Array, ArrayMetadata, CommonTypeMetadata, Id, Instance, Key, KeywordType, PropertySignature, RestType, Tuple, TupleElement,
+    TypeElMetadata, TypeElement, TypeLit, TypeLitMetadata,
 };
-use stc_ts_utils::PatExt;
+use stc_ts_utils::{find_ids_in_pat, PatExt};
 use stc_utils::{cache::Freeze, dev_span, ext::TypeVecExt};
 use swc_atoms::js_word;
 use swc_common::{Spanned, TypeEq, DUMMY_SP};
@@ -170,12 +170,18 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RParam) -> VResult<ty::FnParam> {
-        node.decorators.visit_with(self);
+        if !self.ctx.in_class_member_params {
+            for d in &node.decorators {
+                self.storage.report(ErrorKind::DecoratorNotValidHere { span: d.span }.into());
+                d.validate_with(self).report(&mut self.storage);
+            }
+        }
 
         self.default_any_pat(&node.pat);
 
         let ctx = Ctx {
             pat_mode: PatMode::Decl,
+            in_class_member_params: false,
             ..self.ctx
         };
         node.pat.validate_with(&mut *self.with_ctx(ctx))
@@ -190,6 +196,25 @@ impl Analyzer<'_, '_> {
 }
 
 impl Analyzer<'_, '_> {
+    /// Records the names in `pat` which are not declared with a type
+    /// annotation, which cannot be the target of an assertion call.
+    ///
+    /// Names bound by a destructuring pattern never have their own annotation.
+    pub(crate) fn record_vars_without_type_ann(&mut self, pat: &RPat) {
+        match pat {
+            RPat::Ident(i) => {
+                if i.type_ann.is_none() {
+                    self.data.vars_without_type_ann.insert(i.id.clone().into());
+                }
+            }
+            RPat::Assign(RAssignPat { left, .. }) if matches!(&**left, RPat::Ident(..)) => self.record_vars_without_type_ann(left),
+            _ => {
+                let ids: Vec<Id> = find_ids_in_pat(pat);
+                self.data.vars_without_type_ann.extend(ids);
+            }
+        }
+    }
+
     fn validate_pat(&mut self, p: &RPat) -> VResult<ty::FnParam> {
         if !self.config.is_builtin {
             debug_assert_ne!(p.span(), DUMMY_SP, "A pattern should have a valid span");
@@ -270,6 +295,7 @@ impl Analyzer<'_, '_> {
                 p.visit_with(&mut visitor);
 
                 self.scope.declaring.extend(names.clone());
+                self.record_vars_without_type_ann(p);
 
                 if !self.config.is_builtin {
                     ty = match self.add_vars(
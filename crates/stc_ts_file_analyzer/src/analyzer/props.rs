@@ -479,6 +479,17 @@ impl Analyzer<'_, '_> {
                                 });
                             inferred_ret_ty.freeze();
 
+                            if !p.function.is_generator {
+                                child.report_implicit_return(
+                                    span,
+                                    p.function.return_type.as_deref(),
+                                    &body.stmts,
+                                    p.function.is_async,
+                                    ret_ty.as_ref(),
+                                    Some(&inferred_ret_ty),
+                                );
+                            }
+
                             // Preserve return type if `this` is not involved in return type.
                             if p.function.return_type.is_none() {
                                 inferred_ret_ty = if inferred_ret_ty.metadata().infected_by_this_in_object_literal {
@@ -55,6 +55,7 @@ use crate::{
 
 mod this;
 mod type_param;
+mod unused;
 pub(crate) mod vars;
 
 macro_rules! no_ref {
@@ -104,6 +105,10 @@ pub(crate) struct Scope<'a> {
     pub(super) super_class: Option<Box<Type>>,
 
     pub(super) return_values: ReturnValues,
+    /// `true` if the end of the statements validated by the last call to
+    /// `visit_stmts_for_return` is unreachable, e.g. because of a call
+    /// returning `never`.
+    pub(super) end_of_body_is_unreachable: bool,
 
     /// `0` if we are not trying to expand it.
     expand_triage_depth: u8,
@@ -388,6 +393,7 @@ impl Scope<'_> {
             this_object_members: self.this_object_members,
             super_class: self.super_class,
             return_values: self.return_values,
+            end_of_body_is_unreachable: self.end_of_body_is_unreachable,
             expand_triage_depth: self.expand_triage_depth,
             is_call_arg_count_unknown: self.is_call_arg_count_unknown,
             type_params: self.type_params,
@@ -737,6 +743,29 @@ impl Analyzer<'_, '_> {
         Ok(ty)
     }
 
+    /// Expands all references to named types in `ty`, including ones in
+    /// parameters and return types of functions.
+    ///
+    /// This is used by tools which compare types, and errors are not reported.
+    pub fn expand_fully(&mut self, span: Span, ty: &Type) -> Type {
+        let expanded = self.expand(
+            span,
+            ty.clone(),
+            ExpandOpts {
+                full: true,
+                expand_union: true,
+                expand_params: true,
+                expand_ret_ty: true,
+                ..Default::default()
+            },
+        );
+
+        match expanded {
+            Ok(ty) => ty.freezed(),
+            Err(..) => ty.clone(),
+        }
+    }
+
     pub(super) fn expand_type_params_using_scope(&mut self, ty: Type) -> VResult<Type> {
         let type_params = take(&mut self.scope.type_params);
         let res = self.expand_type_params(&type_params, ty, Default::default());
@@ -1898,6 +1927,7 @@ impl<'a> Scope<'a> {
             this_object_members: Default::default(),
             super_class: None,
             return_values: Default::default(),
+            end_of_body_is_unreachable: false,
             expand_triage_depth: 0,
             is_call_arg_count_unknown: false,
             type_params: Default::default(),
@@ -2576,7 +2606,13 @@ impl Expander<'_, '_, '_> {
                                         )
                                         .ok();
                                     if let Some(type_params) = type_params {
-                                        ty = self.analyzer.expand_type_params(&type_params, ty, Default::default()).unwrap();
+                                        ty = match self.analyzer.expand_type_params(&type_params, ty, Default::default()) {
+                                            Ok(ty) => ty,
+                                            Err(err) => {
+                                                self.analyzer.storage.report(err);
+                                                Type::any(span, Default::default())
+                                            }
+                                        };
                                     }
 
                                     element.ty = Box::new(ty);
@@ -2601,7 +2637,13 @@ impl Expander<'_, '_, '_> {
                             .infer_ts_infer_types(span, extends_type, obj_type, Default::default())
                             .ok();
                         if let Some(type_params) = type_params {
-                            ty = self.analyzer.expand_type_params(&type_params, ty, Default::default()).unwrap();
+                            ty = match self.analyzer.expand_type_params(&type_params, ty, Default::default()) {
+                                Ok(ty) => ty,
+                                Err(err) => {
+                                    self.analyzer.storage.report(err);
+                                    Type::any(span, Default::default())
+                                }
+                            };
                         }
 
                         return ty;
@@ -0,0 +1,665 @@
+//! `noUnusedLocals` and `noUnusedParameters`.
+//!
+//! Bindings are identified by [Id], which is unique for each declaration after
+//! the resolver pass. Reads are recorded by the analyzer while it resolves
+//! identifiers and type references against the scope (see
+//! [Analyzer::mark_as_used]), and declarations are collected from the module
+//! after validation.
+//!
+//! A binding is used if it is read at least once. Writes (`a = 1`) resolve the
+//! binding as an lvalue and are not reads.
+
+use fxhash::{FxHashMap, FxHashSet};
+use rnode::{Visit, VisitWith};
+use stc_ts_ast_rnode::{
+    RArrowExpr, RAssignExpr, RBlockStmt, RClass, RClassDecl, RClassMember, RConstructor, RDecl, RExportDecl, RExpr, RFnDecl, RForInStmt,
+    RForOfStmt, RFunction, RIdent, RImportDecl, RImportSpecifier, RJSXElement, RJSXFragment, RMemberExpr, RMemberProp, RModule,
+    RModuleDecl, RModuleItem, RObjectPatProp, RParamOrTsParamProp, RPat, RPatOrExpr, RPropName, RSetterProp, RTsEntityName, RTsEnumDecl,
+    RTsImportEqualsDecl, RTsInterfaceDecl, RTsModuleDecl, RTsTypeAliasDecl, RTsTypeParamDecl, RVarDecl, RVarDeclOrPat, RVarDeclarator,
+};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::Id;
+use swc_atoms::{js_word, JsWord};
+use swc_common::Span;
+use swc_ecma_ast::*;
+
+use crate::analyzer::Analyzer;
+
+impl Analyzer<'_, '_> {
+    /// Records a read of a binding.
+    pub(crate) fn mark_as_used(&mut self, id: Id) {
+        let rule = self.rule();
+        if rule.no_unused_locals || rule.no_unused_parameters {
+            self.data.used_bindings.insert(id);
+        }
+    }
+
+    /// Records a read of the left-most identifier of `name`, e.g. `a` for
+    /// `a.b.C`.
+    pub(crate) fn mark_entity_as_used(&mut self, name: &RTsEntityName) {
+        match name {
+            RTsEntityName::Ident(i) => self.mark_as_used(i.into()),
+            RTsEntityName::TsQualifiedName(q) => self.mark_entity_as_used(&q.left),
+        }
+    }
+
+    /// Same as [Analyzer::mark_entity_as_used], but for expressions like
+    /// `a.b.C` of `implements a.b.C`.
+    pub(crate) fn mark_expr_as_used(&mut self, e: &RExpr) {
+        match e {
+            RExpr::Ident(i) => self.mark_as_used(i.into()),
+            RExpr::Member(m) => self.mark_expr_as_used(&m.obj),
+            _ => {}
+        }
+    }
+
+    /// Reports unused locals and parameters of a module.
+    pub(crate) fn report_unused(&mut self, m: &RModule) {
+        let rule = self.rule();
+        if self.config.is_builtin || self.config.is_dts || self.ctx.in_declare || !(rule.no_unused_locals || rule.no_unused_parameters) {
+            return;
+        }
+
+        let mut finder = UnusedFinder {
+            is_module: m.body.iter().any(|item| matches!(item, RModuleItem::ModuleDecl(..))),
+            top_level: true,
+            ..Default::default()
+        };
+        m.visit_with(&mut finder);
+
+        for err in finder.into_errors(&self.data.used_bindings, rule.no_unused_locals, rule.no_unused_parameters) {
+            self.storage.report(err.into());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeclKind {
+    /// TS6133
+    Value,
+    /// TS6196
+    Type,
+    Param,
+    TypeParam,
+}
+
+struct Decl {
+    id: Id,
+    span: Span,
+    kind: DeclKind,
+    /// Index of the object pattern containing this binding.
+    pat: Option<usize>,
+    /// For type parameters of interfaces, which are checked only if the
+    /// interface is not merged.
+    owner: Option<Id>,
+}
+
+struct ObjectPat {
+    span: Span,
+    len: usize,
+}
+
+#[derive(Default)]
+struct PrivateMembers {
+    /// `(name, is_private_name, span)`
+    declared: Vec<(JsWord, bool, Span)>,
+    used: FxHashSet<(JsWord, bool)>,
+}
+
+#[derive(Default)]
+struct UnusedFinder {
+    is_module: bool,
+    /// `true` for items of the module which are not in a block.
+    top_level: bool,
+    in_declare: bool,
+    /// Set while visiting the head of `for-in` and `for-of`.
+    in_for_in_of_head: bool,
+    has_jsx: bool,
+
+    decls: Vec<Decl>,
+    object_pats: Vec<ObjectPat>,
+    exported: FxHashSet<Id>,
+    /// Bindings which are used without being read, like `a` of `{ a, ...b }`.
+    implicitly_used: FxHashSet<Id>,
+
+    /// Stack of classes.
+    classes: Vec<PrivateMembers>,
+    /// `(name, span)` of private class members which are not used.
+    unused_members: Vec<(JsWord, Span)>,
+}
+
+impl UnusedFinder {
+    fn should_declare(&self) -> bool {
+        !self.in_declare && (self.is_module || !self.top_level)
+    }
+
+    fn declare(&mut self, i: &RIdent, kind: DeclKind, pat: Option<usize>, owner: Option<Id>) {
+        self.decls.push(Decl {
+            id: i.into(),
+            span: i.span,
+            kind,
+            pat,
+            owner,
+        });
+    }
+
+    /// Declares bindings in `pat`.
+    fn declare_pat(&mut self, pat: &RPat, kind: DeclKind, in_array_pat: bool, obj_pat: Option<usize>) {
+        match pat {
+            RPat::Ident(i) => {
+                let exempted_by_underscore = kind == DeclKind::Param || in_array_pat || self.in_for_in_of_head;
+                if i.id.sym == js_word!("this") || (exempted_by_underscore && i.id.sym.starts_with('_')) {
+                    return;
+                }
+
+                self.declare(&i.id, kind, obj_pat, None);
+            }
+            RPat::Array(arr) => {
+                for elem in arr.elems.iter().flatten() {
+                    self.declare_pat(elem, kind, true, None);
+                }
+            }
+            RPat::Object(obj) => {
+                let has_rest = matches!(obj.props.last(), Some(RObjectPatProp::Rest(..)));
+
+                let idx = self.object_pats.len();
+                self.object_pats.push(ObjectPat {
+                    span: obj.span,
+                    len: obj.props.len(),
+                });
+
+                for prop in &obj.props {
+                    match prop {
+                        // In `{ a, ...b }`, `a` is considered as used because it removes a
+                        // property from `b`.
+                        RObjectPatProp::KeyValue(..) | RObjectPatProp::Assign(..) if has_rest => {
+                            let mut ids = vec![];
+                            prop.visit_with(&mut crate::analyzer::util::VarVisitor { names: &mut ids });
+                            self.implicitly_used.extend(ids);
+                        }
+                        RObjectPatProp::KeyValue(kv) => {
+                            self.declare_pat(&kv.value, kind, false, Some(idx));
+                        }
+                        RObjectPatProp::Assign(a) => {
+                            self.declare(&a.key, kind, Some(idx), None);
+                        }
+                        RObjectPatProp::Rest(r) => {
+                            self.declare_pat(&r.arg, kind, false, Some(idx));
+                        }
+                    }
+                }
+            }
+            RPat::Assign(a) => {
+                self.declare_pat(&a.left, kind, in_array_pat, obj_pat);
+            }
+            RPat::Rest(r) => {
+                self.declare_pat(&r.arg, kind, in_array_pat, obj_pat);
+            }
+            RPat::Invalid(..) | RPat::Expr(..) => {}
+        }
+    }
+
+    fn declare_type_params(&mut self, type_params: Option<&RTsTypeParamDecl>, owner: Option<Id>) {
+        if let Some(type_params) = type_params {
+            for param in &type_params.params {
+                if param.name.sym.starts_with('_') {
+                    continue;
+                }
+
+                self.declare(&param.name, DeclKind::TypeParam, None, owner.clone());
+            }
+        }
+    }
+
+    fn declare_params<'a>(&mut self, params: impl IntoIterator<Item = &'a RPat>) {
+        for pat in params {
+            self.declare_pat(pat, DeclKind::Param, false, None);
+        }
+    }
+
+    /// Marks a private member as used. Only `this.a` and `obj.#a` are
+    /// considered, as other objects may have an unrelated member with the same
+    /// name.
+    fn use_member(&mut self, obj: &RExpr, prop: &RMemberProp) {
+        let key = match prop {
+            RMemberProp::Ident(i) if matches!(obj, RExpr::This(..)) => (i.sym.clone(), false),
+            RMemberProp::PrivateName(p) => (p.id.sym.clone(), true),
+            _ => return,
+        };
+
+        for class in &mut self.classes {
+            class.used.insert(key.clone());
+        }
+    }
+
+    fn with_scope<F>(&mut self, in_declare: bool, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let old_top_level = self.top_level;
+        let old_in_declare = self.in_declare;
+        self.top_level = false;
+        self.in_declare |= in_declare;
+
+        op(self);
+
+        self.top_level = old_top_level;
+        self.in_declare = old_in_declare;
+    }
+
+    /// `used` is the set of bindings read by the analyzer.
+    fn into_errors(self, used: &FxHashSet<Id>, check_locals: bool, check_params: bool) -> Vec<ErrorKind> {
+        let mut decl_counts = FxHashMap::<Id, usize>::default();
+        for decl in &self.decls {
+            *decl_counts.entry(decl.id.clone()).or_default() += 1;
+        }
+
+        let is_used = |id: &Id| {
+            used.contains(id) || self.exported.contains(id) || self.implicitly_used.contains(id) || (self.has_jsx && &**id.sym() == "React")
+        };
+
+        let mut unused_in_pat = vec![0; self.object_pats.len()];
+        for decl in &self.decls {
+            if let Some(idx) = decl.pat {
+                if !is_used(&decl.id) {
+                    unused_in_pat[idx] += 1;
+                }
+            }
+        }
+
+        let mut errors = vec![];
+        let mut reported = FxHashSet::default();
+        let mut reported_pats = FxHashSet::default();
+
+        for decl in &self.decls {
+            let enabled = match decl.kind {
+                DeclKind::Value | DeclKind::Type => check_locals,
+                DeclKind::Param | DeclKind::TypeParam => check_params,
+            };
+            if !enabled || is_used(&decl.id) || !reported.insert(decl.id.clone()) {
+                continue;
+            }
+
+            if let Some(owner) = &decl.owner {
+                if decl_counts.get(owner).copied().unwrap_or_default() > 1 {
+                    continue;
+                }
+            }
+
+            if let Some(idx) = decl.pat {
+                let pat = &self.object_pats[idx];
+                if pat.len > 1 && unused_in_pat[idx] == pat.len {
+                    if reported_pats.insert(idx) {
+                        errors.push(ErrorKind::AllDestructuredElementsUnused { span: pat.span });
+                    }
+                    continue;
+                }
+            }
+
+            errors.push(match decl.kind {
+                DeclKind::Type => ErrorKind::UnusedTypeDeclaration {
+                    span: decl.span,
+                    name: decl.id.sym().clone(),
+                },
+                _ => ErrorKind::UnusedDeclaration {
+                    span: decl.span,
+                    name: decl.id.sym().clone(),
+                },
+            });
+        }
+
+        if check_locals {
+            for (name, span) in self.unused_members {
+                errors.push(ErrorKind::UnusedDeclaration { span, name });
+            }
+        }
+
+        errors
+    }
+}
+
+impl Visit<RBlockStmt> for UnusedFinder {
+    fn visit(&mut self, s: &RBlockStmt) {
+        self.with_scope(false, |f| s.visit_children_with(f));
+    }
+}
+
+impl Visit<RVarDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RVarDecl) {
+        let old = self.in_declare;
+        self.in_declare |= d.declare;
+
+        d.visit_children_with(self);
+
+        self.in_declare = old;
+    }
+}
+
+impl Visit<RVarDeclarator> for UnusedFinder {
+    fn visit(&mut self, d: &RVarDeclarator) {
+        if self.should_declare() {
+            self.declare_pat(&d.name, DeclKind::Value, false, None);
+        }
+
+        let old = self.in_for_in_of_head;
+        self.in_for_in_of_head = false;
+        d.visit_children_with(self);
+        self.in_for_in_of_head = old;
+    }
+}
+
+impl Visit<RForInStmt> for UnusedFinder {
+    fn visit(&mut self, s: &RForInStmt) {
+        self.with_scope(false, |f| {
+            if let RVarDeclOrPat::VarDecl(..) = &s.left {
+                f.in_for_in_of_head = true;
+            }
+            s.left.visit_with(f);
+            f.in_for_in_of_head = false;
+
+            s.right.visit_with(f);
+            s.body.visit_with(f);
+        })
+    }
+}
+
+impl Visit<RForOfStmt> for UnusedFinder {
+    fn visit(&mut self, s: &RForOfStmt) {
+        self.with_scope(false, |f| {
+            if let RVarDeclOrPat::VarDecl(..) = &s.left {
+                f.in_for_in_of_head = true;
+            }
+            s.left.visit_with(f);
+            f.in_for_in_of_head = false;
+
+            s.right.visit_with(f);
+            s.body.visit_with(f);
+        })
+    }
+}
+
+impl Visit<RFnDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RFnDecl) {
+        if !d.declare && self.should_declare() {
+            self.declare(&d.ident, DeclKind::Value, None, None);
+        }
+
+        self.with_scope(d.declare, |f| d.visit_children_with(f));
+    }
+}
+
+impl Visit<RFunction> for UnusedFinder {
+    fn visit(&mut self, f: &RFunction) {
+        self.with_scope(false, |v| {
+            // Overloads and abstract methods.
+            if f.body.is_some() && !v.in_declare {
+                v.declare_params(f.params.iter().map(|p| &p.pat));
+                v.declare_type_params(f.type_params.as_deref(), None);
+            }
+
+            f.visit_children_with(v);
+        })
+    }
+}
+
+impl Visit<RArrowExpr> for UnusedFinder {
+    fn visit(&mut self, f: &RArrowExpr) {
+        self.with_scope(false, |v| {
+            if !v.in_declare {
+                v.declare_params(&f.params);
+                v.declare_type_params(f.type_params.as_deref(), None);
+            }
+
+            f.visit_children_with(v);
+        })
+    }
+}
+
+impl Visit<RConstructor> for UnusedFinder {
+    fn visit(&mut self, c: &RConstructor) {
+        self.with_scope(false, |v| {
+            if c.body.is_some() && !v.in_declare {
+                // Parameter properties are class members.
+                v.declare_params(c.params.iter().filter_map(|p| match p {
+                    RParamOrTsParamProp::Param(p) => Some(&p.pat),
+                    RParamOrTsParamProp::TsParamProp(..) => None,
+                }));
+            }
+
+            c.visit_children_with(v);
+        })
+    }
+}
+
+impl Visit<RSetterProp> for UnusedFinder {
+    fn visit(&mut self, p: &RSetterProp) {
+        self.with_scope(false, |v| {
+            if p.body.is_some() && !v.in_declare {
+                v.declare_params([&*p.param]);
+            }
+
+            p.visit_children_with(v);
+        })
+    }
+}
+
+impl Visit<RClassDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RClassDecl) {
+        if !d.declare && self.should_declare() {
+            self.declare(&d.ident, DeclKind::Type, None, None);
+        }
+
+        self.with_scope(d.declare, |f| d.visit_children_with(f));
+    }
+}
+
+impl Visit<RClass> for UnusedFinder {
+    fn visit(&mut self, c: &RClass) {
+        self.with_scope(false, |f| {
+            if !f.in_declare {
+                f.declare_type_params(c.type_params.as_deref(), None);
+            }
+
+            let mut members = PrivateMembers::default();
+            if !f.in_declare {
+                for member in &c.body {
+                    match member {
+                        RClassMember::ClassProp(p) if p.accessibility == Some(Accessibility::Private) && !p.declare => {
+                            if let RPropName::Ident(i) = &p.key {
+                                members.declared.push((i.sym.clone(), false, i.span));
+                            }
+                        }
+                        RClassMember::Method(m) if m.accessibility == Some(Accessibility::Private) => {
+                            if let RPropName::Ident(i) = &m.key {
+                                members.declared.push((i.sym.clone(), false, i.span));
+                            }
+                        }
+                        RClassMember::PrivateProp(p) => {
+                            members.declared.push((p.key.id.sym.clone(), true, p.key.span));
+                        }
+                        RClassMember::PrivateMethod(m) => {
+                            members.declared.push((m.key.id.sym.clone(), true, m.key.span));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            f.classes.push(members);
+
+            c.visit_children_with(f);
+
+            let members = f.classes.pop().unwrap();
+            for (name, is_private_name, span) in members.declared {
+                if !members.used.contains(&(name.clone(), is_private_name)) {
+                    let name = if is_private_name { format!("#{}", name).into() } else { name };
+
+                    f.unused_members.push((name, span));
+                }
+            }
+        })
+    }
+}
+
+impl Visit<RMemberExpr> for UnusedFinder {
+    fn visit(&mut self, e: &RMemberExpr) {
+        self.use_member(&e.obj, &e.prop);
+
+        e.visit_children_with(self);
+    }
+}
+
+impl Visit<RAssignExpr> for UnusedFinder {
+    fn visit(&mut self, e: &RAssignExpr) {
+        if e.op == op!("=") {
+            // Writing to a member is not a read of the member.
+            if let RPatOrExpr::Expr(box RExpr::Member(m)) | RPatOrExpr::Pat(box RPat::Expr(box RExpr::Member(m))) = &e.left {
+                m.visit_children_with(self);
+                e.right.visit_with(self);
+                return;
+            }
+        }
+
+        e.visit_children_with(self);
+    }
+}
+
+impl Visit<RTsInterfaceDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RTsInterfaceDecl) {
+        if !d.declare && self.should_declare() {
+            self.declare(&d.id, DeclKind::Type, None, None);
+        }
+
+        self.with_scope(d.declare, |f| {
+            if !f.in_declare {
+                f.declare_type_params(d.type_params.as_deref(), Some((&d.id).into()));
+            }
+
+            d.visit_children_with(f)
+        });
+    }
+}
+
+impl Visit<RTsTypeAliasDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RTsTypeAliasDecl) {
+        if !d.declare && self.should_declare() {
+            self.declare(&d.id, DeclKind::Type, None, None);
+        }
+
+        self.with_scope(d.declare, |f| {
+            if !f.in_declare {
+                f.declare_type_params(d.type_params.as_deref(), None);
+            }
+
+            d.visit_children_with(f)
+        });
+    }
+}
+
+impl Visit<RTsEnumDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RTsEnumDecl) {
+        if !d.declare && self.should_declare() {
+            self.declare(&d.id, DeclKind::Type, None, None);
+        }
+
+        self.with_scope(d.declare, |f| d.visit_children_with(f));
+    }
+}
+
+impl Visit<RTsModuleDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RTsModuleDecl) {
+        self.with_scope(d.declare || d.global, |f| d.visit_children_with(f));
+    }
+}
+
+impl Visit<RImportDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RImportDecl) {
+        for specifier in &d.specifiers {
+            let (local, is_type_only) = match specifier {
+                RImportSpecifier::Named(s) => (&s.local, s.is_type_only),
+                RImportSpecifier::Default(s) => (&s.local, false),
+                RImportSpecifier::Namespace(s) => (&s.local, false),
+            };
+
+            let kind = if d.type_only || is_type_only {
+                DeclKind::Type
+            } else {
+                DeclKind::Value
+            };
+            self.declare(local, kind, None, None);
+        }
+
+        d.visit_children_with(self);
+    }
+}
+
+impl Visit<RTsImportEqualsDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RTsImportEqualsDecl) {
+        if !d.is_export && !d.declare && self.should_declare() {
+            let kind = if d.is_type_only { DeclKind::Type } else { DeclKind::Value };
+            self.declare(&d.id, kind, None, None);
+        }
+
+        d.visit_children_with(self);
+    }
+}
+
+impl Visit<RExportDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RExportDecl) {
+        match &d.decl {
+            RDecl::Class(c) => {
+                self.exported.insert((&c.ident).into());
+            }
+            RDecl::Fn(f) => {
+                self.exported.insert((&f.ident).into());
+            }
+            RDecl::Var(v) => {
+                let mut ids = vec![];
+                v.decls
+                    .iter()
+                    .for_each(|d| d.name.visit_with(&mut crate::analyzer::util::VarVisitor { names: &mut ids }));
+                self.exported.extend(ids);
+            }
+            RDecl::TsInterface(i) => {
+                self.exported.insert((&i.id).into());
+            }
+            RDecl::TsTypeAlias(a) => {
+                self.exported.insert((&a.id).into());
+            }
+            RDecl::TsEnum(e) => {
+                self.exported.insert((&e.id).into());
+            }
+            RDecl::TsModule(..) => {}
+        }
+
+        d.visit_children_with(self);
+    }
+}
+
+impl Visit<RModuleDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RModuleDecl) {
+        // `export import a = b.c;`
+        if let RModuleDecl::TsImportEquals(i) = d {
+            if i.is_export {
+                self.exported.insert((&i.id).into());
+            }
+        }
+
+        d.visit_children_with(self);
+    }
+}
+
+impl Visit<RJSXElement> for UnusedFinder {
+    fn visit(&mut self, e: &RJSXElement) {
+        self.has_jsx = true;
+
+        e.visit_children_with(self);
+    }
+}
+
+impl Visit<RJSXFragment> for UnusedFinder {
+    fn visit(&mut self, e: &RJSXFragment) {
+        self.has_jsx = true;
+
+        e.visit_children_with(self);
+    }
+}
@@ -308,7 +308,19 @@ impl Analyzer<'_, '_> {
                                     });
 
                                     match result {
-                                        Ok(ty) => Ok(ty.into_owned().generalize_lit()),
+                                        Ok(elem_ty) => {
+                                            let elem_ty = elem_ty.into_owned().generalize_lit();
+                                            if let RPat::Assign(..) = elem {
+                                                return Ok(elem_ty);
+                                            }
+
+                                            let key = Key::Num(RNumber {
+                                                span: elem.span(),
+                                                value: idx as f64,
+                                                raw: None,
+                                            });
+                                            Ok(self.add_undefined_for_unchecked_index(span, ty, &key, elem_ty))
+                                        }
                                         Err(err) => match &*err {
                                             ErrorKind::TupleIndexError { .. } => match elem {
                                                 RPat::Assign(p) => {
@@ -498,21 +510,21 @@ impl Analyzer<'_, '_> {
                                     break;
                                 }
 
+                                let key = Key::Num(RNumber {
+                                    span: elem.span(),
+                                    value: idx as f64,
+                                    raw: None,
+                                });
                                 let mut elem_ty = match &ty {
                                     Some(ty) => self
-                                        .access_property(
-                                            elem.span(),
-                                            ty,
-                                            &Key::Num(RNumber {
-                                                span: elem.span(),
-                                                value: idx as f64,
-                                                raw: None,
-                                            }),
-                                            TypeOfMode::RValue,
-                                            IdCtx::Var,
-                                            Default::default(),
-                                        )
-                                        .map(|ty| ty.generalize_lit())
+                                        .access_property(elem.span(), ty, &key, TypeOfMode::RValue, IdCtx::Var, Default::default())
+                                        .map(|elem_ty| {
+                                            let elem_ty = elem_ty.generalize_lit();
+                                            if let RPat::Assign(..) = elem {
+                                                return elem_ty;
+                                            }
+                                            self.add_undefined_for_unchecked_index(elem.span(), ty, &key, elem_ty)
+                                        })
                                         .context("tried to access property to declare variables using an array pattern")
                                         .report(&mut self.storage),
                                     None => None,
@@ -644,8 +656,9 @@ impl Analyzer<'_, '_> {
                                 disallow_unknown_object_property: true,
                                 ..self.ctx
                             };
-                            let prop_ty = ty.as_ref().try_map(|ty| {
-                                self.with_ctx(ctx)
+                            let prop_ty = ty.as_ref().try_map(|ty| -> VResult<Type> {
+                                let prop_ty = self
+                                    .with_ctx(ctx)
                                     .access_property(
                                         span,
                                         ty,
@@ -660,7 +673,12 @@ impl Analyzer<'_, '_> {
                                         },
                                     )
                                     .map(|ty| ty.generalize_lit())
-                                    .context("tried to access property to declare variables")
+                                    .context("tried to access property to declare variables")?;
+
+                                if let RPat::Assign(..) = &*prop.value {
+                                    return Ok(prop_ty);
+                                }
+                                Ok(self.add_undefined_for_unchecked_index(span, ty, &key, prop_ty))
                             });
 
                             let default_prop_ty = default
@@ -740,8 +758,9 @@ impl Analyzer<'_, '_> {
                                 ..self.ctx
                             };
 
-                            let prop_ty = ty.as_ref().try_map(|ty| {
-                                self.with_ctx(ctx)
+                            let prop_ty = ty.as_ref().try_map(|ty| -> VResult<Type> {
+                                let prop_ty = self
+                                    .with_ctx(ctx)
                                     .access_property(
                                         span,
                                         ty,
@@ -756,7 +775,12 @@ impl Analyzer<'_, '_> {
                                         },
                                     )
                                     .map(|ty| ty.generalize_lit())
-                                    .context("tried to access property to declare variables")
+                                    .context("tried to access property to declare variables")?;
+
+                                if prop.value.is_some() {
+                                    return Ok(prop_ty);
+                                }
+                                Ok(self.add_undefined_for_unchecked_index(span, ty, &key, prop_ty))
                             });
 
                             let mut default_prop_ty = default
@@ -1,17 +1,20 @@
 use std::time::Instant;
 
 use rnode::VisitWith;
-use stc_ts_ast_rnode::{RBlockStmt, RBool, RDecl, RExpr, RExprStmt, RForStmt, RModuleItem, RStmt, RTsExprWithTypeArgs, RTsLit, RWithStmt};
+use stc_ts_ast_rnode::{
+    RBlockStmt, RBool, RDecl, RExpr, RExprStmt, RForStmt, RLabeledStmt, RModuleItem, RStmt, RTsExprWithTypeArgs, RTsLit, RWithStmt,
+};
 use stc_ts_errors::{DebugExt, ErrorKind};
 use stc_ts_types::{LitType, Type};
-use stc_utils::{dev_span, stack};
+use stc_utils::{dev_span, instantiation, stack};
 use swc_common::{Spanned, DUMMY_SP};
 use swc_ecma_utils::Value::Known;
 use tracing::{trace, warn};
 
 use self::return_type::LoopBreakerFinder;
 use crate::{
-    analyzer::{scope::ScopeKind, util::ResultExt, Analyzer},
+    analyzer::{scope::ScopeKind, util::ResultExt, Analyzer, Ctx},
+    util::reachability::has_jump,
     validator,
     validator::ValidateWith,
 };
@@ -26,6 +29,7 @@ mod var_decl;
 impl Analyzer<'_, '_> {
     fn validate(&mut self, i: &RModuleItem) {
         let _stack = stack::start(100);
+        let _instantiation = instantiation::start(i.span());
 
         i.visit_children_with(self);
 
@@ -127,6 +131,22 @@ impl Analyzer<'_, '_> {
     }
 }
 
+#[validator]
+impl Analyzer<'_, '_> {
+    fn validate(&mut self, s: &RLabeledStmt) {
+        if self.rule().allow_unused_labels == Some(false) {
+            let jumps = has_jump(&s.body, Some(&s.label.sym));
+            if !jumps.break_ && !jumps.continue_ {
+                self.storage.report(ErrorKind::UnusedLabel { span: s.label.span }.into());
+            }
+        }
+
+        s.body.visit_with(self);
+
+        Ok(())
+    }
+}
+
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, s: &RBlockStmt) {
@@ -178,7 +198,14 @@ impl Analyzer<'_, '_> {
 
         let prev_cond_facts = self.cur_facts.clone();
 
-        node.expr.visit_with(self);
+        let ctx = Ctx {
+            expr_stmt_call_span: match &*node.expr {
+                RExpr::Call(call) => Some(call.span),
+                _ => None,
+            },
+            ..self.ctx
+        };
+        node.expr.visit_with(&mut *self.with_ctx(ctx));
 
         if preserve_cond_facts {
             self.cur_facts = prev_cond_facts;
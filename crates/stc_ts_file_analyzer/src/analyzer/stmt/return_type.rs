@@ -291,6 +291,7 @@ impl Analyzer<'_, '_> {
             Ok(Some(ty))
         })()?;
         ret_ty.freeze();
+        self.scope.end_of_body_is_unreachable = is_unreachable;
 
         if self.config.is_builtin {
             return Ok(ret_ty);
@@ -352,6 +353,10 @@ impl Analyzer<'_, '_> {
         debug_assert!(!self.config.is_builtin, "builtin: return statement is not supported");
         debug_assert_ne!(node.span, DUMMY_SP, "return statement should have valid span");
 
+        if node.arg.is_none() {
+            self.report_empty_return(node.span);
+        }
+
         let mut ty = if let Some(res) = {
             let ctx = Ctx {
                 in_return_arg: true,
@@ -1,10 +1,23 @@
+use std::borrow::Cow;
+
 use rnode::VisitWith;
-use stc_ts_ast_rnode::RCatchClause;
+use stc_ts_ast_rnode::{RCatchClause, RPat};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::{FnParam, Type};
+use stc_ts_utils::PatExt;
+use stc_utils::cache::Freeze;
+use swc_common::Spanned;
 
 use crate::{
-    analyzer::{pat::PatMode, scope::ScopeKind, Analyzer, Ctx},
+    analyzer::{
+        pat::PatMode,
+        scope::{vars::DeclareVarsOpts, ScopeKind, VarKind},
+        util::ResultExt,
+        Analyzer, Ctx,
+    },
     validator,
     validator::ValidateWith,
+    VResult,
 };
 
 #[validator]
@@ -17,8 +30,13 @@ impl Analyzer<'_, '_> {
         self.with_ctx(ctx)
             .with_child(ScopeKind::Block, Default::default(), |child: &mut Analyzer| {
                 match &s.param {
+                    Some(pat @ RPat::Ident(i)) if i.type_ann.is_none() => {
+                        child.declare_catch_var(pat).report(&mut child.storage);
+                    }
                     Some(pat) => {
-                        pat.validate_with(child)?;
+                        let param = pat.validate_with(child)?;
+
+                        child.report_error_for_catch_var_type_ann(pat, &param);
                     }
                     None => {}
                 }
@@ -29,3 +47,51 @@ impl Analyzer<'_, '_> {
             })
     }
 }
+
+impl Analyzer<'_, '_> {
+    /// Catch variables without type annotation are `unknown` if
+    /// `useUnknownInCatchVariables` is enabled, and `any` otherwise.
+    fn declare_catch_var(&mut self, pat: &RPat) -> VResult<()> {
+        let span = pat.span();
+        let ty = if self.rule().use_unknown_in_catch_variables {
+            Type::unknown(span, Default::default())
+        } else {
+            Type::any(span, Default::default())
+        }
+        .freezed();
+
+        self.add_vars(
+            pat,
+            Some(ty),
+            None,
+            None,
+            DeclareVarsOpts {
+                kind: VarKind::Param,
+                use_iterator_for_array: false,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Reports `TS1196` if the type annotation is not `any` or `unknown`.
+    fn report_error_for_catch_var_type_ann(&mut self, pat: &RPat, param: &FnParam) {
+        let span = match pat.get_ty() {
+            Some(ty) => ty.span(),
+            None => return,
+        };
+
+        let ty = match param.ty.normalize() {
+            Type::Instance(ty) => &*ty.ty,
+            ty => ty,
+        };
+        let ty = match self.normalize(Some(span), Cow::Borrowed(ty), Default::default()) {
+            Ok(ty) => ty,
+            Err(..) => return,
+        };
+
+        if !ty.is_any() && !ty.is_unknown() {
+            self.storage.report(ErrorKind::InvalidCatchVarTypeAnn { span }.into());
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use std::borrow::Cow;
 
+use fxhash::FxHashSet;
 use rnode::{FoldWith, Visit, VisitWith};
 use stc_ts_ast_rnode::{
-    RArrayPat, RArrowExpr, RBindingIdent, RCallExpr, RCallee, RExpr, RIdent, RPat, RTsAsExpr, RTsEntityName, RTsFnOrConstructorType,
-    RTsFnParam, RTsFnType, RTsQualifiedName, RTsType, RTsTypeAssertion, RVarDecl, RVarDeclarator,
+    RArrayPat, RArrowExpr, RBindingIdent, RBlockStmt, RCallExpr, RCallee, RDecl, RExportDecl, RExpr, RIdent, RModule, RModuleDecl,
+    RModuleItem, RPat, RStmt, RSwitchCase, RTsAsExpr, RTsEntityName, RTsFnOrConstructorType, RTsFnParam, RTsFnType, RTsModuleBlock,
+    RTsQualifiedName, RTsType, RTsTypeAssertion, RVarDecl, RVarDeclarator,
 };
 use stc_ts_errors::{debug::dump_type_as_string, DebugExt, ErrorKind, Errors};
 use stc_ts_type_ops::{generalization::prevent_generalize, Fix};
@@ -14,7 +16,7 @@ use stc_ts_types::{
 use stc_ts_utils::{find_ids_in_pat, PatExt};
 use stc_utils::cache::Freeze;
 use swc_atoms::js_word;
-use swc_common::{Spanned, SyntaxContext};
+use swc_common::{Span, Spanned, SyntaxContext};
 use swc_ecma_ast::*;
 use tracing::debug;
 use ty::TypeExt;
@@ -30,7 +32,10 @@ use crate::{
         Analyzer, Ctx, ScopeKind,
     },
     ty::{self, Tuple, Type, TypeParam},
-    util::{should_instantiate_type_ann, RemoveTypes},
+    util::{
+        definite_assignment::{find_unassigned_reads, Analysis},
+        should_instantiate_type_ann, RemoveTypes,
+    },
     validator,
     validator::ValidateWith,
 };
@@ -97,6 +102,7 @@ impl Analyzer<'_, '_> {
                 None
             };
             let ids: Vec<Id> = find_ids_in_pat(&v.name);
+            self.record_vars_without_type_ann(&v.name);
             let prev_declaring_len = self.scope.declaring.len();
             self.scope.declaring.extend(ids);
 
@@ -761,6 +767,12 @@ impl Analyzer<'_, '_> {
                             }
                         }
 
+                        if let Some(ty) = &ty {
+                            if !v.definite && self.should_be_assigned_before_read(i.id.span, ty) {
+                                self.data.vars_requiring_assignment.insert(sym.clone());
+                            }
+                        }
+
                         match self.declare_var(
                             i.id.span,
                             VarKind::Var(kind),
@@ -828,6 +840,108 @@ impl Analyzer<'_, '_> {
     }
 }
 
+impl Analyzer<'_, '_> {
+    fn should_be_assigned_before_read(&mut self, span: Span, ty: &Type) -> bool {
+        self.rule().strict_null_checks
+            && !self.config.is_builtin
+            && !self.config.is_dts
+            && !self.ctx.in_declare
+            && !self.can_be_uninitialized(span, ty)
+    }
+
+    /// Reports `TS2454` for variables which are read before being assigned.
+    pub(crate) fn report_errors_for_unassigned_vars(&mut self, m: &RModule) {
+        if self.data.vars_requiring_assignment.is_empty() {
+            return;
+        }
+
+        let mut finder = UnassignedReadFinder {
+            vars: &self.data.vars_requiring_assignment,
+            spans: vec![],
+        };
+        m.visit_with(&mut finder);
+
+        for span in finder.spans {
+            self.storage.report(ErrorKind::VarMayNotBeInitialized { span }.into());
+        }
+    }
+}
+
+/// Checks each statement list for reads of variables declared in the list.
+struct UnassignedReadFinder<'a> {
+    vars: &'a FxHashSet<Id>,
+    spans: Vec<Span>,
+}
+
+impl UnassignedReadFinder<'_> {
+    fn check<N>(&mut self, nodes: &[N], var_decl: fn(&N) -> Option<&RVarDecl>)
+    where
+        N: for<'b> VisitWith<Analysis<'b>>,
+    {
+        let vars = nodes
+            .iter()
+            .filter_map(var_decl)
+            .flat_map(|d| &d.decls)
+            .filter_map(|d| match &d.name {
+                RPat::Ident(i) => Some(Id::from(&i.id)),
+                _ => None,
+            })
+            .filter(|id| self.vars.contains(id))
+            .collect::<FxHashSet<_>>();
+
+        if !vars.is_empty() {
+            self.spans.extend(find_unassigned_reads(nodes, &vars));
+        }
+    }
+}
+
+impl Visit<RModule> for UnassignedReadFinder<'_> {
+    fn visit(&mut self, m: &RModule) {
+        self.check(&m.body, |item| match item {
+            RModuleItem::Stmt(RStmt::Decl(RDecl::Var(d)))
+            | RModuleItem::ModuleDecl(RModuleDecl::ExportDecl(RExportDecl { decl: RDecl::Var(d), .. })) => Some(&**d),
+            _ => None,
+        });
+
+        m.visit_children_with(self);
+    }
+}
+
+impl Visit<RTsModuleBlock> for UnassignedReadFinder<'_> {
+    fn visit(&mut self, b: &RTsModuleBlock) {
+        self.check(&b.body, |item| match item {
+            RModuleItem::Stmt(RStmt::Decl(RDecl::Var(d)))
+            | RModuleItem::ModuleDecl(RModuleDecl::ExportDecl(RExportDecl { decl: RDecl::Var(d), .. })) => Some(&**d),
+            _ => None,
+        });
+
+        b.visit_children_with(self);
+    }
+}
+
+impl Visit<RBlockStmt> for UnassignedReadFinder<'_> {
+    fn visit(&mut self, b: &RBlockStmt) {
+        self.check(&b.stmts, var_decl_of_stmt);
+
+        b.visit_children_with(self);
+    }
+}
+
+impl Visit<RSwitchCase> for UnassignedReadFinder<'_> {
+    fn visit(&mut self, c: &RSwitchCase) {
+        self.check(&c.cons, var_decl_of_stmt);
+
+        c.visit_children_with(self);
+    }
+}
+
+fn var_decl_of_stmt(s: &RStmt) -> Option<&RVarDecl> {
+    match s {
+        RStmt::Decl(RDecl::Var(d)) => Some(&**d),
+        _ => None,
+    }
+}
+
 struct TypeParamFinder {
     found: bool,
 }
@@ -7,6 +7,7 @@ use stc_utils::{
     cache::{Freeze, ALLOW_DEEP_CLONE},
     dev_span,
     ext::TypeVecExt,
+    instantiation,
 };
 use swc_common::{Span, SyntaxContext, TypeEq};
 use tracing::{debug, info};
@@ -25,133 +26,103 @@ impl Analyzer<'_, '_> {
         opts: NormalizeTypeOpts,
     ) -> VResult<Cow<'a, Type>> {
         let span = if c.span.is_dummy() { actual_span } else { c.span };
-        // TODO(kdy1): Cleanup
-        c = match self.expand_conditional_type(span, Type::Conditional(c)).foldable() {
-            Type::Conditional(c) => c,
-            ty => return Ok(Cow::Owned(ty)),
-        };
-
-        ALLOW_DEEP_CLONE.set(&(), || {
-            let ty = dump_type_as_string(&Type::Conditional(c.clone()));
-
-            debug!("normalize: conditional: {}", ty)
-        });
-
-        c.check_type = Box::new(
-            self.normalize(
-                Some(span),
-                Cow::Borrowed(&c.check_type),
-                NormalizeTypeOpts {
-                    preserve_keyof: true,
-                    ..Default::default()
-                },
-            )
-            .context("tried to normalize the `check` type of a conditional type")?
-            .freezed()
-            .into_owned()
-            .freezed(),
-        );
-
-        c.extends_type = Box::new(
-            self.normalize(Some(span), Cow::Borrowed(&c.extends_type), Default::default())
-                .unwrap_or(Cow::Borrowed(&c.extends_type))
+        let _instantiation = instantiation::track()?;
+        let mut tail_count = 0;
+
+        loop {
+            // TODO(kdy1): Cleanup
+            c = match self.expand_conditional_type(span, Type::Conditional(c)).foldable() {
+                Type::Conditional(c) => c,
+                ty => return Ok(Cow::Owned(ty)),
+            };
+
+            ALLOW_DEEP_CLONE.set(&(), || {
+                let ty = dump_type_as_string(&Type::Conditional(c.clone()));
+
+                debug!("normalize: conditional: {}", ty)
+            });
+
+            c.check_type = Box::new(
+                self.normalize(
+                    Some(span),
+                    Cow::Borrowed(&c.check_type),
+                    NormalizeTypeOpts {
+                        preserve_keyof: true,
+                        ..Default::default()
+                    },
+                )
+                .context("tried to normalize the `check` type of a conditional type")?
                 .freezed()
                 .into_owned()
                 .freezed(),
-        );
-
-        if let Some(v) = self.extends(span, &c.check_type, &c.extends_type, Default::default()) {
-            info!("normalize: conditional: check_type extends extends_type: {:?}", v);
-            let ty = if v { &c.true_type } else { &c.false_type };
-            // TODO(kdy1): Optimize
-            let ty = self
-                .normalize(Some(span), Cow::Borrowed(ty), opts)
-                .context("tried to normalize the calculated type of a conditional type")?
-                .into_owned();
-            return Ok(Cow::Owned(ty));
-        }
-        if let Type::Param(TypeParam {
-            name,
-            constraint: Some(check_type_constraint),
-            ..
-        }) = c.check_type.normalize()
-        {
-            let new_type = self
-                .reduce_conditional_type(
-                    c.span,
-                    &c.check_type,
-                    check_type_constraint,
-                    &c.extends_type,
-                    &c.true_type,
-                    &c.false_type,
-                    c.metadata,
-                )
-                .context("tried to reduce conditional type")?;
-
-            if let Some(new_type) = new_type {
-                return self.normalize(Some(span), Cow::Owned(new_type), opts);
-            }
-        }
-
-        if let Type::Union(check_type_union) = c.check_type.normalize() {
-            let mut all = true;
-            let mut types = vec![];
-            for check_type in &check_type_union.types {
-                let res = self.extends(span, check_type, &c.extends_type, Default::default());
-                if let Some(v) = res {
-                    if v {
-                        if !c.true_type.is_never() {
-                            types.push(check_type.clone());
-                        }
-                    } else {
-                        if !c.false_type.is_never() {
-                            types.push(check_type.clone());
-                        }
+            );
+
+            c.extends_type = Box::new(
+                self.normalize(Some(span), Cow::Borrowed(&c.extends_type), Default::default())
+                    .unwrap_or(Cow::Borrowed(&c.extends_type))
+                    .freezed()
+                    .into_owned()
+                    .freezed(),
+            );
+
+            if let Some(v) = self.extends(span, &c.check_type, &c.extends_type, Default::default()) {
+                info!("normalize: conditional: check_type extends extends_type: {:?}", v);
+                let ty = if v { &c.true_type } else { &c.false_type };
+
+                // Evaluate tail-recursive conditional types in a loop, like tsc,
+                // so deep recursion does not count as nested instantiations.
+                let next = self
+                    .expand_top_ref(span, Cow::Borrowed(ty), Default::default())
+                    .ok()
+                    .and_then(|ty| match ty.normalize() {
+                        Type::Conditional(next) => Some(next.clone()),
+                        _ => None,
+                    });
+                if let Some(next) = next {
+                    tail_count += 1;
+                    if tail_count >= instantiation::MAX_TAIL_RECURSION {
+                        return Err(instantiation::too_deep().into());
                     }
-                } else {
-                    all = false;
-                    break;
-                }
-            }
-
-            if all {
-                let new = Type::Union(Union {
-                    span: actual_span.with_ctxt(SyntaxContext::empty()),
-                    types,
-                    metadata: Default::default(),
-                    tracker: Default::default(),
-                })
-                .fixed();
 
-                new.assert_valid();
+                    c = next;
+                    continue;
+                }
 
-                return Ok(Cow::Owned(new));
+                // TODO(kdy1): Optimize
+                let ty = self
+                    .normalize(Some(span), Cow::Borrowed(ty), opts)
+                    .context("tried to normalize the calculated type of a conditional type")?
+                    .into_owned();
+                return Ok(Cow::Owned(ty));
+            }
+            if let Type::Param(TypeParam {
+                name,
+                constraint: Some(check_type_constraint),
+                ..
+            }) = c.check_type.normalize()
+            {
+                let new_type = self
+                    .reduce_conditional_type(
+                        c.span,
+                        &c.check_type,
+                        check_type_constraint,
+                        &c.extends_type,
+                        &c.true_type,
+                        &c.false_type,
+                        c.metadata,
+                    )
+                    .context("tried to reduce conditional type")?;
+
+                if let Some(new_type) = new_type {
+                    return self.normalize(Some(span), Cow::Owned(new_type), opts);
+                }
             }
-        }
-
-        // TODO: Optimize
-        // If we can calculate type using constraints, do so.
 
-        // TODO(kdy1): PERF
-        if let Type::Param(TypeParam {
-            name,
-            constraint: Some(check_type_constraint),
-            ..
-        }) = c.check_type.normalize_mut()
-        {
-            // We removes unmatchable constraints.
-            // It means, for
-            //
-            // T: a type param extends string | undefined
-            // A: T extends null | undefined ? never : T
-            //
-            // We removes `undefined` from parents of T.
-
-            if let Type::Union(check_type_union) = check_type_constraint.normalize() {
+            if let Type::Union(check_type_union) = c.check_type.normalize() {
                 let mut all = true;
                 let mut types = vec![];
                 for check_type in &check_type_union.types {
-                    let res = self.extends(c.span, check_type, &c.extends_type, Default::default());
+                    let res = self.extends(span, check_type, &c.extends_type, Default::default());
                     if let Some(v) = res {
                         if v {
                             if !c.true_type.is_never() {
@@ -169,28 +140,83 @@ impl Analyzer<'_, '_> {
                 }
 
                 if all {
-                    types.dedup_type();
                     let new = Type::Union(Union {
                         span: actual_span.with_ctxt(SyntaxContext::empty()),
                         types,
                         metadata: Default::default(),
                         tracker: Default::default(),
-                    });
+                    })
+                    .fixed();
+
+                    new.assert_valid();
+
+                    return Ok(Cow::Owned(new));
+                }
+            }
+
+            // TODO: Optimize
+            // If we can calculate type using constraints, do so.
+
+            // TODO(kdy1): PERF
+            if let Type::Param(TypeParam {
+                name,
+                constraint: Some(check_type_constraint),
+                ..
+            }) = c.check_type.normalize_mut()
+            {
+                // We removes unmatchable constraints.
+                // It means, for
+                //
+                // T: a type param extends string | undefined
+                // A: T extends null | undefined ? never : T
+                //
+                // We removes `undefined` from parents of T.
+
+                if let Type::Union(check_type_union) = check_type_constraint.normalize() {
+                    let mut all = true;
+                    let mut types = vec![];
+                    for check_type in &check_type_union.types {
+                        let res = self.extends(c.span, check_type, &c.extends_type, Default::default());
+                        if let Some(v) = res {
+                            if v {
+                                if !c.true_type.is_never() {
+                                    types.push(check_type.clone());
+                                }
+                            } else {
+                                if !c.false_type.is_never() {
+                                    types.push(check_type.clone());
+                                }
+                            }
+                        } else {
+                            all = false;
+                            break;
+                        }
+                    }
+
+                    if all {
+                        types.dedup_type();
+                        let new = Type::Union(Union {
+                            span: actual_span.with_ctxt(SyntaxContext::empty()),
+                            types,
+                            metadata: Default::default(),
+                            tracker: Default::default(),
+                        });
 
-                    **check_type_constraint = new;
+                        **check_type_constraint = new;
 
-                    let mut params = HashMap::default();
-                    params.insert(name.clone(), ALLOW_DEEP_CLONE.set(&(), || *c.check_type.clone().fixed().freezed()));
-                    let c = self.expand_type_params(&params, c, Default::default())?;
-                    let c = Type::Conditional(c);
-                    c.assert_valid();
+                        let mut params = HashMap::default();
+                        params.insert(name.clone(), ALLOW_DEEP_CLONE.set(&(), || *c.check_type.clone().fixed().freezed()));
+                        let c = self.expand_type_params(&params, c, Default::default())?;
+                        let c = Type::Conditional(c);
+                        c.assert_valid();
 
-                    return Ok(Cow::Owned(c));
+                        return Ok(Cow::Owned(c));
+                    }
                 }
             }
-        }
 
-        Ok(Cow::Owned(Type::Conditional(c)))
+            return Ok(Cow::Owned(Type::Conditional(c)));
+        }
     }
 
     pub(crate) fn has_type_param_for_conditional(c: &Type) -> bool {
@@ -261,11 +287,27 @@ impl Analyzer<'_, '_> {
                 .ok();
 
             if let Some(type_params) = type_params {
-                check_type = Box::new(self.expand_type_params(&type_params, *check_type, Default::default()).unwrap());
-                extends_type = Box::new(self.expand_type_params(&type_params, *extends_type, Default::default()).unwrap());
-
-                true_type = Box::new(self.expand_type_params(&type_params, *true_type, Default::default()).unwrap());
-                false_type = Box::new(self.expand_type_params(&type_params, *false_type, Default::default()).unwrap());
+                let expanded: VResult<_> = try {
+                    (
+                        self.expand_type_params(&type_params, *check_type, Default::default())?,
+                        self.expand_type_params(&type_params, *extends_type, Default::default())?,
+                        self.expand_type_params(&type_params, *true_type, Default::default())?,
+                        self.expand_type_params(&type_params, *false_type, Default::default())?,
+                    )
+                };
+
+                match expanded {
+                    Ok((c, e, t, f)) => {
+                        check_type = Box::new(c);
+                        extends_type = Box::new(e);
+                        true_type = Box::new(t);
+                        false_type = Box::new(f);
+                    }
+                    Err(err) => {
+                        self.storage.report(err);
+                        return Type::any(span, Default::default());
+                    }
+                }
             }
 
             if check_type.is_class() {
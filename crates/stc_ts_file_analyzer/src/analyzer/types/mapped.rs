@@ -14,7 +14,7 @@ use stc_ts_types::{
 };
 use stc_utils::{
     cache::{Freeze, ALLOW_DEEP_CLONE},
-    dev_span, stack,
+    dev_span, instantiation, stack,
 };
 use swc_common::{Span, Spanned, SyntaxContext, TypeEq};
 use swc_ecma_ast::{TruePlusMinus, TsKeywordTypeKind};
@@ -37,6 +37,7 @@ impl Analyzer<'_, '_> {
     /// TODO(kdy1): Handle index signatures.
     pub(crate) fn expand_mapped(&mut self, span: Span, m: &Mapped) -> VResult<Option<Type>> {
         let _guard = stack::track(span)?;
+        let _instantiation = instantiation::track()?;
         let _tracing = dev_span!("expand_mapped");
 
         let orig = dump_type_as_string(&ALLOW_DEEP_CLONE.set(&(), || Type::Mapped(m.clone())));
@@ -168,8 +169,12 @@ impl Analyzer<'_, '_> {
         match keyof_operand.normalize() {
             Type::Array(array) => {
                 let elem_type = m.ty.clone().unwrap_or_else(|| Box::new(Type::any(span, Default::default())));
+                // With `exactOptionalPropertyTypes`, `?` means the element may be missing, not
+                // that it may be `undefined`.
                 let elem_type = match m.optional {
-                    Some(TruePlusMinus::True) => Box::new(elem_type.union_with_undefined(span)),
+                    Some(TruePlusMinus::True) if !self.rule().exact_optional_property_types => {
+                        Box::new(elem_type.union_with_undefined(span))
+                    }
                     _ => elem_type,
                 };
 
@@ -280,7 +285,9 @@ impl Analyzer<'_, '_> {
                             }
 
                             let ty = match m.optional {
-                                Some(TruePlusMinus::True) => Box::new(ty.union_with_undefined(span)),
+                                Some(TruePlusMinus::True) if !self.rule().exact_optional_property_types => {
+                                    Box::new(ty.union_with_undefined(span))
+                                }
                                 _ => ty,
                             };
 
@@ -1395,6 +1395,18 @@ impl Analyzer<'_, '_> {
         }
     }
 
+    /// Returns `true` if a variable or a property of type `ty` does not need
+    /// to be initialized, which means `ty` is `unknown` or it can be
+    /// `undefined`.
+    pub(crate) fn can_be_uninitialized(&mut self, span: Span, ty: &Type) -> bool {
+        let ty = match ty.normalize() {
+            Type::Instance(ty) => &*ty.ty,
+            ty => ty,
+        };
+
+        ty.is_unknown() || self.can_be_undefined(span, ty, false).unwrap_or(true)
+    }
+
     pub(crate) fn can_be_undefined(&mut self, span: Span, ty: &Type, include_null: bool) -> VResult<bool> {
         let _tracing = dev_span!("can_be_undefined", include_null = include_null);
 
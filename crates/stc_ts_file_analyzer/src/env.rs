@@ -1,4 +1,4 @@
-use std::{collections::hash_map::Entry, error::Error, path::Path, sync::Arc, time::Instant};
+use std::{collections::hash_map::Entry, error::Error, sync::Arc, time::Instant};
 
 use dashmap::DashMap;
 use once_cell::sync::{Lazy, OnceCell};
@@ -11,7 +11,11 @@ use stc_ts_env::{BuiltIn, Env, ModuleConfig, Rule, StableEnv};
 use stc_ts_storage::Builtin;
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{ClassDef, ModuleTypeData, Type};
-use stc_utils::{cache::Freeze, stack};
+use stc_utils::{
+    cache::Freeze,
+    disk_cache::{cache_dir, write_atomic, STC_VERSION},
+    stack,
+};
 use swc_atoms::JsWord;
 use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
@@ -36,17 +40,23 @@ pub trait BuiltInGen: Sized {
         let cell = CACHE.entry(libs.to_vec()).or_default().clone();
 
         cell.get_or_init(|| {
-            // Loading builtin is very slow, so we cache it to a file using serde_json
-
+            // Loading builtin is very slow, so we cache it to a file using rmp_serde.
+            //
+            // The key contains the version of stc because the serialized form of types
+            // may change between versions.
             let key = {
                 let mut hasher = Sha1::new();
-                hasher.update(format!("{:?}", libs).as_bytes());
+                hasher.update(STC_VERSION.as_bytes());
+                for lib in libs {
+                    hasher.update(format!("{:?}", lib).as_bytes());
+                    hasher.update(lib.source().as_bytes());
+                }
                 let result = hasher.finalize();
 
                 format!("{:x}", result)
             };
 
-            let cache_path = Path::new(".stc").join(".builtin-cache").join(format!("{}.rmp", key));
+            let cache_path = cache_dir().join("builtin").join(format!("{}.rmp", key));
 
             if cache_path.is_file() {
                 let res = || -> Result<BuiltIn, Box<dyn Error>> {
@@ -58,11 +68,12 @@ pub trait BuiltInGen: Sized {
                 }();
 
                 match res {
-                    Ok(builtin) => {
+                    Ok(mut builtin) => {
+                        builtin.set_libs(libs.to_vec());
                         return Arc::new(builtin);
                     }
                     Err(err) => {
-                        warn!("Failed to load builtin from cache: {:?}", err);
+                        warn!("Failed to load builtin from cache at {:?}: {:?}", cache_path, err);
                     }
                 }
             }
@@ -84,16 +95,21 @@ pub trait BuiltInGen: Sized {
                 .cloned()
                 .map(|orig| RModuleItem::from_orig(&mut node_id_gen, orig));
 
-            let builtin = Self::from_module_items(env, iter);
-
-            let json_data =
-                rmp_serde::encode::to_vec(&builtin).unwrap_or_else(|err| panic!("failed to serialize builtin cache: {:?}", err));
+            let mut builtin = Self::from_module_items(env, iter);
 
-            std::fs::create_dir_all(cache_path.parent().unwrap())
-                .unwrap_or_else(|err| panic!("failed to create directory for builtin cache at {:?}: {:?}", cache_path, err));
+            match rmp_serde::encode::to_vec(&builtin) {
+                Ok(data) => {
+                    // The cache is an optimization, so failing to write it is not an error.
+                    if let Err(err) = write_atomic(&cache_path, &data) {
+                        warn!("Failed to write builtin cache at {:?}: {:?}", cache_path, err);
+                    }
+                }
+                Err(err) => {
+                    warn!("Failed to serialize builtin cache: {:?}", err);
+                }
+            }
 
-            std::fs::write(&cache_path, json_data)
-                .unwrap_or_else(|err| panic!("failed to write builtin cache at {:?}: {:?}", cache_path, err));
+            builtin.set_libs(libs.to_vec());
 
             Arc::new(builtin)
         })
@@ -189,27 +205,36 @@ pub trait BuiltInGen: Sized {
 
                                 assert!(!data.types.is_empty() || !data.vars.is_empty());
 
+                                let exports = ModuleTypeData {
+                                    private_vars: Default::default(),
+                                    vars: data.vars,
+                                    private_types: Default::default(),
+                                    types: data.types,
+                                };
+
                                 match types.entry(id.clone()) {
-                                    Entry::Occupied(mut e) => match e.get_mut().normalize_mut() {
-                                        Type::Module(module) => {
-                                            //
-                                            module.exports.types.extend(data.types);
-                                            module.exports.vars.extend(data.vars);
+                                    Entry::Occupied(mut e) => {
+                                        if let Type::Module(module) = e.get_mut().normalize_mut() {
+                                            module.exports.merge(exports);
+                                        } else {
+                                            // A namespace merged with an interface or a class.
+                                            let module = Type::Module(stc_ts_types::Module {
+                                                span: DUMMY_SP,
+                                                name: RTsModuleName::Ident(RIdent::new(id.clone(), DUMMY_SP)),
+                                                exports: Box::new(exports),
+                                                metadata: Default::default(),
+                                                tracker: Default::default(),
+                                            });
+                                            let prev = e.get().clone();
+                                            e.insert(Type::new_intersection(DUMMY_SP, vec![prev, module]).freezed());
                                         }
-
-                                        ref e => unimplemented!("Merging module with {:?}", e),
-                                    },
+                                    }
                                     Entry::Vacant(e) => {
                                         e.insert(
                                             Type::Module(stc_ts_types::Module {
                                                 span: DUMMY_SP,
                                                 name: RTsModuleName::Ident(RIdent::new(id.clone(), DUMMY_SP)),
-                                                exports: Box::new(ModuleTypeData {
-                                                    private_vars: Default::default(),
-                                                    vars: data.vars,
-                                                    private_types: Default::default(),
-                                                    types: data.types,
-                                                }),
+                                                exports: Box::new(exports),
                                                 metadata: Default::default(),
                                                 tracker: Default::default(),
                                             })
@@ -246,12 +271,16 @@ pub trait BuiltInGen: Sized {
                                     .expect_interface();
 
                                 match types.entry(i.id.sym.clone()) {
-                                    Entry::Occupied(mut e) => match e.get_mut().normalize_mut() {
-                                        Type::Interface(ref mut v) => {
+                                    Entry::Occupied(mut e) => {
+                                        if let Type::Interface(ref mut v) = e.get_mut().normalize_mut() {
                                             v.body.extend(body.body);
+                                        } else {
+                                            // An interface merged with a namespace.
+                                            let ty = i.clone().validate_with(&mut analyzer).expect("builtin: failed to parse interface");
+                                            let prev = e.get().clone();
+                                            e.insert(Type::new_intersection(DUMMY_SP, vec![prev, ty]).freezed());
                                         }
-                                        _ => unreachable!("cannot merge interface with other type"),
-                                    },
+                                    }
                                     Entry::Vacant(e) => {
                                         let ty = i.clone().validate_with(&mut analyzer).expect("builtin: failed to parse interface");
 
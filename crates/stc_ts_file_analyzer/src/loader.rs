@@ -41,6 +41,17 @@ pub trait Load: 'static + Send + Sync {
     /// Returned value must be [Type::Arc] of [Type::Module]
     fn load_non_circular_dep(&self, base: &Arc<FileName>, src: &str) -> VResult<Type>;
 
+    /// Declares an ambient module `name` in the file `base`. Declarations of
+    /// a module in multiple files are merged.
+    ///
+    /// `module` should be [Type::Arc] of [Type::Module].
+    fn declare_module(&self, base: &Arc<FileName>, name: &JsWord, module: Type) -> ModuleId;
+
+    /// Merges `module` into the exports of the module `src` resolves to, for
+    /// all importers of the module.
+    ///
+    /// Returns [None] if `src` cannot be resolved.
+    ///
     /// `module` should be [Type::Arc] of [Type::Module].
-    fn declare_module(&self, name: &JsWord, module: Type) -> ModuleId;
+    fn augment_module(&self, base: &Arc<FileName>, src: &str, module: Type) -> Option<ModuleId>;
 }
@@ -0,0 +1,501 @@
+//! Syntactic definite assignment analysis.
+//!
+//! This is used by `strictPropertyInitialization` and to report variables
+//! which are read before being assigned.
+
+use fxhash::FxHashSet;
+use rnode::{Visit, VisitWith};
+use stc_ts_ast_rnode::{
+    RArrowExpr, RAssignExpr, RBinExpr, RBreakStmt, RCatchClause, RClass, RComputedPropName, RCondExpr, RContinueStmt, RDecl, RDoWhileStmt,
+    RExpr, RForInStmt, RForOfStmt, RForStmt, RFunction, RGetterProp, RIdent, RIfStmt, RLabeledStmt, RLit, RMemberExpr, RMemberProp,
+    RModuleDecl, RObjectPatProp, RPat, RPatOrExpr, RPropName, RReturnStmt, RSetterProp, RSuperProp, RSuperPropExpr, RSwitchStmt,
+    RThrowStmt, RTryStmt, RTsType, RVarDeclOrPat, RVarDeclarator, RWhileStmt,
+};
+use stc_ts_types::Id;
+use swc_atoms::JsWord;
+use swc_common::Span;
+use swc_ecma_ast::op;
+
+use crate::util::reachability::is_truthy_lit;
+
+/// Returns the spans of identifiers in `nodes` which read one of `vars`
+/// before it is definitely assigned.
+pub(crate) fn find_unassigned_reads<N>(nodes: &[N], vars: &FxHashSet<Id>) -> Vec<Span>
+where
+    N: for<'a> VisitWith<Analysis<'a>>,
+{
+    let mut v = Analysis {
+        vars: Some(vars),
+        ..Default::default()
+    };
+    nodes.iter().for_each(|node| node.visit_with(&mut v));
+    v.reads
+}
+
+/// Finds properties of `this` which are definitely assigned when a
+/// constructor with `body` returns.
+pub(crate) fn find_assigned_this_props<N>(body: &[N]) -> AssignedProps
+where
+    N: for<'a> VisitWith<Analysis<'a>>,
+{
+    let mut v = Analysis::default();
+    body.iter().for_each(|node| node.visit_with(&mut v));
+
+    let state = match v.exit.take() {
+        Some(exit) => exit.intersect(v.state),
+        None => v.state,
+    };
+
+    AssignedProps {
+        props: state.this_props,
+        all: state.unreachable,
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct AssignedProps {
+    props: FxHashSet<JsWord>,
+    /// `true` if the constructor never returns.
+    all: bool,
+}
+
+impl AssignedProps {
+    pub fn contains(&self, sym: &JsWord) -> bool {
+        self.all || self.props.contains(sym)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct State {
+    vars: FxHashSet<Id>,
+    this_props: FxHashSet<JsWord>,
+    /// `true` if the current position is not reachable, which means
+    /// everything is definitely assigned.
+    unreachable: bool,
+}
+
+impl State {
+    fn intersect(self, other: Self) -> Self {
+        if self.unreachable {
+            return other;
+        }
+        if other.unreachable {
+            return self;
+        }
+
+        State {
+            vars: self.vars.intersection(&other.vars).cloned().collect(),
+            this_props: self.this_props.intersection(&other.this_props).cloned().collect(),
+            unreachable: false,
+        }
+    }
+
+    fn unreachable() -> Self {
+        State {
+            unreachable: true,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Analysis<'a> {
+    /// Variables to check.
+    vars: Option<&'a FxHashSet<Id>>,
+    state: State,
+    /// State at `return` statements.
+    exit: Option<State>,
+    /// States at unlabeled `break` statements, for each enclosing loop or
+    /// `switch`.
+    breaks: Vec<State>,
+    reads: Vec<Span>,
+}
+
+impl Analysis<'_> {
+    fn read(&mut self, i: &RIdent) {
+        if self.state.unreachable {
+            return;
+        }
+
+        if let Some(vars) = self.vars {
+            let id = Id::from(i);
+            if vars.contains(&id) && !self.state.vars.contains(&id) {
+                self.reads.push(i.span);
+            }
+        }
+    }
+
+    fn assign_expr(&mut self, e: &RExpr) {
+        match e {
+            RExpr::Ident(i) => {
+                self.state.vars.insert(i.into());
+            }
+            RExpr::Paren(e) => self.assign_expr(&e.expr),
+            RExpr::Member(RMemberExpr {
+                obj: box RExpr::This(..),
+                prop: RMemberProp::Ident(prop),
+                ..
+            }) => {
+                self.state.this_props.insert(prop.sym.clone());
+            }
+            RExpr::Member(RMemberExpr {
+                obj: box RExpr::This(..),
+                prop: RMemberProp::PrivateName(prop),
+                ..
+            }) => {
+                self.state.this_props.insert(prop.id.sym.clone());
+            }
+            // `this["a"] = 1`
+            RExpr::Member(RMemberExpr {
+                obj: box RExpr::This(..),
+                prop:
+                    RMemberProp::Computed(RComputedPropName {
+                        expr: box RExpr::Lit(RLit::Str(prop)),
+                        ..
+                    }),
+                ..
+            }) => {
+                self.state.this_props.insert(prop.value.clone());
+            }
+            _ => e.visit_with(self),
+        }
+    }
+
+    fn assign_pat(&mut self, p: &RPat) {
+        match p {
+            RPat::Ident(i) => {
+                self.state.vars.insert((&i.id).into());
+            }
+            RPat::Array(p) => {
+                for elem in p.elems.iter().flatten() {
+                    self.assign_pat(elem);
+                }
+            }
+            RPat::Rest(p) => self.assign_pat(&p.arg),
+            RPat::Object(p) => {
+                for prop in &p.props {
+                    match prop {
+                        RObjectPatProp::KeyValue(prop) => {
+                            prop.key.visit_with(self);
+                            self.assign_pat(&prop.value);
+                        }
+                        RObjectPatProp::Assign(prop) => {
+                            prop.value.visit_with(self);
+                            self.state.vars.insert((&prop.key).into());
+                        }
+                        RObjectPatProp::Rest(p) => self.assign_pat(&p.arg),
+                    }
+                }
+            }
+            RPat::Assign(p) => {
+                p.right.visit_with(self);
+                self.assign_pat(&p.left);
+            }
+            RPat::Expr(e) => self.assign_expr(e),
+            RPat::Invalid(..) => {}
+        }
+    }
+
+    /// Runs `op` for the body of a loop.
+    ///
+    /// An infinite loop, like `while (true)`, is exited only by `break`
+    /// statements. Otherwise the body may not be executed at all.
+    fn visit_loop<F>(&mut self, is_infinite: bool, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let entry = self.state.clone();
+        self.breaks.push(State::unreachable());
+        op(self);
+        let breaks = self.breaks.pop().unwrap();
+
+        self.state = if is_infinite { breaks } else { entry };
+    }
+}
+
+impl Visit<RIdent> for Analysis<'_> {
+    fn visit(&mut self, i: &RIdent) {
+        self.read(i);
+    }
+}
+
+impl Visit<RMemberExpr> for Analysis<'_> {
+    fn visit(&mut self, e: &RMemberExpr) {
+        e.obj.visit_with(self);
+
+        if let RMemberProp::Computed(prop) = &e.prop {
+            prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<RSuperPropExpr> for Analysis<'_> {
+    fn visit(&mut self, e: &RSuperPropExpr) {
+        if let RSuperProp::Computed(prop) = &e.prop {
+            prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<RPropName> for Analysis<'_> {
+    fn visit(&mut self, n: &RPropName) {
+        if let RPropName::Computed(n) = n {
+            n.visit_with(self);
+        }
+    }
+}
+
+impl Visit<RAssignExpr> for Analysis<'_> {
+    fn visit(&mut self, e: &RAssignExpr) {
+        if e.op != op!("=") {
+            match &e.left {
+                RPatOrExpr::Pat(box RPat::Ident(i)) => self.read(&i.id),
+                RPatOrExpr::Expr(left) | RPatOrExpr::Pat(box RPat::Expr(left)) => left.visit_with(self),
+                _ => {}
+            }
+        }
+
+        e.right.visit_with(self);
+
+        match &e.left {
+            RPatOrExpr::Pat(p) => self.assign_pat(p),
+            RPatOrExpr::Expr(e) => self.assign_expr(e),
+        }
+    }
+}
+
+impl Visit<RVarDeclarator> for Analysis<'_> {
+    fn visit(&mut self, d: &RVarDeclarator) {
+        if let Some(init) = &d.init {
+            init.visit_with(self);
+            self.assign_pat(&d.name);
+        }
+    }
+}
+
+impl Visit<RBinExpr> for Analysis<'_> {
+    fn visit(&mut self, e: &RBinExpr) {
+        e.left.visit_with(self);
+
+        if matches!(e.op, op!("&&") | op!("||") | op!("??")) {
+            let state = self.state.clone();
+            e.right.visit_with(self);
+            self.state = state;
+        } else {
+            e.right.visit_with(self);
+        }
+    }
+}
+
+impl Visit<RCondExpr> for Analysis<'_> {
+    fn visit(&mut self, e: &RCondExpr) {
+        e.test.visit_with(self);
+
+        let state = self.state.clone();
+        e.cons.visit_with(self);
+        let cons = std::mem::replace(&mut self.state, state);
+        e.alt.visit_with(self);
+
+        self.state = cons.intersect(self.state.clone());
+    }
+}
+
+impl Visit<RIfStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RIfStmt) {
+        s.test.visit_with(self);
+
+        let state = self.state.clone();
+        s.cons.visit_with(self);
+        let cons = std::mem::replace(&mut self.state, state);
+        s.alt.visit_with(self);
+
+        self.state = cons.intersect(self.state.clone());
+    }
+}
+
+impl Visit<RSwitchStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RSwitchStmt) {
+        s.discriminant.visit_with(self);
+
+        let entry = self.state.clone();
+        let has_default = s.cases.iter().any(|case| case.test.is_none());
+
+        self.breaks.push(State::unreachable());
+        for case in &s.cases {
+            // A case is entered by a jump or by falling through.
+            self.state = entry.clone().intersect(self.state.clone());
+            case.visit_with(self);
+        }
+        let breaks = self.breaks.pop().unwrap();
+
+        let mut state = self.state.clone().intersect(breaks);
+        if !has_default {
+            state = state.intersect(entry);
+        }
+        self.state = state;
+    }
+}
+
+impl Visit<RWhileStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RWhileStmt) {
+        s.test.visit_with(self);
+        self.visit_loop(is_truthy_lit(&s.test), |v| s.body.visit_with(v));
+    }
+}
+
+impl Visit<RDoWhileStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RDoWhileStmt) {
+        self.breaks.push(State::unreachable());
+        s.body.visit_with(self);
+        let breaks = self.breaks.pop().unwrap();
+
+        s.test.visit_with(self);
+        self.state = self.state.clone().intersect(breaks);
+    }
+}
+
+impl Visit<RForStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RForStmt) {
+        s.init.visit_with(self);
+        s.test.visit_with(self);
+        self.visit_loop(s.test.as_deref().map_or(true, is_truthy_lit), |v| {
+            s.body.visit_with(v);
+            s.update.visit_with(v);
+        });
+    }
+}
+
+impl Visit<RForInStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RForInStmt) {
+        s.right.visit_with(self);
+        self.visit_loop(false, |v| {
+            if let RVarDeclOrPat::Pat(p) = &s.left {
+                v.assign_pat(p);
+            }
+            s.body.visit_with(v);
+        });
+    }
+}
+
+impl Visit<RForOfStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RForOfStmt) {
+        s.right.visit_with(self);
+        self.visit_loop(false, |v| {
+            if let RVarDeclOrPat::Pat(p) = &s.left {
+                v.assign_pat(p);
+            }
+            s.body.visit_with(v);
+        });
+    }
+}
+
+impl Visit<RTryStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RTryStmt) {
+        let entry = self.state.clone();
+        s.block.visit_with(self);
+
+        if let Some(handler) = &s.handler {
+            // An exception can be thrown anywhere in the `try` block.
+            let block = std::mem::replace(&mut self.state, entry);
+            handler.visit_with(self);
+            self.state = block.intersect(self.state.clone());
+        }
+
+        s.finalizer.visit_with(self);
+    }
+}
+
+impl Visit<RCatchClause> for Analysis<'_> {
+    fn visit(&mut self, c: &RCatchClause) {
+        c.body.visit_with(self);
+    }
+}
+
+impl Visit<RReturnStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RReturnStmt) {
+        s.arg.visit_with(self);
+
+        let state = std::mem::replace(&mut self.state, State::unreachable());
+        self.exit = Some(match self.exit.take() {
+            Some(exit) => exit.intersect(state),
+            None => state,
+        });
+    }
+}
+
+impl Visit<RThrowStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RThrowStmt) {
+        s.arg.visit_with(self);
+        self.state = State::unreachable();
+    }
+}
+
+impl Visit<RBreakStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RBreakStmt) {
+        let state = std::mem::replace(&mut self.state, State::unreachable());
+        if s.label.is_none() {
+            if let Some(breaks) = self.breaks.pop() {
+                self.breaks.push(breaks.intersect(state));
+            }
+        }
+    }
+}
+
+impl Visit<RContinueStmt> for Analysis<'_> {
+    fn visit(&mut self, _: &RContinueStmt) {
+        self.state = State::unreachable();
+    }
+}
+
+impl Visit<RLabeledStmt> for Analysis<'_> {
+    fn visit(&mut self, s: &RLabeledStmt) {
+        s.body.visit_with(self);
+    }
+}
+
+impl Visit<RDecl> for Analysis<'_> {
+    fn visit(&mut self, d: &RDecl) {
+        if let RDecl::Var(d) = d {
+            d.visit_with(self);
+        }
+    }
+}
+
+impl Visit<RModuleDecl> for Analysis<'_> {
+    fn visit(&mut self, d: &RModuleDecl) {
+        match d {
+            RModuleDecl::ExportDecl(d) => d.decl.visit_with(self),
+            RModuleDecl::ExportDefaultExpr(e) => e.expr.visit_with(self),
+            _ => {}
+        }
+    }
+}
+
+impl Visit<RGetterProp> for Analysis<'_> {
+    fn visit(&mut self, p: &RGetterProp) {
+        p.key.visit_with(self);
+    }
+}
+
+impl Visit<RSetterProp> for Analysis<'_> {
+    fn visit(&mut self, p: &RSetterProp) {
+        p.key.visit_with(self);
+    }
+}
+
+/// Variables read by a function are assumed to be assigned, like `tsc`.
+impl Visit<RFunction> for Analysis<'_> {
+    fn visit(&mut self, _: &RFunction) {}
+}
+
+impl Visit<RArrowExpr> for Analysis<'_> {
+    fn visit(&mut self, _: &RArrowExpr) {}
+}
+
+impl Visit<RClass> for Analysis<'_> {
+    fn visit(&mut self, _: &RClass) {}
+}
+
+impl Visit<RTsType> for Analysis<'_> {
+    fn visit(&mut self, _: &RTsType) {}
+}
@@ -8,7 +8,9 @@ use swc_ecma_ast::*;
 use crate::ty::{Intersection, Type, Union};
 
 pub(crate) mod dashmap;
+pub(crate) mod definite_assignment;
 pub(crate) mod graph;
+pub(crate) mod reachability;
 pub(crate) mod type_ext;
 
 pub(crate) trait ModuleItemOrStmt {
@@ -0,0 +1,172 @@
+//! Syntactic reachability of the end of statements.
+//!
+//! This is used by checks which only depend on the shape of the code, like
+//! `noFallthroughCasesInSwitch` and `noImplicitReturns`.
+
+use rnode::{Visit, VisitWith};
+use stc_ts_ast_rnode::{
+    RArrowExpr, RBool, RBreakStmt, RClass, RContinueStmt, RDoWhileStmt, RExpr, RForInStmt, RForOfStmt, RForStmt, RFunction, RLit, RNumber,
+    RStmt, RSwitchStmt, RWhileStmt,
+};
+use swc_atoms::JsWord;
+
+/// Returns `true` if the end of `stmts` is reachable.
+pub(crate) fn can_complete_normally(stmts: &[RStmt]) -> bool {
+    stmts.iter().all(completes)
+}
+
+fn completes(s: &RStmt) -> bool {
+    match s {
+        RStmt::Return(..) | RStmt::Throw(..) | RStmt::Break(..) | RStmt::Continue(..) => false,
+
+        RStmt::Block(s) => can_complete_normally(&s.stmts),
+
+        RStmt::With(s) => completes(&s.body),
+
+        RStmt::If(s) => completes(&s.cons) || s.alt.as_deref().map_or(true, completes),
+
+        RStmt::Try(s) => {
+            if let Some(finalizer) = &s.finalizer {
+                if !can_complete_normally(&finalizer.stmts) {
+                    return false;
+                }
+            }
+
+            can_complete_normally(&s.block.stmts) || s.handler.as_ref().map_or(false, |h| can_complete_normally(&h.body.stmts))
+        }
+
+        RStmt::Switch(s) => {
+            let has_default = s.cases.iter().any(|case| case.test.is_none());
+
+            !has_default
+                || s.cases.last().map_or(true, |case| can_complete_normally(&case.cons))
+                || s.cases.iter().any(|case| case.cons.iter().any(|s| has_jump(s, None).break_))
+        }
+
+        RStmt::While(s) => !is_truthy_lit(&s.test) || has_jump(&s.body, None).break_,
+
+        RStmt::For(s) => s.test.as_deref().map_or(false, |test| !is_truthy_lit(test)) || has_jump(&s.body, None).break_,
+
+        RStmt::DoWhile(s) => {
+            let jump = has_jump(&s.body, None);
+
+            jump.break_ || (!is_truthy_lit(&s.test) && (completes(&s.body) || jump.continue_))
+        }
+
+        RStmt::Labeled(s) => completes(&s.body) || has_jump(&s.body, Some(&s.label.sym)).break_,
+
+        _ => true,
+    }
+}
+
+/// Returns `true` if `e` is a literal which is always truthy, like `true` of
+/// `while (true)`.
+pub(crate) fn is_truthy_lit(e: &RExpr) -> bool {
+    match e {
+        RExpr::Paren(e) => is_truthy_lit(&e.expr),
+        RExpr::Lit(RLit::Bool(RBool { value, .. })) => *value,
+        RExpr::Lit(RLit::Num(RNumber { value, .. })) => *value != 0.0 && !value.is_nan(),
+        _ => false,
+    }
+}
+
+/// Finds `break` and `continue` statements targeting `label`, or the
+/// enclosing statement if `label` is [None].
+pub(crate) fn has_jump(s: &RStmt, label: Option<&JsWord>) -> Jumps {
+    let mut v = JumpFinder {
+        label,
+        loop_depth: 0,
+        switch_depth: 0,
+        found: Default::default(),
+    };
+    s.visit_with(&mut v);
+    v.found
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Jumps {
+    pub break_: bool,
+    pub continue_: bool,
+}
+
+struct JumpFinder<'a> {
+    label: Option<&'a JsWord>,
+    loop_depth: usize,
+    switch_depth: usize,
+    found: Jumps,
+}
+
+impl Visit<RBreakStmt> for JumpFinder<'_> {
+    fn visit(&mut self, s: &RBreakStmt) {
+        match (&s.label, self.label) {
+            (Some(l), Some(label)) => {
+                if l.sym == *label {
+                    self.found.break_ = true;
+                }
+            }
+            (None, None) => {
+                if self.loop_depth == 0 && self.switch_depth == 0 {
+                    self.found.break_ = true;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visit<RContinueStmt> for JumpFinder<'_> {
+    fn visit(&mut self, s: &RContinueStmt) {
+        match (&s.label, self.label) {
+            (Some(l), Some(label)) => {
+                if l.sym == *label {
+                    self.found.continue_ = true;
+                }
+            }
+            (None, None) => {
+                if self.loop_depth == 0 {
+                    self.found.continue_ = true;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visit<RSwitchStmt> for JumpFinder<'_> {
+    fn visit(&mut self, s: &RSwitchStmt) {
+        self.switch_depth += 1;
+        s.visit_children_with(self);
+        self.switch_depth -= 1;
+    }
+}
+
+macro_rules! loop_stmt {
+    ($T:ty) => {
+        impl Visit<$T> for JumpFinder<'_> {
+            fn visit(&mut self, s: &$T) {
+                self.loop_depth += 1;
+                s.visit_children_with(self);
+                self.loop_depth -= 1;
+            }
+        }
+    };
+}
+
+loop_stmt!(RWhileStmt);
+loop_stmt!(RDoWhileStmt);
+loop_stmt!(RForStmt);
+loop_stmt!(RForInStmt);
+loop_stmt!(RForOfStmt);
+
+/// Jumps can't cross function boundaries.
+impl Visit<RFunction> for JumpFinder<'_> {
+    fn visit(&mut self, _: &RFunction) {}
+}
+
+impl Visit<RArrowExpr> for JumpFinder<'_> {
+    fn visit(&mut self, _: &RArrowExpr) {}
+}
+
+impl Visit<RClass> for JumpFinder<'_> {
+    fn visit(&mut self, _: &RClass) {}
+}
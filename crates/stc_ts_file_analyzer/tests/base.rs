@@ -41,7 +41,8 @@ struct StcError {
     code: usize,
 }
 
-fn get_env() -> Env {
+/// `spec` is used for options which are disabled by default.
+fn get_env(spec: &Rule, module_config: ModuleConfig) -> Env {
     let mut libs = vec![];
     let ls = &[
         "es2022.full",
@@ -62,15 +63,32 @@ fn get_env() -> Env {
     Env::simple(
         Rule {
             strict_function_types: true,
+            strict_null_checks: spec.strict_null_checks,
+            no_unused_locals: spec.no_unused_locals,
+            no_unused_parameters: spec.no_unused_parameters,
+            allow_unused_labels: spec.allow_unused_labels,
+            no_fallthrough_cases_in_switch: spec.no_fallthrough_cases_in_switch,
+            no_implicit_returns: spec.no_implicit_returns,
+            no_implicit_override: spec.no_implicit_override,
+            use_unknown_in_catch_variables: spec.use_unknown_in_catch_variables,
+            strict_property_initialization: spec.strict_property_initialization,
+            exact_optional_property_types: spec.exact_optional_property_types,
+            no_unchecked_indexed_access: spec.no_unchecked_indexed_access,
+            no_property_access_from_index_signature: spec.no_property_access_from_index_signature,
+            isolated_modules: spec.isolated_modules,
+            verbatim_module_syntax: spec.verbatim_module_syntax,
+            experimental_decorators: spec.experimental_decorators,
             ..Default::default()
         },
         EsVersion::latest(),
-        ModuleConfig::None,
+        module_config,
         &libs,
     )
 }
 
 fn validate(input: &Path) -> Vec<StcError> {
+    let case = parse_conformance_test(input).unwrap().into_iter().next().unwrap();
+
     let tester = Tester::new();
     let diagnostics = tester
         .errors(|cm, handler| {
@@ -78,7 +96,7 @@ fn validate(input: &Path) -> Vec<StcError> {
 
             let fm = cm.load_file(input).unwrap();
 
-            let env = get_env();
+            let env = get_env(&case.rule, case.module_config);
 
             let generator = module_id::ModuleIdGenerator::default();
             let path = Arc::new(FileName::Real(input.to_path_buf()));
@@ -88,7 +106,10 @@ fn validate(input: &Path) -> Vec<StcError> {
             let mut node_id_gen = NodeIdGenerator::default();
             let mut module = {
                 let lexer = Lexer::new(
-                    Syntax::Typescript(TsConfig { ..Default::default() }),
+                    Syntax::Typescript(TsConfig {
+                        decorators: true,
+                        ..Default::default()
+                    }),
                     EsVersion::Es2021,
                     SourceFileInput::from(&*fm),
                     None,
@@ -226,6 +247,10 @@ fn tsc_args(c: &mut Command, spec: &TestSpec) {
         EsVersion::EsNext => "esnext",
     });
 
+    if !matches!(spec.module_config, ModuleConfig::None) {
+        c.arg("--module").arg(spec.module_config.to_string());
+    }
+
     if spec.rule.strict_function_types {
         c.arg("--strictFunctionTypes");
     }
@@ -233,6 +258,62 @@ fn tsc_args(c: &mut Command, spec: &TestSpec) {
     if spec.rule.strict_null_checks {
         c.arg("--strictNullChecks");
     }
+
+    if spec.rule.no_unused_locals {
+        c.arg("--noUnusedLocals");
+    }
+
+    if spec.rule.no_unused_parameters {
+        c.arg("--noUnusedParameters");
+    }
+
+    if let Some(v) = spec.rule.allow_unused_labels {
+        c.arg("--allowUnusedLabels").arg(v.to_string());
+    }
+
+    if spec.rule.no_fallthrough_cases_in_switch {
+        c.arg("--noFallthroughCasesInSwitch");
+    }
+
+    if spec.rule.no_implicit_returns {
+        c.arg("--noImplicitReturns");
+    }
+
+    if spec.rule.strict_property_initialization {
+        c.arg("--strictPropertyInitialization");
+    }
+
+    if spec.rule.use_unknown_in_catch_variables {
+        c.arg("--useUnknownInCatchVariables");
+    }
+
+    if spec.rule.no_implicit_override {
+        c.arg("--noImplicitOverride");
+    }
+
+    if spec.rule.exact_optional_property_types {
+        c.arg("--exactOptionalPropertyTypes");
+    }
+
+    if spec.rule.no_unchecked_indexed_access {
+        c.arg("--noUncheckedIndexedAccess");
+    }
+
+    if spec.rule.no_property_access_from_index_signature {
+        c.arg("--noPropertyAccessFromIndexSignature");
+    }
+
+    if spec.rule.isolated_modules {
+        c.arg("--isolatedModules");
+    }
+
+    if spec.rule.verbatim_module_syntax {
+        c.arg("--verbatimModuleSyntax");
+    }
+
+    if spec.rule.experimental_decorators {
+        c.arg("--experimentalDecorators");
+    }
 }
 
 /// If `for_error` is false, this function will run as type dump mode.
@@ -0,0 +1,18 @@
+// @useUnknownInCatchVariables: true
+
+export function f() {
+    try {
+    } catch (e) {
+        if (e instanceof Error) {
+            e.message;
+        }
+    }
+
+    try {
+    } catch (e: string) {
+    }
+
+    try {
+    } catch (e: unknown) {
+    }
+}
@@ -0,0 +1,14 @@
+declare function methodDec(value: Function, context: ClassMethodDecoratorContext): void;
+declare function fieldDec(value: undefined, context: ClassFieldDecoratorContext): (value: number) => number;
+declare function legacyDec(target: any, key: string, descriptor: PropertyDescriptor): void;
+
+export class C {
+    @methodDec
+    method() {}
+
+    @fieldDec
+    x = 1;
+
+    @legacyDec
+    y = 2;
+}
@@ -0,0 +1,20 @@
+// @experimentalDecorators: true
+
+declare function classDec(target: Function): void;
+declare function badClassDec(target: Function, extra: string): void;
+declare function methodDec(target: any, key: string, descriptor: PropertyDescriptor): void;
+declare function badMethodDec(target: any, key: number, descriptor: PropertyDescriptor): void;
+declare function paramDec(target: any, key: string | undefined, index: number): void;
+declare function badParamDec(target: any, key: string, index: string): void;
+
+@classDec
+class C {
+    @methodDec
+    method(@paramDec a: number) {}
+
+    @badMethodDec
+    other(@badParamDec b: number) {}
+}
+
+@badClassDec
+class D {}
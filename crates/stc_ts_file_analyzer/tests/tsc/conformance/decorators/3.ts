@@ -0,0 +1,9 @@
+declare function paramDec(target: any, key: string | undefined, index: number): void;
+
+class C {
+    constructor(@paramDec a: number) {}
+
+    method(@paramDec b: number) {}
+}
+
+function f(@paramDec c: number) {}
@@ -0,0 +1,21 @@
+// @target: es2020
+
+const enum Prefix {
+    A = "a",
+    B = `${A}-b`,
+    C = A + "-c",
+}
+
+enum Big {
+    X = 1n,
+}
+
+enum Merged {
+    First = 1,
+}
+
+const enum Merged {
+    Second = 2,
+}
+
+export const a: "a-b" = Prefix.B;
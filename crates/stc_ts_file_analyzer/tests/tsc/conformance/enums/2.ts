@@ -0,0 +1,18 @@
+enum Str {
+    A = `${1e21}`,
+    B = `${0.000001}`,
+    C = `${1e-7}`,
+    D = `${1 / 0}`,
+    E = `${0 / 0}`,
+    F = "x" + 1e21,
+    G = 1.5e-7 + "",
+}
+
+export const a: "1e+21" = Str.A;
+export const b: "0.000001" = Str.B;
+export const c: "1e-7" = Str.C;
+export const d: "Infinity" = Str.D;
+export const e: "NaN" = Str.E;
+export const f: "x1e+21" = Str.F;
+export const g: "1.5e-7" = Str.G;
+export const h: "1e21" = Str.A;
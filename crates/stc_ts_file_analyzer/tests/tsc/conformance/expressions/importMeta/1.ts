@@ -0,0 +1,11 @@
+// @module: commonjs
+
+export {};
+
+declare global {
+    interface ImportMeta {
+        env: { MODE: string };
+    }
+}
+
+const mode: string = import.meta.env.MODE;
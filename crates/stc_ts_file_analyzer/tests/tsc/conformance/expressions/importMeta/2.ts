@@ -0,0 +1,12 @@
+// @module: esnext
+
+export {};
+
+declare global {
+    interface ImportMeta {
+        env: { MODE: string };
+    }
+}
+
+const url: string = import.meta.url;
+const mode: number = import.meta.env.MODE;
@@ -0,0 +1,33 @@
+// @noFallthroughCasesInSwitch: true
+// @noImplicitReturns: true
+// @allowUnusedLabels: false
+
+export function f(x: number): number {
+    switch (x) {
+        case 0:
+        case 1:
+            x++;
+        case 2:
+            return 1;
+        default:
+            break;
+    }
+    if (x > 3) {
+        return 2;
+    }
+}
+
+export function g(x: number): string {
+    if (x) {
+        return "a";
+    }
+    return;
+}
+
+export function h() {
+    unused: for (let i = 0; i < 3; i++) {
+        used: for (let j = 0; j < 3; j++) {
+            if (j) continue used;
+        }
+    }
+}
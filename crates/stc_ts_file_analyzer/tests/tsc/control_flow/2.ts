@@ -0,0 +1,35 @@
+declare function assertIsString(x: unknown): asserts x is string;
+declare function assert(cond: unknown): asserts cond;
+
+const assertArrow = (x: unknown): asserts x => {};
+
+export function f(x: unknown, y: string | undefined) {
+    assertIsString(x);
+    x.length;
+    assert(y);
+    y.length;
+    assertArrow(y);
+}
+
+declare const assertions: { assertIsString(x: unknown): asserts x is string };
+const { assertIsString: destructured } = assertions;
+
+export function g(x: string | number, param = assertIsString) {
+    destructured(x);
+    param(x);
+    const r = assertIsString(x);
+    x.length;
+}
+
+class Animal {
+    assertIsDog(): asserts this is Dog {}
+}
+
+class Dog extends Animal {
+    bark() {}
+}
+
+export function h(a: Animal) {
+    a.assertIsDog();
+    a.bark();
+}
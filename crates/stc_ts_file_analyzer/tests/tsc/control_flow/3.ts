@@ -0,0 +1,18 @@
+// @noImplicitReturns: true
+
+declare function fail(message: string): never;
+declare function log(message: string): void;
+
+export function f(x: number): number {
+    if (x > 0) {
+        return x;
+    }
+    fail("negative");
+}
+
+export function g(x: number): number {
+    if (x > 0) {
+        return x;
+    }
+    log("negative");
+}
@@ -0,0 +1,25 @@
+// @strictNullChecks: true
+
+export function f(cond: boolean) {
+    let a: number;
+    while (true) {
+        a = 1;
+        break;
+    }
+    a;
+
+    let b: number;
+    while (cond) {
+        b = 1;
+    }
+    b;
+
+    let c: number;
+    for (;;) {
+        if (cond) {
+            c = 1;
+            break;
+        }
+    }
+    c;
+}
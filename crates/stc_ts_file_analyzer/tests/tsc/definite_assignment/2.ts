@@ -0,0 +1,21 @@
+// @strictNullChecks: true
+// @strictPropertyInitialization: true
+
+export class C {
+    a: number;
+    "b": number;
+    "c": number;
+    d: number;
+    e: number;
+
+    constructor(cond: boolean) {
+        this["c"] = 1;
+        while (true) {
+            this.d = 1;
+            break;
+        }
+        if (cond) {
+            this.e = 1;
+        }
+    }
+}
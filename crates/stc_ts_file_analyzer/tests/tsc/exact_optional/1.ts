@@ -0,0 +1,19 @@
+// @strictNullChecks: true
+// @exactOptionalPropertyTypes: true
+
+interface Options {
+    name?: string;
+}
+
+const a: Options = { name: undefined };
+
+declare const options: Options;
+options.name = undefined;
+
+const b: Options = {};
+options.name = "b";
+
+type Flags = Partial<[boolean, number]>;
+const flags: Flags = [true];
+
+export { a, b, flags };
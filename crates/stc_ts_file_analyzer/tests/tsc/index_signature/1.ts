@@ -0,0 +1,12 @@
+// @noPropertyAccessFromIndexSignature: true
+
+interface Dict {
+    known: string;
+    [key: string]: string;
+}
+
+export function f(d: Dict) {
+    d.known;
+    d.unknown;
+    d["unknown"];
+}
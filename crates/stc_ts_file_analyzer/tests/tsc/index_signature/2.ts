@@ -0,0 +1,7 @@
+// @noUncheckedIndexedAccess: true
+// @strictNullChecks: true
+
+export function f<T extends { [key: string]: number }, K extends string>(obj: T, key: K) {
+    const value: number = obj[key];
+    return value;
+}
@@ -0,0 +1,27 @@
+// @noUncheckedIndexedAccess: true
+// @strictNullChecks: true
+
+declare const arr: string[];
+declare const dict: { [key: string]: number };
+declare const rows: string[][];
+declare const tuple: [string, number];
+
+const [a] = arr;
+export const s: string = a;
+
+const { x } = dict;
+export const n: number = x;
+
+const [first] = tuple;
+export const t: string = first;
+
+for (const [cell] of rows) {
+    const c: string = cell;
+}
+
+for (const item of arr) {
+    const v: string = item;
+}
+
+const [withDefault = ""] = arr;
+export const d: string = withDefault;
@@ -0,0 +1,17 @@
+// @noUncheckedIndexedAccess: true
+// @strictNullChecks: true
+
+declare const arr: string[];
+declare const dict: { [key: string]: number };
+
+let a: string;
+let b: string = "";
+let x: number;
+let y: number;
+
+[a] = arr;
+[b = ""] = arr;
+({ x } = dict);
+({ key: y } = dict);
+
+export {};
@@ -0,0 +1,4 @@
+// @isolatedModules: true
+
+const a = 1;
+let b = a;
@@ -0,0 +1,7 @@
+// @isolatedModules: true
+
+declare const enum E {
+    A = 1,
+}
+
+export const x = E.A;
@@ -0,0 +1,21 @@
+// @noImplicitOverride: true
+
+class Base {
+    foo() {}
+    bar = 1;
+    static s() {}
+}
+
+export class Derived extends Base {
+    foo() {}
+    override bar = 2;
+    override baz() {}
+    static s() {}
+    constructor(public foo2: number) {
+        super();
+    }
+}
+
+export class NoBase {
+    override foo() {}
+}
@@ -0,0 +1,4 @@
+type Count<N extends unknown[]> = N["length"] extends 200 ? 0 : 1 | Count<[...N, 0]>;
+declare function count<N extends unknown[]>(n: N): Count<N>;
+
+count([]);
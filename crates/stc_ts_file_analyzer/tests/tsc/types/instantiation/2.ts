@@ -0,0 +1,5 @@
+type Build<L extends number, A extends unknown[] = []> = A["length"] extends L ? A : Build<L, [...A, 0]>;
+declare function build<L extends number>(l: L): Build<L>;
+
+build(999);
+build(1000);
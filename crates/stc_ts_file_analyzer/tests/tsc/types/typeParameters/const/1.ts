@@ -0,0 +1,10 @@
+declare function tuple<const T extends readonly unknown[]>(x: T): T;
+declare function lit<const T>(x: T): T;
+
+const t = tuple([1, "a"]);
+const t1: readonly [1, "a"] = t;
+const t2: readonly [2, "a"] = t;
+
+const l = lit({ a: 1 });
+const l1: { a: 1 } = l;
+const l2: { a: 2 } = l;
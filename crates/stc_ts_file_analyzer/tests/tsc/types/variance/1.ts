@@ -0,0 +1,10 @@
+type Covariant<out T> = {
+    x: T;
+};
+
+type Identity<in out T> = T;
+
+declare let sub: Covariant<"a">;
+declare let sup: Covariant<string>;
+
+sup = sub;
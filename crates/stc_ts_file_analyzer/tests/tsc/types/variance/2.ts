@@ -0,0 +1,17 @@
+interface Consumer<out T> {
+    consume: (value: T) => void;
+}
+
+type Producer<in T> = {
+    produce: () => T;
+};
+
+type Sink<in T> = {
+    write: (value: T) => void;
+};
+
+declare let sinkStr: Sink<string>;
+declare let sinkA: Sink<"a">;
+
+sinkStr = sinkA;
+sinkA = sinkStr;
@@ -0,0 +1,23 @@
+// @noUnusedLocals: true
+// @noUnusedParameters: true
+
+export function foo(used: number, unused: string, _ignored: boolean) {
+    const local = 1;
+    let written = 2;
+    written = 3;
+    const { x, y } = { x: 1, y: 2 };
+    return used;
+}
+
+interface Unused {}
+
+class C {
+    private unusedMember = 1;
+    private usedMember = 2;
+
+    method() {
+        return this.usedMember;
+    }
+}
+
+export const c = new C();
@@ -0,0 +1,9 @@
+// @noUnusedParameters: true
+
+class C {
+    private unusedMember = 1;
+
+    method(unused: number) {}
+}
+
+export const c = new C();
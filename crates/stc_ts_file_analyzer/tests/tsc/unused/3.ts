@@ -0,0 +1,15 @@
+// @noUnusedLocals: true
+// @noUnusedParameters: true
+
+type Used = { value: number };
+type Unused = string;
+
+function generic<T, U>(value: T): T {
+    return value;
+}
+
+export function destructure([_first, second]: number[], { a, ...rest }: { a: number; b: number }) {
+    return rest;
+}
+
+export const v: Used = { value: generic(1) };
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use stc_ts_env::{ModuleConfig, Rule};
+use stc_ts_env::{ExtraCompilerOptions, ModuleConfig, Rule};
 use swc_common::FileName;
 use swc_ecma_ast::EsVersion;
 use tracing::error;
@@ -55,7 +55,11 @@ pub(crate) fn parse_ts_config(db: &dyn Db, src: SourceFile) -> ParsedTsConfig {
 
     ParsedTsConfig::new(
         db,
-        v.compiler_options.as_ref().map(Rule::from).unwrap_or_default(),
+        v.compiler_options
+            .as_ref()
+            .map(Rule::from)
+            .unwrap_or_default()
+            .with_extra_options(ExtraCompilerOptions::parse_str(s)),
         v.compiler_options
             .as_ref()
             .and_then(|v| v.target.clone())
@@ -41,7 +41,8 @@ pub(crate) fn get_module_loader(db: &dyn Db, config: ParsedTsConfig) -> ProjectE
         builtin,
     );
 
-    let loader = ModuleLoader::new(cm, env.clone(), NodeResolver, DefaultFileLoader);
+    let resolver = NodeResolver::new().allow_js(env.rule().allow_js);
+    let loader = ModuleLoader::new(cm, env.clone(), resolver, DefaultFileLoader);
 
     ProjectEnv::new(db, config, DebugIgnore(env), DebugIgnore(Arc::new(loader)))
 }
@@ -12,6 +12,9 @@ use swc_ecma_loader::resolve::Resolve;
 
 static EXTENSIONS: &[&str] = &["tsx", "ts", "d.ts"];
 
+/// Tried after [EXTENSIONS] if `allowJs` is enabled.
+static JS_EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "cjs"];
+
 #[derive(Deserialize)]
 struct PackageJson {
     #[serde(default)]
@@ -19,11 +22,24 @@ struct PackageJson {
 }
 
 #[derive(Default)]
-pub struct NodeResolver;
+pub struct NodeResolver {
+    allow_js: bool,
+}
 
 impl NodeResolver {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Resolves JavaScript files if `allow_js` is true.
+    pub fn allow_js(self, allow_js: bool) -> Self {
+        Self { allow_js }
+    }
+
+    fn extensions(&self) -> impl Iterator<Item = &'static str> {
+        let js = if self.allow_js { JS_EXTENSIONS } else { &[] };
+
+        EXTENSIONS.iter().chain(js).copied()
     }
 
     fn wrap(&self, path: PathBuf) -> Result<FileName, Error> {
@@ -39,7 +55,7 @@ impl NodeResolver {
             return Ok(path.to_path_buf());
         }
 
-        for ext in EXTENSIONS {
+        for ext in self.extensions() {
             let ext_path = path.with_extension(ext);
             if ext_path.is_file() {
                 return Ok(ext_path);
@@ -87,7 +103,7 @@ impl NodeResolver {
         // 1. If X/index.js is a file, load X/index.js as JavaScript text.
         // 2. If X/index.json is a file, parse X/index.json to a JavaScript object.
         // 3. If X/index.node is a file, load X/index.node as binary addon.
-        for ext in EXTENSIONS {
+        for ext in self.extensions() {
             let ext_path = path.join(format!("index.{}", ext));
             if ext_path.is_file() {
                 return Ok(ext_path);
@@ -97,6 +97,7 @@ pub fn parse_conformance_test(file_name: &Path) -> Result<Vec<TestSpec>> {
         let mut parser = Parser::new(
             Syntax::Typescript(TsConfig {
                 tsx: fname.contains("tsx"),
+                decorators: true,
                 ..Default::default()
             }),
             SourceFileInput::from(&*fm),
@@ -178,6 +179,8 @@ pub fn parse_conformance_test(file_name: &Path) -> Result<Vec<TestSpec>> {
                     rule.always_strict = strict;
                     rule.strict_null_checks = strict;
                     rule.strict_function_types = strict;
+                    rule.use_unknown_in_catch_variables = strict;
+                    rule.strict_property_initialization = strict;
                 } else if s.starts_with("noLib:") {
                     let v = s["noLib:".len()..].trim().parse().unwrap();
                     rule.no_lib = v;
@@ -190,6 +193,21 @@ pub fn parse_conformance_test(file_name: &Path) -> Result<Vec<TestSpec>> {
                 } else if s.starts_with("noImplicitReturns:") {
                     let v = s["noImplicitReturns:".len()..].trim().parse().unwrap();
                     rule.no_implicit_returns = v;
+                } else if s.starts_with("noFallthroughCasesInSwitch:") {
+                    let v = s["noFallthroughCasesInSwitch:".len()..].trim().parse().unwrap();
+                    rule.no_fallthrough_cases_in_switch = v;
+                } else if s.starts_with("exactOptionalPropertyTypes:") {
+                    let v = s["exactOptionalPropertyTypes:".len()..].trim().parse().unwrap();
+                    rule.exact_optional_property_types = v;
+                } else if s.starts_with("noImplicitOverride:") {
+                    let v = s["noImplicitOverride:".len()..].trim().parse().unwrap();
+                    rule.no_implicit_override = v;
+                } else if s.starts_with("useUnknownInCatchVariables:") {
+                    let v = s["useUnknownInCatchVariables:".len()..].trim().parse().unwrap();
+                    rule.use_unknown_in_catch_variables = v;
+                } else if s.starts_with("noPropertyAccessFromIndexSignature:") {
+                    let v = s["noPropertyAccessFromIndexSignature:".len()..].trim().parse().unwrap();
+                    rule.no_property_access_from_index_signature = v;
                 } else if s.starts_with("noUncheckedIndexedAccess:") {
                     let v = s["noUncheckedIndexedAccess:".len()..].trim().parse().unwrap();
                     rule.no_unchecked_indexed_access = v;
@@ -198,9 +216,15 @@ pub fn parse_conformance_test(file_name: &Path) -> Result<Vec<TestSpec>> {
                     // TODO(kdy1): Handle
                 } else if s.starts_with("traceResolution") {
                     // no-op
+                } else if s.starts_with("noUnusedLocals:") {
+                    let v = s["noUnusedLocals:".len()..].trim().parse().unwrap();
+                    rule.no_unused_locals = v;
+                } else if s.starts_with("noUnusedParameters:") {
+                    let v = s["noUnusedParameters:".len()..].trim().parse().unwrap();
+                    rule.no_unused_parameters = v;
                 } else if s.starts_with("allowUnusedLabels:") {
                     let v = s["allowUnusedLabels:".len()..].trim().parse().unwrap();
-                    rule.allow_unused_labels = v;
+                    rule.allow_unused_labels = Some(v);
                 } else if s.starts_with("noEmitHelpers") {
                     // TODO
                 } else if s.starts_with("downlevelIteration:") {
@@ -208,7 +232,14 @@ pub fn parse_conformance_test(file_name: &Path) -> Result<Vec<TestSpec>> {
                 } else if s.starts_with("sourceMap:") || s.starts_with("sourcemap:") {
                     // TODO
                 } else if s.starts_with("isolatedModules:") {
-                    // TODO
+                    let v = s["isolatedModules:".len()..].trim().parse().unwrap();
+                    rule.isolated_modules = v;
+                } else if s.starts_with("verbatimModuleSyntax:") {
+                    let v = s["verbatimModuleSyntax:".len()..].trim().parse().unwrap();
+                    rule.verbatim_module_syntax = v;
+                } else if s.starts_with("experimentalDecorators:") {
+                    let v = s["experimentalDecorators:".len()..].trim().parse().unwrap();
+                    rule.experimental_decorators = v;
                 } else if s.starts_with("lib:") {
                     let s = s["lib:".len()..].trim();
                     let mut ls = FxHashSet::<_>::default();
@@ -226,6 +257,9 @@ pub fn parse_conformance_test(file_name: &Path) -> Result<Vec<TestSpec>> {
                 } else if s.starts_with("allowUnreachableCode:") {
                     let v = s["allowUnreachableCode:".len()..].trim().parse().unwrap();
                     rule.allow_unreachable_code = v;
+                } else if s.starts_with("strictPropertyInitialization:") {
+                    let v = s["strictPropertyInitialization:".len()..].trim().parse().unwrap();
+                    rule.strict_property_initialization = v;
                 } else if s.starts_with("strictNullChecks:") {
                     let v = s["strictNullChecks:".len()..].trim().parse().unwrap();
                     rule.strict_null_checks = v;
@@ -255,10 +289,15 @@ pub fn parse_conformance_test(file_name: &Path) -> Result<Vec<TestSpec>> {
                     rule.always_strict = strict;
                     rule.strict_null_checks = strict;
                     rule.strict_function_types = strict;
+                    rule.use_unknown_in_catch_variables = strict;
+                    rule.strict_property_initialization = strict;
                 } else if s.to_ascii_lowercase().starts_with("filename") {
-                } else if s.to_ascii_lowercase().starts_with("allowjs") || s.to_ascii_lowercase().starts_with("checkjs") {
-                    // panic!("allowJs and checkJs are not supported yet. See https://github.com/dudykr/stc/issues/702")
-                    return Err(());
+                } else if s.to_ascii_lowercase().starts_with("allowjs:") {
+                    let v = s["allowJs:".len()..].trim().parse().unwrap();
+                    rule.allow_js = v;
+                } else if s.to_ascii_lowercase().starts_with("checkjs:") {
+                    let v = s["checkJs:".len()..].trim().parse().unwrap();
+                    rule.check_js = v;
                 } else {
                     writeln!(stderr(), "Comment is not handled: {}", s).unwrap();
                 }
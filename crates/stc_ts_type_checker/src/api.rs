@@ -0,0 +1,480 @@
+//! Textual report of the public api of a module, and classification of the
+//! differences between two reports.
+//!
+//! The report is sorted by name so that it can be committed and diffed by
+//! release tooling.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    sync::Arc,
+};
+
+use rnode::{NodeId, RNode};
+use stc_ts_ast_rnode::{RTsType, RTsTypeElement, RTsTypeLit, RTsTypeParamDecl};
+use stc_ts_types::{Function, Intersection, ModuleTypeData, Type, TypeElement, Union};
+use stc_utils::cache::ALLOW_DEEP_CLONE;
+use swc_common::{sync::Lrc, FileName, SourceMap, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+
+use crate::Checker;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExportKind {
+    Type,
+    Var,
+}
+
+impl Display for ExportKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportKind::Type => write!(f, "type"),
+            ExportKind::Var => write!(f, "var"),
+        }
+    }
+}
+
+/// An exported name.
+#[derive(Debug, Clone)]
+pub struct ApiEntry {
+    pub kind: ExportKind,
+    /// Names exported from namespaces are joined with `.`.
+    pub name: String,
+    /// Declarations of the export, printed as typescript.
+    pub printed: String,
+    /// All declarations of the export. This contains multiple types for merged
+    /// declarations.
+    pub types: Vec<Type>,
+}
+
+/// Sorted list of exported names of a module.
+#[derive(Debug, Clone, Default)]
+pub struct ApiReport {
+    entries: BTreeMap<(String, ExportKind), ApiEntry>,
+}
+
+impl ApiReport {
+    pub fn new(data: &ModuleTypeData) -> Self {
+        let mut report = ApiReport::default();
+        report.add_exports("", data);
+        report
+    }
+
+    /// Analyzes `path` using `checker` and returns the report of its exports.
+    ///
+    /// Types in the report are expanded in the context of the module, so that
+    /// reports of different modules can be compared using [diff] and
+    /// [Checker::is_assignable].
+    ///
+    /// Returns [None] if `path` is not a module.
+    pub fn load(checker: &Checker, path: Arc<FileName>) -> Option<Self> {
+        let id = checker.check(path.clone());
+
+        let ty = checker.get_types(id)?;
+        let mut report = match ty.normalize() {
+            Type::Module(m) => ApiReport::new(&m.exports),
+            _ => return None,
+        };
+
+        for entry in report.entries.values_mut() {
+            entry.types = entry.types.iter().map(|ty| checker.expand_exported_type(&path, ty)).collect();
+        }
+
+        Some(report)
+    }
+
+    fn add_exports(&mut self, prefix: &str, data: &ModuleTypeData) {
+        for (name, ty) in data.vars.iter() {
+            let name = format!("{}{}", prefix, name);
+
+            match ty.normalize() {
+                Type::Namespace(ns) => {
+                    self.add_exports(&format!("{}.", name), &ns.exports);
+                }
+                Type::Module(m) => {
+                    self.add_exports(&format!("{}.", name), &m.exports);
+                }
+                _ => {
+                    let printed = print_var(&name, ty);
+                    self.insert(ExportKind::Var, name, printed, vec![ty.clone()]);
+                }
+            }
+        }
+
+        for (name, types) in data.types.iter() {
+            let name = format!("{}{}", prefix, name);
+
+            let mut printed = vec![];
+            let mut decls = vec![];
+            for ty in types {
+                match ty.normalize() {
+                    // Already handled as a variable.
+                    Type::Namespace(..) | Type::Module(..) => {}
+                    _ => {
+                        printed.push(print_type_decl(&name, ty));
+                        decls.push(ty.clone());
+                    }
+                }
+            }
+
+            if !decls.is_empty() {
+                self.insert(ExportKind::Type, name, printed.join("\n"), decls);
+            }
+        }
+    }
+
+    fn insert(&mut self, kind: ExportKind, name: String, printed: String, types: Vec<Type>) {
+        self.entries.insert(
+            (name.clone(), kind),
+            ApiEntry {
+                kind,
+                name,
+                printed,
+                types,
+            },
+        );
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &ApiEntry> {
+        self.entries.values()
+    }
+
+    pub fn get(&self, kind: ExportKind, name: &str) -> Option<&ApiEntry> {
+        self.entries.get(&(name.to_string(), kind))
+    }
+
+    /// Returns the report as text. The output is stable across runs.
+    pub fn to_text(&self) -> String {
+        let mut buf = String::new();
+
+        for entry in self.entries() {
+            buf.push_str(&entry.printed);
+            buf.push('\n');
+        }
+
+        buf
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Breaking,
+    NonBreaking,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeReason {
+    Removed,
+    Added,
+    /// The parameter at `index` does not accept some values accepted before.
+    NarrowedParam {
+        index: usize,
+    },
+    AddedRequiredParam {
+        index: usize,
+    },
+    /// The optional parameter at `index` became required.
+    MadeParamRequired {
+        index: usize,
+    },
+    WidenedReturn,
+    /// The type of a variable was changed to a type which is not assignable to
+    /// the previous one.
+    WidenedType,
+    /// The type was changed in a way which is not compatible in both
+    /// directions.
+    IncompatibleType,
+    /// The printed type changed, but it's compatible with the previous one.
+    Compatible,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiChange {
+    pub severity: Severity,
+    pub kind: ExportKind,
+    pub name: String,
+    pub reason: ChangeReason,
+}
+
+impl Display for ApiChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Breaking => "breaking",
+            Severity::NonBreaking => "non-breaking",
+        };
+        write!(f, "{}: {} `{}`: ", severity, self.kind, self.name)?;
+
+        match &self.reason {
+            ChangeReason::Removed => write!(f, "removed export"),
+            ChangeReason::Added => write!(f, "added export"),
+            ChangeReason::NarrowedParam { index } => write!(f, "narrowed parameter #{}", index),
+            ChangeReason::AddedRequiredParam { index } => write!(f, "added required parameter #{}", index),
+            ChangeReason::MadeParamRequired { index } => write!(f, "made parameter #{} required", index),
+            ChangeReason::WidenedReturn => write!(f, "widened return type"),
+            ChangeReason::WidenedType => write!(f, "widened type"),
+            ChangeReason::IncompatibleType => write!(f, "incompatible type"),
+            ChangeReason::Compatible => write!(f, "compatible type change"),
+        }
+    }
+}
+
+/// Compares two reports.
+///
+/// `is_assignable(left, right)` should return `true` if `right` is assignable
+/// to `left`.
+pub fn diff<F>(old: &ApiReport, new: &ApiReport, mut is_assignable: F) -> Vec<ApiChange>
+where
+    F: FnMut(&Type, &Type) -> bool,
+{
+    let mut changes = vec![];
+
+    for (key, old_entry) in old.entries.iter() {
+        let new_entry = match new.entries.get(key) {
+            Some(v) => v,
+            None => {
+                changes.push(ApiChange {
+                    severity: Severity::Breaking,
+                    kind: old_entry.kind,
+                    name: old_entry.name.clone(),
+                    reason: ChangeReason::Removed,
+                });
+                continue;
+            }
+        };
+
+        if old_entry.printed == new_entry.printed {
+            continue;
+        }
+
+        let reason = match old_entry.kind {
+            ExportKind::Var => compare_var(&old_entry.types, &new_entry.types, &mut is_assignable),
+            ExportKind::Type => {
+                let compatible = old_entry.types.len() == new_entry.types.len()
+                    && old_entry
+                        .types
+                        .iter()
+                        .zip(new_entry.types.iter())
+                        .all(|(o, n)| is_assignable(o, n) && is_assignable(n, o));
+
+                if compatible {
+                    ChangeReason::Compatible
+                } else {
+                    ChangeReason::IncompatibleType
+                }
+            }
+        };
+
+        changes.push(ApiChange {
+            severity: if reason == ChangeReason::Compatible {
+                Severity::NonBreaking
+            } else {
+                Severity::Breaking
+            },
+            kind: old_entry.kind,
+            name: old_entry.name.clone(),
+            reason,
+        });
+    }
+
+    for (key, new_entry) in new.entries.iter() {
+        if !old.entries.contains_key(key) {
+            changes.push(ApiChange {
+                severity: Severity::NonBreaking,
+                kind: new_entry.kind,
+                name: new_entry.name.clone(),
+                reason: ChangeReason::Added,
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| (a.severity, &a.name, a.kind).cmp(&(b.severity, &b.name, b.kind)));
+
+    changes
+}
+
+/// A value is used by consumers, so the new type should be assignable to the
+/// old one.
+///
+/// Each signature of the old type, including overloads and members of unions,
+/// should be accepted by a signature of the new type.
+fn compare_var<F>(old: &[Type], new: &[Type], is_assignable: &mut F) -> ChangeReason
+where
+    F: FnMut(&Type, &Type) -> bool,
+{
+    let old_sigs = old.iter().flat_map(signatures).collect::<Vec<_>>();
+    let new_sigs = new.iter().flat_map(signatures).collect::<Vec<_>>();
+
+    if !new_sigs.is_empty() {
+        for (index, old_fn) in old_sigs.iter().enumerate() {
+            let mut reasons = vec![];
+            for new_fn in &new_sigs {
+                match compare_signature(old_fn, new_fn, is_assignable) {
+                    Some(reason) => reasons.push(reason),
+                    None => break,
+                }
+            }
+
+            if reasons.len() == new_sigs.len() {
+                // Report the reason for the signature at the same position.
+                return reasons.swap_remove(index.min(reasons.len() - 1));
+            }
+        }
+    }
+
+    let compatible = old.len() == new.len() && old.iter().zip(new.iter()).all(|(o, n)| is_assignable(o, n));
+    if compatible {
+        ChangeReason::Compatible
+    } else {
+        ChangeReason::WidenedType
+    }
+}
+
+/// Returns [None] if calls valid for `old_fn` are valid for `new_fn`.
+fn compare_signature<F>(old_fn: &Function, new_fn: &Function, is_assignable: &mut F) -> Option<ChangeReason>
+where
+    F: FnMut(&Type, &Type) -> bool,
+{
+    for (index, (o, n)) in old_fn.params.iter().zip(new_fn.params.iter()).enumerate() {
+        if !is_assignable(&n.ty, &o.ty) {
+            return Some(ChangeReason::NarrowedParam { index });
+        }
+
+        if !o.required && n.required {
+            return Some(ChangeReason::MadeParamRequired { index });
+        }
+    }
+
+    if let Some(index) = new_fn.params.iter().skip(old_fn.params.len()).position(|p| p.required) {
+        return Some(ChangeReason::AddedRequiredParam {
+            index: old_fn.params.len() + index,
+        });
+    }
+
+    if !is_assignable(&old_fn.ret_ty, &new_fn.ret_ty) {
+        return Some(ChangeReason::WidenedReturn);
+    }
+
+    None
+}
+
+/// Returns call signatures of `ty`, including overloads.
+fn signatures(ty: &Type) -> Vec<&Function> {
+    match ty.normalize() {
+        Type::Function(f) => vec![f],
+        Type::Union(Union { types, .. }) | Type::Intersection(Intersection { types, .. }) => types.iter().flat_map(signatures).collect(),
+        _ => vec![],
+    }
+}
+
+fn print_var(name: &str, ty: &Type) -> String {
+    let ty = ALLOW_DEEP_CLONE.set(&(), || ty.normalize().clone());
+
+    let type_ann = match ty {
+        Type::ClassDef(def) => RTsType::TsTypeLit(RTsTypeLit {
+            node_id: NodeId::invalid(),
+            span: def.span,
+            members: def.into_inner().body.into_iter().map(RTsTypeElement::from).collect(),
+        }),
+        ty => RTsType::from(ty),
+    };
+
+    print(Decl::Var(Box::new(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Const,
+        declare: true,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(BindingIdent {
+                id: Ident::new(name.into(), DUMMY_SP),
+                type_ann: Some(Box::new(TsTypeAnn {
+                    span: DUMMY_SP,
+                    type_ann: Box::new(type_ann.into_orig()),
+                })),
+            }),
+            init: None,
+            definite: false,
+        }],
+    })))
+}
+
+fn print_type_decl(name: &str, ty: &Type) -> String {
+    let ty = ALLOW_DEEP_CLONE.set(&(), || ty.normalize().clone());
+
+    let decl = match ty {
+        Type::Interface(i) => Decl::TsInterface(Box::new(TsInterfaceDecl {
+            span: DUMMY_SP,
+            id: Ident::new(name.into(), DUMMY_SP),
+            declare: false,
+            type_params: i.type_params.map(|v| Box::new(RTsTypeParamDecl::from(*v).into_orig())),
+            extends: Default::default(),
+            body: TsInterfaceBody {
+                span: DUMMY_SP,
+                body: i.body.into_iter().map(print_type_element).collect(),
+            },
+        })),
+        Type::Alias(a) => Decl::TsTypeAlias(Box::new(TsTypeAliasDecl {
+            span: DUMMY_SP,
+            declare: false,
+            id: Ident::new(name.into(), DUMMY_SP),
+            type_params: a.type_params.map(|v| Box::new(RTsTypeParamDecl::from(*v).into_orig())),
+            type_ann: Box::new(RTsType::from(*a.ty).into_orig()),
+        })),
+        Type::ClassDef(def) => {
+            let def = def.into_inner();
+            Decl::TsTypeAlias(Box::new(TsTypeAliasDecl {
+                span: DUMMY_SP,
+                declare: false,
+                id: Ident::new(name.into(), DUMMY_SP),
+                type_params: def.type_params.map(|v| Box::new(RTsTypeParamDecl::from(*v).into_orig())),
+                type_ann: Box::new(
+                    RTsType::TsTypeLit(RTsTypeLit {
+                        node_id: NodeId::invalid(),
+                        span: def.span,
+                        members: def.body.into_iter().map(RTsTypeElement::from).collect(),
+                    })
+                    .into_orig(),
+                ),
+            }))
+        }
+        ty => Decl::TsTypeAlias(Box::new(TsTypeAliasDecl {
+            span: DUMMY_SP,
+            declare: false,
+            id: Ident::new(name.into(), DUMMY_SP),
+            type_params: None,
+            type_ann: Box::new(RTsType::from(ty).into_orig()),
+        })),
+    };
+
+    print(decl)
+}
+
+fn print_type_element(e: TypeElement) -> TsTypeElement {
+    RTsTypeElement::from(e).into_orig()
+}
+
+fn print(decl: Decl) -> String {
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { span: DUMMY_SP, decl }))],
+        shebang: None,
+    };
+
+    let mut buf = vec![];
+    {
+        let cm = Lrc::new(SourceMap::default());
+        let mut emitter = Emitter {
+            cfg: swc_ecma_codegen::Config {
+                minify: false,
+                ..Default::default()
+            },
+            cm: cm.clone(),
+            comments: None,
+            wr: Box::new(JsWriter::new(cm, "\n", &mut buf, None)),
+        };
+
+        emitter.emit_module(&module).expect("failed to print api report");
+    }
+
+    String::from_utf8(buf).expect("codegen should emit utf8").trim_end().to_string()
+}
@@ -0,0 +1,641 @@
+//! Build mode for projects using `references` of `tsconfig.json`.
+//!
+//! Projects are checked in the topological order of the reference graph, and
+//! projects which don't depend on each other are checked in parallel. A
+//! downstream project consumes `.d.ts` files emitted by the upstream projects
+//! instead of analyzing their sources again.
+//!
+//! A project is skipped if the hash of its inputs is equal to the one stored
+//! in the build info file of the last successful build.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Context, Error};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use rayon::prelude::*;
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ExtraCompilerOptions, ModuleConfig, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use swc_common::{errors::Handler, FileName, SourceMap, GLOBALS};
+use swc_ecma_ast::EsVersion;
+use swc_ecma_loader::resolve::Resolve;
+use tracing::info;
+use tsconfig::{CompilerOptions, References, Target, TsConfig};
+
+use crate::{
+    incremental::{hash, print_dts},
+    loader::{DefaultFileLoader, ModuleLoader},
+    Checker,
+};
+
+/// Extensions of files which are included in a project.
+static SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx"];
+
+/// Extensions of files which are included in a project with `allowJs`.
+static JS_SOURCE_EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "cjs"];
+
+/// A project in the reference graph.
+#[derive(Debug)]
+pub struct Project {
+    /// Path to `tsconfig.json`.
+    pub config_path: PathBuf,
+    /// Directory of `tsconfig.json`.
+    pub dir: PathBuf,
+    /// `rootDir` of the project. Emitted files mirror the layout of this
+    /// directory.
+    pub root_dir: PathBuf,
+    /// Directory for `.d.ts` files.
+    pub declaration_dir: PathBuf,
+    pub compiler_options: CompilerOptions,
+    /// Options which are not supported by [CompilerOptions].
+    pub extra_options: ExtraCompilerOptions,
+    pub files: Vec<PathBuf>,
+    /// Indexes of referenced projects.
+    pub references: Vec<usize>,
+}
+
+impl Project {
+    fn build_info_path(&self) -> PathBuf {
+        let stem = self
+            .config_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        self.declaration_dir.join(format!("{}.stcbuildinfo", stem))
+    }
+
+    /// Returns the path of the emitted `.d.ts` file for `file`.
+    fn declaration_path(&self, file: &Path) -> Option<PathBuf> {
+        let relative = file.strip_prefix(&self.root_dir).ok()?;
+        let name = relative.file_stem()?.to_string_lossy();
+
+        Some(self.declaration_dir.join(relative).with_file_name(format!("{}.d.ts", name)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildStatus {
+    /// Inputs are not changed since the last successful build.
+    UpToDate,
+    Built,
+    /// Errors are found, and declarations are not emitted.
+    Failed,
+    /// A referenced project failed.
+    Skipped,
+}
+
+#[derive(Debug)]
+pub struct ProjectResult {
+    pub config_path: PathBuf,
+    pub status: BuildStatus,
+    pub errors: Vec<stc_ts_errors::Error>,
+}
+
+/// Builds a project and all projects referenced by it.
+pub struct Builder {
+    cm: Arc<SourceMap>,
+    handler: Arc<Handler>,
+    /// Sorted in topological order.
+    projects: Vec<Project>,
+}
+
+impl Builder {
+    /// Reads the reference graph starting from `config_path`.
+    pub fn new(cm: Arc<SourceMap>, handler: Arc<Handler>, config_path: &Path) -> Result<Self, Error> {
+        let mut loader = ProjectGraphLoader::default();
+        loader.load(config_path, &mut vec![])?;
+
+        Ok(Self {
+            cm,
+            handler,
+            projects: loader.projects,
+        })
+    }
+
+    pub fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+
+    /// Builds all projects. If `force` is `true`, projects are built even if
+    /// they are up to date.
+    ///
+    /// This should be called with [GLOBALS] set.
+    pub fn build(&self, force: bool) -> Vec<ProjectResult> {
+        // Projects in a level only depend on projects of previous levels.
+        let mut levels: Vec<Vec<usize>> = vec![];
+        let mut depths = vec![0; self.projects.len()];
+        for (idx, project) in self.projects.iter().enumerate() {
+            let depth = project.references.iter().map(|&r| depths[r] + 1).max().unwrap_or(0);
+            depths[idx] = depth;
+
+            if levels.len() <= depth {
+                levels.resize_with(depth + 1, Default::default);
+            }
+            levels[depth].push(idx);
+        }
+
+        let mut input_hashes: Vec<Option<String>> = vec![None; self.projects.len()];
+        let mut statuses: Vec<Option<BuildStatus>> = vec![None; self.projects.len()];
+        let mut results = vec![];
+
+        for level in levels {
+            let level_results = GLOBALS.with(|globals| {
+                level
+                    .par_iter()
+                    .map(|&idx| {
+                        GLOBALS.set(globals, || {
+                            let project = &self.projects[idx];
+
+                            if project
+                                .references
+                                .iter()
+                                .any(|&r| matches!(statuses[r], Some(BuildStatus::Failed | BuildStatus::Skipped)))
+                            {
+                                return (
+                                    idx,
+                                    None,
+                                    ProjectResult {
+                                        config_path: project.config_path.clone(),
+                                        status: BuildStatus::Skipped,
+                                        errors: vec![],
+                                    },
+                                );
+                            }
+
+                            let upstream_hashes = project
+                                .references
+                                .iter()
+                                .map(|&r| input_hashes[r].clone().unwrap_or_default())
+                                .collect::<Vec<_>>();
+
+                            let (input_hash, result) = self.build_project(project, &upstream_hashes, force);
+
+                            (idx, Some(input_hash), result)
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+            for (idx, input_hash, result) in level_results {
+                input_hashes[idx] = input_hash;
+                statuses[idx] = Some(result.status);
+                results.push(result);
+            }
+        }
+
+        results
+    }
+
+    fn build_project(&self, project: &Project, upstream_hashes: &[String], force: bool) -> (String, ProjectResult) {
+        let input_hash = input_hash(project, upstream_hashes);
+        let build_info_path = project.build_info_path();
+
+        if !force {
+            if let Ok(stored) = fs::read_to_string(&build_info_path) {
+                if stored.trim() == input_hash {
+                    info!("Project `{}` is up to date", project.config_path.display());
+
+                    return (
+                        input_hash,
+                        ProjectResult {
+                            config_path: project.config_path.clone(),
+                            status: BuildStatus::UpToDate,
+                            errors: vec![],
+                        },
+                    );
+                }
+            }
+        }
+
+        info!("Building project `{}`", project.config_path.display());
+
+        let env = create_env(&project.compiler_options, project.extra_options);
+
+        let resolver = ProjectResolver {
+            node: NodeResolver::new().allow_js(env.rule().allow_js),
+            upstreams: project
+                .references
+                .iter()
+                .map(|&r| {
+                    let upstream = &self.projects[r];
+                    (upstream.root_dir.clone(), upstream.declaration_dir.clone())
+                })
+                .collect(),
+        };
+
+        let type_roots = project
+            .compiler_options
+            .type_roots
+            .iter()
+            .flatten()
+            .map(|root| project.dir.join(root))
+            .collect();
+
+        let mut checker = Checker::new(
+            self.cm.clone(),
+            self.handler.clone(),
+            env.clone(),
+            None,
+            Box::new(ModuleLoader::new(self.cm.clone(), env, resolver, DefaultFileLoader).with_type_roots(type_roots)),
+        );
+
+        checker.load_typings(&project.dir, None, None);
+
+        let ids = project
+            .files
+            .iter()
+            .map(|file| (file, checker.check(Arc::new(FileName::Real(file.clone())))))
+            .collect::<Vec<_>>();
+
+        let errors = checker.take_errors();
+        if !errors.is_empty() {
+            return (
+                input_hash,
+                ProjectResult {
+                    config_path: project.config_path.clone(),
+                    status: BuildStatus::Failed,
+                    errors,
+                },
+            );
+        }
+
+        let emitted = || -> Result<(), Error> {
+            for (file, id) in ids {
+                if file.to_string_lossy().ends_with(".d.ts") {
+                    continue;
+                }
+
+                let (dts, path) = match (checker.take_dts(id), project.declaration_path(file)) {
+                    (Some(dts), Some(path)) => (dts, path),
+                    _ => continue,
+                };
+
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&path, print_dts(&dts)).with_context(|| format!("failed to write `{}`", path.display()))?;
+            }
+
+            fs::create_dir_all(&project.declaration_dir)?;
+            fs::write(&build_info_path, &input_hash)
+                .with_context(|| format!("failed to write build info to `{}`", build_info_path.display()))?;
+
+            Ok(())
+        }();
+
+        let status = match emitted {
+            Ok(()) => BuildStatus::Built,
+            Err(err) => {
+                self.handler.err(&format!("{:?}", err));
+                BuildStatus::Failed
+            }
+        };
+
+        (
+            input_hash,
+            ProjectResult {
+                config_path: project.config_path.clone(),
+                status,
+                errors: vec![],
+            },
+        )
+    }
+}
+
+/// Hash of the config, source files, and inputs of referenced projects.
+fn input_hash(project: &Project, upstream_hashes: &[String]) -> String {
+    let mut data = vec![];
+
+    data.push(hash(&fs::read(&project.config_path).unwrap_or_default()));
+    for file in &project.files {
+        data.push(format!("{}:{}", file.display(), hash(&fs::read(file).unwrap_or_default())));
+    }
+    data.extend(upstream_hashes.iter().cloned());
+
+    hash(data.join("\n").as_bytes())
+}
+
+#[derive(Default)]
+struct ProjectGraphLoader {
+    projects: Vec<Project>,
+    indexes: HashMap<PathBuf, usize>,
+}
+
+impl ProjectGraphLoader {
+    /// Loads a project after loading projects referenced by it, so that
+    /// `self.projects` is sorted in topological order.
+    fn load(&mut self, config_path: &Path, stack: &mut Vec<PathBuf>) -> Result<usize, Error> {
+        let config_path = fs::canonicalize(config_path).with_context(|| format!("failed to find `{}`", config_path.display()))?;
+        let config_path = if config_path.is_dir() {
+            config_path.join("tsconfig.json")
+        } else {
+            config_path
+        };
+
+        if let Some(&idx) = self.indexes.get(&config_path) {
+            return Ok(idx);
+        }
+
+        if stack.contains(&config_path) {
+            bail!("project references form a cycle: `{}`", config_path.display())
+        }
+
+        let config = TsConfig::parse_file(&config_path).with_context(|| format!("failed to parse `{}`", config_path.display()))?;
+        let dir = config_path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        stack.push(config_path.clone());
+        let mut references = vec![];
+        if let Some(References::References(refs)) = &config.references {
+            for r in refs {
+                let idx = self.load(&dir.join(&r.path), stack)?;
+
+                if !self.projects[idx].compiler_options.composite.unwrap_or_default() {
+                    bail!(
+                        "referenced project `{}` must have setting \"composite\": true",
+                        self.projects[idx].config_path.display()
+                    )
+                }
+
+                references.push(idx);
+            }
+        }
+        stack.pop();
+
+        let compiler_options = config.compiler_options.clone().unwrap_or_default();
+
+        let root_dir = compiler_options.root_dir.as_ref().map_or_else(|| dir.clone(), |d| dir.join(d));
+        let declaration_dir = compiler_options
+            .declaration_dir
+            .as_ref()
+            .or(compiler_options.out_dir.as_ref())
+            .map_or_else(|| root_dir.clone(), |d| dir.join(d));
+
+        let files = match &config.files {
+            Some(files) => files.iter().map(|f| dir.join(f)).collect(),
+            None => {
+                let include = config.include.clone().unwrap_or_else(|| vec!["**/*".into()]);
+                let exclude = config.exclude.clone().unwrap_or_default();
+
+                let mut collector = FileCollector {
+                    dir: &dir,
+                    include: build_glob_set(&include).with_context(|| format!("invalid `include` in `{}`", config_path.display()))?,
+                    exclude: build_glob_set(&exclude).with_context(|| format!("invalid `exclude` in `{}`", config_path.display()))?,
+                    out_dir: compiler_options
+                        .declaration_dir
+                        .as_ref()
+                        .or(compiler_options.out_dir.as_ref())
+                        .map(|_| declaration_dir.as_path()),
+                    root_dir: &root_dir,
+                    declaration_dir: &declaration_dir,
+                    allow_js: compiler_options.allow_js.unwrap_or_default(),
+                    files: vec![],
+                };
+                for pattern in &include {
+                    collector.collect(&dir.join(pattern_base(pattern)));
+                }
+
+                let mut files = collector.files;
+                files.sort();
+                files.dedup();
+                files
+            }
+        };
+
+        let idx = self.projects.len();
+        self.projects.push(Project {
+            config_path: config_path.clone(),
+            dir,
+            root_dir,
+            declaration_dir,
+            compiler_options,
+            extra_options: ExtraCompilerOptions::parse_file(&config_path),
+            files,
+            references,
+        });
+        self.indexes.insert(config_path, idx);
+
+        Ok(idx)
+    }
+}
+
+/// Returns the part of a glob pattern before the first wildcard.
+fn pattern_base(pattern: &str) -> PathBuf {
+    pattern.split('/').take_while(|c| !c.contains(['*', '?'])).collect()
+}
+
+/// Builds a matcher for `include` or `exclude` of `tsconfig.json`.
+///
+/// Like `tsc`, a pattern also matches all files in the directories it matches.
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, Error> {
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+
+        for pattern in [pattern.to_string(), format!("{}/**", pattern)] {
+            builder.add(GlobBuilder::new(&pattern).literal_separator(true).build()?);
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+struct FileCollector<'a> {
+    /// Directory of `tsconfig.json`. Patterns are relative to this.
+    dir: &'a Path,
+    include: GlobSet,
+    exclude: GlobSet,
+    /// Emitted files are not inputs.
+    out_dir: Option<&'a Path>,
+    root_dir: &'a Path,
+    /// `.d.ts` files emitted next to the sources are not inputs either.
+    declaration_dir: &'a Path,
+    allow_js: bool,
+    files: Vec<PathBuf>,
+}
+
+impl FileCollector<'_> {
+    fn collect(&mut self, path: &Path) {
+        if self.out_dir.map_or(false, |out_dir| path.starts_with(out_dir)) {
+            return;
+        }
+
+        // Files outside of the directory can be included only by `include`.
+        let relative = path.strip_prefix(self.dir).ok();
+        if relative.map_or(false, |relative| self.exclude.is_match(relative)) {
+            return;
+        }
+
+        if path.is_file() {
+            let name = path.to_string_lossy();
+            let is_source = |exts: &[&str]| exts.iter().any(|ext| name.ends_with(&format!(".{}", ext)));
+            let is_included = relative.map_or(true, |relative| self.include.is_match(relative));
+
+            if is_included
+                && !self.is_emitted_declaration(path)
+                && (is_source(SOURCE_EXTENSIONS) || (self.allow_js && is_source(JS_SOURCE_EXTENSIONS)))
+            {
+                self.files.push(path.to_path_buf());
+            }
+            return;
+        }
+
+        if path.file_name().map_or(false, |name| name == "node_modules") {
+            return;
+        }
+
+        let entries = match fs::read_dir(path) {
+            Ok(v) => v,
+            Err(..) => return,
+        };
+        for entry in entries.flatten() {
+            self.collect(&entry.path());
+        }
+    }
+
+    /// Returns true if `path` is a `.d.ts` file emitted for a source file of
+    /// this project.
+    fn is_emitted_declaration(&self, path: &Path) -> bool {
+        let relative = match path.strip_prefix(self.declaration_dir) {
+            Ok(v) => v,
+            Err(..) => return false,
+        };
+        let name = match relative.file_name().and_then(|name| name.to_str()?.strip_suffix(".d.ts")) {
+            Some(v) => v,
+            None => return false,
+        };
+        let source = self.root_dir.join(relative);
+
+        SOURCE_EXTENSIONS
+            .iter()
+            .chain(if self.allow_js { JS_SOURCE_EXTENSIONS } else { &[] })
+            .any(|ext| source.with_file_name(format!("{}.{}", name, ext)).is_file())
+    }
+}
+
+fn create_env(options: &CompilerOptions, extra_options: ExtraCompilerOptions) -> Env {
+    let target = options.target.clone().map_or_else(EsVersion::latest, es_version);
+
+    let mut libs = match &options.lib {
+        Some(libs) => libs.iter().flat_map(|lib| Lib::from(lib).with_deps()).collect::<Vec<_>>(),
+        None => Lib::load(&format!("{:?}", target).to_ascii_lowercase()),
+    };
+    libs.sort();
+    libs.dedup();
+
+    Env::simple(
+        Rule::from(options).with_extra_options(extra_options),
+        target,
+        options.module.clone().map_or_else(ModuleConfig::default, ModuleConfig::from),
+        &libs,
+    )
+}
+
+fn es_version(target: Target) -> EsVersion {
+    match target {
+        Target::Es3 => EsVersion::Es3,
+        Target::Es5 => EsVersion::Es5,
+        Target::Es2015 | Target::Es6 => EsVersion::Es2015,
+        Target::Es2016 | Target::Es7 => EsVersion::Es2016,
+        Target::Es2017 => EsVersion::Es2017,
+        Target::Es2018 => EsVersion::Es2018,
+        Target::Es2019 => EsVersion::Es2019,
+        Target::Es2020 => EsVersion::Es2020,
+        Target::EsNext => EsVersion::EsNext,
+        Target::Other(s) => match &*s.to_ascii_lowercase() {
+            "es2021" => EsVersion::Es2021,
+            "es2022" => EsVersion::Es2022,
+            _ => EsVersion::latest(),
+        },
+    }
+}
+
+/// Resolves imports of source files of upstream projects to the emitted
+/// `.d.ts` files.
+struct ProjectResolver {
+    node: NodeResolver,
+    /// `(rootDir, declarationDir)` of referenced projects.
+    upstreams: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Resolve for ProjectResolver {
+    fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error> {
+        let resolved = self.node.resolve(base, module_specifier)?;
+
+        let path = match &resolved {
+            FileName::Real(path) if !path.to_string_lossy().ends_with(".d.ts") => path,
+            _ => return Ok(resolved),
+        };
+
+        for (root_dir, declaration_dir) in &self.upstreams {
+            if let Ok(relative) = path.strip_prefix(root_dir) {
+                let name = match relative.file_stem() {
+                    Some(v) => v.to_string_lossy(),
+                    None => continue,
+                };
+                let dts = declaration_dir.join(relative).with_file_name(format!("{}.d.ts", name));
+
+                if dts.is_file() {
+                    return Ok(FileName::Real(dts));
+                }
+            }
+        }
+
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn create_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join("stc-build-resolver").join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("upstream").join("src")).unwrap();
+        fs::create_dir_all(dir.join("upstream").join("dist")).unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    fn resolve(dir: &Path, module_specifier: &str) -> PathBuf {
+        let resolver = ProjectResolver {
+            node: NodeResolver::new(),
+            upstreams: vec![(dir.join("upstream").join("src"), dir.join("upstream").join("dist"))],
+        };
+
+        match resolver.resolve(&FileName::Real(dir.join("index.ts")), module_specifier).unwrap() {
+            FileName::Real(path) => path.strip_prefix(dir).unwrap().to_path_buf(),
+            name => panic!("unexpected file name: {:?}", name),
+        }
+    }
+
+    #[test]
+    fn resolver_uses_emitted_declaration() {
+        let dir = create_dir("emitted");
+        fs::write(dir.join("upstream/src/a.ts"), "export const a = 1;").unwrap();
+        fs::write(dir.join("upstream/dist/a.d.ts"), "export declare const a = 1;").unwrap();
+
+        assert_eq!(resolve(&dir, "./upstream/src/a"), Path::new("upstream/dist/a.d.ts"));
+    }
+
+    #[test]
+    fn resolver_falls_back_to_source() {
+        let dir = create_dir("not_emitted");
+        fs::write(dir.join("upstream/src/a.ts"), "export const a = 1;").unwrap();
+        fs::write(dir.join("b.ts"), "export const b = 1;").unwrap();
+
+        // The upstream project is not built yet.
+        assert_eq!(resolve(&dir, "./upstream/src/a"), Path::new("upstream/src/a.ts"));
+        // Not a file of an upstream project.
+        assert_eq!(resolve(&dir, "./b"), Path::new("b.ts"));
+    }
+}
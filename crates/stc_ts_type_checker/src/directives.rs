@@ -0,0 +1,193 @@
+//! `@ts-ignore`, `@ts-expect-error`, `@ts-nocheck` and `@ts-check` comments.
+
+use std::sync::Arc;
+
+use fxhash::FxHashMap;
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_utils::StcComments;
+use swc_common::{
+    comments::{Comment, CommentKind},
+    BytePos, FileName, SourceFile, Span, Spanned,
+};
+
+use crate::{loader::is_js_file, Checker};
+
+/// Comment directives of a file.
+#[derive(Debug, Default)]
+struct FileDirectives {
+    /// `true` if the file starts with `@ts-nocheck`, or if it's a JavaScript
+    /// file which is not checked.
+    no_check: bool,
+    is_js: bool,
+    /// Directives by the (0-based) line.
+    lines: FxHashMap<usize, Directive>,
+}
+
+#[derive(Debug)]
+struct Directive {
+    span: Span,
+    expect_error: bool,
+    used: bool,
+}
+
+impl Checker {
+    /// Removes errors suppressed by comment directives of `files`, and
+    /// reports unused `@ts-expect-error` directives.
+    pub(crate) fn apply_comment_directives(&self, files: &[Arc<FileName>], errors: Vec<Error>) -> Vec<Error> {
+        let mut directives = files
+            .iter()
+            .filter_map(|file| Some(((**file).clone(), self.directives_of(file)?)))
+            .collect::<FxHashMap<_, _>>();
+
+        if directives.is_empty() {
+            return errors;
+        }
+
+        let mut result = vec![];
+        for err in ErrorKind::flatten(errors) {
+            let span = err.span();
+            if !span.is_dummy() {
+                let fm = self.cm.lookup_char_pos(span.lo).file;
+                if let Some(d) = directives.get_mut(&fm.name) {
+                    if d.suppresses(&fm, span.lo, &err) {
+                        continue;
+                    }
+                }
+            }
+
+            result.push(err);
+        }
+
+        for d in directives.into_values().filter(|d| !d.no_check) {
+            for directive in d.lines.into_values() {
+                if directive.expect_error && !directive.used {
+                    result.push(ErrorKind::UnusedTsExpectError { span: directive.span }.into());
+                }
+            }
+        }
+
+        result
+    }
+
+    fn directives_of(&self, file: &Arc<FileName>) -> Option<FileDirectives> {
+        let records = self.module_loader.load_module(file, false).ok()?;
+        // `SourceMap::get_source_file` returns the first file registered with the
+        // name, which is stale if the file was parsed again.
+        let fm = self.cm.lookup_char_pos(records.entry.ast.span.lo).file;
+        let first_stmt = records.entry.ast.body.first().map_or(fm.end_pos, |item| item.span().lo);
+
+        let mut d = FileDirectives {
+            is_js: is_js_file(file),
+            ..Default::default()
+        };
+        // JavaScript files are checked only with `checkJs` or `// @ts-check`.
+        let mut checked = !d.is_js || self.env().rule().check_js;
+        for_each_comment(&records.comments, fm.start_pos, fm.end_pos, |c| {
+            if c.span.hi <= first_stmt {
+                let text = c.text.trim();
+                if text.starts_with("@ts-nocheck") {
+                    d.no_check = true;
+                } else if text.starts_with("@ts-check") {
+                    checked = true;
+                }
+            }
+
+            let expect_error = match directive_kind(c) {
+                Some(v) => v,
+                None => return,
+            };
+
+            if let Some(line) = fm.lookup_line(c.span.hi) {
+                d.lines.insert(
+                    line,
+                    Directive {
+                        span: c.span,
+                        expect_error,
+                        used: false,
+                    },
+                );
+            }
+        });
+
+        d.no_check |= !checked;
+
+        if !d.no_check && !d.is_js && d.lines.is_empty() {
+            return None;
+        }
+
+        Some(d)
+    }
+}
+
+impl FileDirectives {
+    /// Returns `true` if an error at `pos` should not be reported.
+    ///
+    /// Like `tsc`, blank lines and line comments between a directive and the
+    /// error are ignored.
+    fn suppresses(&mut self, fm: &SourceFile, pos: BytePos, err: &ErrorKind) -> bool {
+        if self.no_check {
+            return true;
+        }
+
+        // Missing type annotations are not errors in JavaScript files.
+        if self.is_js && matches!(err, ErrorKind::ImplicitAny { .. } | ErrorKind::ImplicitReturnType { .. }) {
+            return true;
+        }
+
+        let mut line = match fm.lookup_line(pos) {
+            Some(v) => v,
+            None => return false,
+        };
+
+        while line > 0 {
+            line -= 1;
+
+            if let Some(directive) = self.lines.get_mut(&line) {
+                directive.used = true;
+                return true;
+            }
+
+            let text = fm.get_line(line).unwrap_or_default();
+            let text = text.trim();
+            if !text.is_empty() && !text.starts_with("//") {
+                return false;
+            }
+        }
+
+        false
+    }
+}
+
+/// Returns `Some(true)` for `@ts-expect-error` and `Some(false)` for
+/// `@ts-ignore`.
+fn directive_kind(c: &Comment) -> Option<bool> {
+    let text = match c.kind {
+        CommentKind::Line => c.text.strip_prefix('/').unwrap_or(&c.text),
+        // Only the last line of a block comment is checked.
+        CommentKind::Block => c.text.lines().last()?.trim_start_matches(|c| c == '/' || c == '*'),
+    };
+    let text = text.trim_start();
+
+    if text.starts_with("@ts-expect-error") {
+        Some(true)
+    } else if text.starts_with("@ts-ignore") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn for_each_comment<F>(comments: &StcComments, start: BytePos, end: BytePos, mut op: F)
+where
+    F: FnMut(&Comment),
+{
+    for map in [&comments.leading, &comments.trailing] {
+        for entry in map.iter() {
+            for c in entry.value() {
+                if start <= c.span.lo && c.span.lo < end {
+                    op(c);
+                }
+            }
+        }
+    }
+}
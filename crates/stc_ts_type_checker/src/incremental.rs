@@ -0,0 +1,161 @@
+//! On-disk cache of analyzed modules, used to skip analysis of unchanged files.
+//!
+//! Types can't be stored as-is because they contain spans and syntax contexts
+//! which are only valid for the process which created them. Instead, the
+//! `.d.ts` output of a module is stored, and a cache hit is analyzed as a
+//! `.d.ts` file. Diagnostics are stored with offsets relative to the start of
+//! the file.
+
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::Type;
+use stc_utils::disk_cache::write_atomic;
+use swc_common::{sync::Lrc, BytePos, SourceFile, SourceMap, Span, Spanned};
+use swc_ecma_ast::Module;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+use tracing::warn;
+
+use crate::api::ApiReport;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedModule {
+    /// Hash of the content of the file and the public api of its dependencies.
+    pub key: String,
+    pub dts: String,
+    pub diagnostics: Vec<CachedDiagnostic>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedDiagnostic {
+    pub code: usize,
+    /// Relative to the start of the file.
+    pub lo: u32,
+    /// Relative to the start of the file.
+    pub hi: u32,
+    pub message: String,
+}
+
+/// A directory which stores analysis results of modules.
+#[derive(Debug, Clone)]
+pub struct ModuleCache {
+    dir: PathBuf,
+}
+
+impl ModuleCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn entry_path(&self, path: &Path) -> PathBuf {
+        self.dir.join(format!("{}.rmp", hash(path.to_string_lossy().as_bytes())))
+    }
+
+    pub(crate) fn load(&self, path: &Path) -> Option<CachedModule> {
+        let entry_path = self.entry_path(path);
+        if !entry_path.is_file() {
+            return None;
+        }
+
+        let res = || -> Result<CachedModule, Box<dyn Error>> {
+            let data = fs::read(&entry_path)?;
+
+            Ok(rmp_serde::decode::from_slice(&data)?)
+        }();
+
+        match res {
+            Ok(v) => Some(v),
+            Err(err) => {
+                warn!("Failed to load module cache of `{}`: {:?}", path.display(), err);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn store(&self, path: &Path, module: &CachedModule) {
+        let entry_path = self.entry_path(path);
+
+        let res = || -> Result<(), Box<dyn Error>> {
+            let data = rmp_serde::encode::to_vec(module)?;
+
+            write_atomic(&entry_path, &data)?;
+
+            Ok(())
+        }();
+
+        if let Err(err) = res {
+            warn!("Failed to store module cache of `{}`: {:?}", path.display(), err);
+        }
+    }
+}
+
+pub(crate) fn hash(data: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Hash of the public api of a module. This is stable across runs.
+pub(crate) fn interface_hash(module: &Type) -> String {
+    match module.normalize() {
+        Type::Module(m) => hash(ApiReport::new(&m.exports).to_text().as_bytes()),
+        _ => String::new(),
+    }
+}
+
+/// Returns [None] if an error is not in `fm`.
+pub(crate) fn to_cached_diagnostics(fm: &SourceFile, errors: &[stc_ts_errors::Error]) -> Option<Vec<CachedDiagnostic>> {
+    errors
+        .iter()
+        .map(|err| {
+            let span = err.span();
+            if span.lo < fm.start_pos || span.hi > fm.end_pos {
+                return None;
+            }
+
+            Some(CachedDiagnostic {
+                code: err.code(),
+                lo: (span.lo - fm.start_pos).0,
+                hi: (span.hi - fm.start_pos).0,
+                message: format!("{:#?}", err),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn from_cached_diagnostics(fm: &SourceFile, diagnostics: Vec<CachedDiagnostic>) -> Vec<stc_ts_errors::Error> {
+    diagnostics
+        .into_iter()
+        .map(|d| {
+            ErrorKind::Restored {
+                span: Span::new(fm.start_pos + BytePos(d.lo), fm.start_pos + BytePos(d.hi), Default::default()),
+                code: d.code,
+                message: d.message.into_boxed_str(),
+            }
+            .into()
+        })
+        .collect()
+}
+
+pub(crate) fn print_dts(module: &Module) -> String {
+    let mut buf = vec![];
+    {
+        let cm = Lrc::new(SourceMap::default());
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            comments: None,
+            wr: Box::new(JsWriter::new(cm, "\n", &mut buf, None)),
+        };
+
+        emitter.emit_module(module).expect("failed to print .d.ts for module cache");
+    }
+
+    String::from_utf8(buf).expect("codegen should emit utf8")
+}
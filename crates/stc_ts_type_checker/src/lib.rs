@@ -4,23 +4,41 @@ use std::{mem::take, sync::Arc, time::Instant};
 
 use dashmap::{DashMap, DashSet, SharedValue};
 use fxhash::{FxBuildHasher, FxHashMap};
+use indexmap::IndexMap;
 use loader::LoadModule;
 use once_cell::sync::OnceCell;
 use parking_lot::{Mutex, RwLock};
 use rnode::{NodeIdGenerator, RNode, VisitWith};
 use stc_ts_ast_rnode::{RModule, RStr, RTsModuleName};
+use stc_ts_builtin_types::Lib;
 use stc_ts_dts::{apply_mutations, cleanup_module_for_dts};
-use stc_ts_env::Env;
-use stc_ts_errors::{debug::debugger::Debugger, Error};
-use stc_ts_file_analyzer::{analyzer::Analyzer, loader::Load, validator::ValidateWith, ModuleTypeData, VResult};
-use stc_ts_storage::{ErrorStore, File, Group, Single};
+use stc_ts_env::{BuiltIn, Env};
+use stc_ts_errors::{debug::debugger::Debugger, Error, ErrorKind};
+use stc_ts_file_analyzer::{analyzer::Analyzer, env::BuiltInGen, loader::Load, validator::ValidateWith, ModuleTypeData, VResult};
+use stc_ts_storage::{ErrorStore, File, Group, Info, Single};
 use stc_ts_types::{ModuleId, Type};
-use stc_utils::{cache::Freeze, early_error};
+use stc_ts_utils::{imports::ImportRef, StcComments};
+use stc_utils::{cache::Freeze, disk_cache::STC_VERSION, early_error};
 use swc_atoms::JsWord;
-use swc_common::{errors::Handler, FileName, SourceMap, Spanned, DUMMY_SP};
-use swc_ecma_ast::Module;
+use swc_common::{errors::Handler, input::SourceFileInput, BytePos, FileName, SourceFile, SourceMap, Spanned, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::{Decl, Module, ModuleItem, Stmt, TsModuleName};
+use swc_ecma_parser::{Parser, Syntax, TsConfig};
+use swc_ecma_visit::VisitMutWith;
 use tracing::{info, warn};
 
+use self::{
+    api::ApiReport,
+    incremental::{from_cached_diagnostics, hash, interface_hash, print_dts, to_cached_diagnostics, CachedModule, ModuleCache},
+    loader::{
+        analyzer::{find_lib_references, find_modules_and_deps},
+        ModuleRecord,
+    },
+};
+
+pub mod api;
+pub mod build;
+mod directives;
+pub mod incremental;
 pub mod loader;
 mod typings;
 
@@ -33,6 +51,14 @@ pub struct Checker {
 
     declared_modules: DashMap<String, ModuleId, FxBuildHasher>,
 
+    /// Declarations of ambient modules, keyed by the declared module and the
+    /// location of the declaration.
+    module_declarations: DashMap<ModuleId, IndexMap<DeclarationKey, Type>, FxBuildHasher>,
+
+    /// Module augmentations, keyed by the augmented module and the location of
+    /// the augmentation.
+    augmentations: DashMap<ModuleId, IndexMap<DeclarationKey, Type>, FxBuildHasher>,
+
     /// Information required to generate `.d.ts` files.
     dts_modules: Arc<DashMap<ModuleId, RModule, FxBuildHasher>>,
 
@@ -43,9 +69,28 @@ pub struct Checker {
 
     errors: Mutex<Vec<Error>>,
 
-    env: Env,
+    /// Files analyzed since the last call to [Checker::take_errors].
+    checked_files: Mutex<Vec<Arc<FileName>>>,
+
+    /// Files already checked by [Checker::analyze_global_declarations].
+    scanned_for_globals: DashSet<Arc<FileName>, FxBuildHasher>,
+
+    /// `/// <reference lib="..." />` and `/// <reference no-default-lib="true"
+    /// />` directives of each file.
+    lib_references: DashMap<Arc<FileName>, Vec<ImportRef>, FxBuildHasher>,
+
+    /// Libraries of the environment passed to [Checker::new], used unless a
+    /// file contains `/// <reference no-default-lib="true" />`.
+    default_libs: Vec<Lib>,
+
+    /// Replaced if a file contains `/// <reference lib="..." />` or
+    /// `/// <reference no-default-lib="true" />`.
+    env: RwLock<Env>,
 
     debugger: Option<Debugger>,
+
+    /// On-disk cache of analysis results.
+    cache: Option<ModuleCache>,
 }
 
 impl Checker {
@@ -56,26 +101,53 @@ impl Checker {
         debugger: Option<Debugger>,
         module_loader: Box<dyn LoadModule>,
     ) -> Self {
+        let mut default_libs = env.builtin().libs().to_vec();
+        default_libs.sort();
+        default_libs.dedup();
+
         Checker {
-            env,
+            env: RwLock::new(env),
             cm,
             handler,
             module_types: Default::default(),
             dts_modules: Default::default(),
             started: Default::default(),
             errors: Default::default(),
+            checked_files: Default::default(),
+            scanned_for_globals: Default::default(),
+            lib_references: Default::default(),
+            default_libs,
             debugger,
             declared_modules: Default::default(),
+            module_declarations: Default::default(),
+            augmentations: Default::default(),
             module_loader,
+            cache: None,
         }
     }
+
+    /// Stores analysis results of modules to `cache`, and reuses them if a
+    /// file and the public api of its dependencies are not changed.
+    ///
+    /// Modules in a circular import group are always analyzed.
+    pub fn set_module_cache(&mut self, cache: ModuleCache) {
+        self.cache = Some(cache);
+    }
 }
 
 impl Checker {
+    fn env(&self) -> Env {
+        self.env.read().clone()
+    }
+
     /// Get type information of a module.
     pub fn get_types(&self, id: ModuleId) -> Option<Type> {
-        let lock = self.module_types.read();
-        lock.get(&id).and_then(|v| v.get().cloned())
+        let ty = {
+            let lock = self.module_types.read();
+            lock.get(&id).and_then(|v| v.get().cloned())?
+        };
+
+        Some(self.apply_augmentations(id, ty))
     }
 
     /// Removes dts module from `self` and return it.
@@ -87,6 +159,79 @@ impl Checker {
         &self.module_loader
     }
 
+    /// Expands references to named types in `ty`, which is a type exported from
+    /// `path`.
+    ///
+    /// Exported types may reference types which are only visible in the module
+    /// declaring them, so they should be expanded using this method before
+    /// being passed to [Checker::is_assignable].
+    pub fn expand_exported_type(&self, path: &Arc<FileName>, ty: &Type) -> Type {
+        let records = match self.module_loader.load_module(path, false) {
+            Ok(v) => v,
+            Err(err) => {
+                warn!("Failed to load `{}`: {:?}", path, err);
+                return ty.clone();
+            }
+        };
+        let module_id = records.entry.id;
+
+        let module_ty = self.get_types(module_id);
+        let exports = match module_ty.as_ref().map(Type::normalize) {
+            Some(Type::Module(m)) => (*m.exports).clone(),
+            _ => return ty.clone(),
+        };
+
+        let mut storage = Single {
+            parent: None,
+            id: module_id,
+            top_level_ctxt: records.entry.top_level_ctxt,
+            path: path.clone(),
+            is_dts: records.entry.is_dts,
+            info: Info {
+                exports,
+                ..Default::default()
+            },
+        };
+
+        let mut a = Analyzer::root(
+            self.env(),
+            self.cm.clone(),
+            Default::default(),
+            Box::new(&mut storage),
+            self,
+            self.debugger.clone(),
+        );
+
+        a.expand_fully(ty.span(), ty)
+    }
+
+    /// Returns `true` if `right` is assignable to `left`.
+    ///
+    /// Both types should be created by this checker, and references to types
+    /// which are not global should be expanded using
+    /// [Checker::expand_exported_type].
+    pub fn is_assignable(&self, left: &Type, right: &Type) -> bool {
+        let mut storage = Single {
+            parent: None,
+            id: ModuleId::builtin(),
+            top_level_ctxt: SyntaxContext::empty(),
+            path: Arc::new(FileName::Anon),
+            is_dts: false,
+            info: Default::default(),
+        };
+
+        let mut a = Analyzer::root(
+            self.env(),
+            self.cm.clone(),
+            Default::default(),
+            Box::new(&mut storage),
+            self,
+            self.debugger.clone(),
+        );
+
+        a.is_assignable(right.span(), left, right)
+    }
+
     /// After calling this method, you can get errors using `.take_errors()`
     pub fn check(&self, entry: Arc<FileName>) -> ModuleId {
         let start = Instant::now();
@@ -98,6 +243,16 @@ impl Checker {
 
         let start = Instant::now();
 
+        // Results of previous checks depend on the previous libraries.
+        let stale = if self.apply_lib_references() {
+            self.drop_analysis_results()
+        } else {
+            vec![]
+        };
+        self.analyze_global_declarations(&entry);
+        for file in stale {
+            self.analyze_module(None, file);
+        }
         self.analyze_module(None, entry.clone());
 
         let end = Instant::now();
@@ -106,8 +261,225 @@ impl Checker {
         modules.entry.id
     }
 
+    /// Errors suppressed by `@ts-ignore`, `@ts-expect-error` or `@ts-nocheck`
+    /// comments are not returned.
     pub fn take_errors(&mut self) -> Vec<Error> {
-        take(self.errors.get_mut())
+        let errors = take(self.errors.get_mut());
+        let files = take(self.checked_files.get_mut());
+
+        self.apply_comment_directives(&files, errors)
+    }
+
+    /// Drops cached results of `files` and all modules depending on them, so
+    /// the next call to [Checker::check] analyzes them again.
+    ///
+    /// Global declarations, ambient modules and module augmentations are
+    /// visible to all modules, so all modules are invalidated if a file
+    /// declaring them is changed.
+    ///
+    /// Returns all invalidated files. Errors of those files are reported again
+    /// by the next [Checker::check].
+    pub fn invalidate(&mut self, files: &[Arc<FileName>]) -> Vec<Arc<FileName>> {
+        let all_files = self.module_loader.files();
+
+        // The previous version of a file is checked before invalidating it, and the
+        // new version is checked after it.
+        let mut is_global = files.iter().any(|file| self.declares_for_other_modules(file));
+        let mut invalidated = self.invalidate_files(files);
+        is_global |= files.iter().any(|file| self.declares_for_other_modules(file));
+
+        if is_global {
+            self.env().clear_global_declarations();
+            invalidated.extend(self.invalidate_files(&all_files));
+        }
+
+        invalidated.sort();
+        invalidated.dedup();
+
+        invalidated
+    }
+
+    fn invalidate_files(&mut self, files: &[Arc<FileName>]) -> Vec<Arc<FileName>> {
+        let mut invalidated = vec![];
+
+        let module_types = self.module_types.get_mut();
+        for file in files {
+            for (id, path) in self.module_loader.invalidate(file) {
+                module_types.remove(&id);
+                self.dts_modules.remove(&id);
+                self.started.remove(&id);
+                self.scanned_for_globals.remove(&path);
+                self.lib_references.remove(&path);
+
+                for mut augmentations in self.augmentations.iter_mut() {
+                    augmentations.retain(|(file, _), _| *file != path);
+                }
+
+                for mut declarations in self.module_declarations.iter_mut() {
+                    let len = declarations.len();
+                    declarations.retain(|(file, _), _| *file != path);
+                    if declarations.len() == len {
+                        continue;
+                    }
+
+                    let module_id = *declarations.key();
+                    match merge_declarations(&declarations) {
+                        Some(module) => {
+                            module_types.insert(module_id, Arc::new(OnceCell::from(module)));
+                        }
+                        None => {
+                            module_types.remove(&module_id);
+                            self.declared_modules.retain(|_, id| *id != module_id);
+                        }
+                    }
+                }
+
+                invalidated.push(path);
+            }
+        }
+
+        invalidated
+    }
+
+    /// Returns `true` if `file` declares global types, ambient modules or
+    /// augments other modules.
+    fn declares_for_other_modules(&self, file: &Arc<FileName>) -> bool {
+        let records = match self.module_loader.load_module(file, false) {
+            Ok(v) => v,
+            // Removed files
+            Err(..) => return false,
+        };
+
+        declares_globals(&records.entry)
+            || records.entry.ast.body.iter().any(|item| match item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(m))) => matches!(m.id, TsModuleName::Str(..)),
+                _ => false,
+            })
+    }
+
+    /// Computes the libraries from the default libraries and the directives
+    /// of all loaded files. Libraries referenced by `/// <reference lib="..."
+    /// />` are added, and the default libraries are removed if a file contains
+    /// `/// <reference no-default-lib="true" />`.
+    ///
+    /// Each file is read only once until it's invalidated.
+    ///
+    /// Returns `true` if the libraries are changed.
+    fn apply_lib_references(&self) -> bool {
+        let files = self.module_loader.files();
+        self.lib_references.retain(|file, _| files.contains(file));
+
+        for file in files {
+            if self.lib_references.contains_key(&file) {
+                continue;
+            }
+
+            let records = match self.module_loader.load_module(&file, false) {
+                Ok(v) => v,
+                Err(err) => {
+                    warn!("Failed to load `{}`: {:?}", file, err);
+                    continue;
+                }
+            };
+
+            let references = find_lib_references(&records.comments, &records.entry.ast);
+            self.lib_references.insert(file, references);
+        }
+
+        let mut libs = vec![];
+        let mut no_default_lib = false;
+
+        for entry in self.lib_references.iter() {
+            for reference in entry.value() {
+                match reference {
+                    ImportRef::Lib(name) => {
+                        let deps = Lib::load(name);
+                        if deps.is_empty() {
+                            warn!("Unknown library `{}` is referenced by `{}`", name, entry.key());
+                        }
+                        libs.extend(deps);
+                    }
+                    ImportRef::NoDefaultLib => no_default_lib = true,
+                    _ => {}
+                }
+            }
+        }
+
+        if !no_default_lib {
+            libs.extend(self.default_libs.iter().copied());
+        }
+        libs.sort();
+        libs.dedup();
+
+        let mut env = self.env.write();
+        let mut current = env.builtin().libs().to_vec();
+        current.sort();
+        current.dedup();
+        if libs == current {
+            return false;
+        }
+
+        info!("Using libraries {:?}", libs);
+        let builtin = BuiltIn::from_ts_libs(env.shared(), &libs, no_default_lib);
+        *env = env.with_builtin(builtin);
+
+        true
+    }
+
+    /// Drops the analysis results of all modules, so that they are analyzed
+    /// again with the current environment.
+    ///
+    /// Returns the files analyzed since the last call to
+    /// [Checker::take_errors], as their errors are dropped too.
+    fn drop_analysis_results(&self) -> Vec<Arc<FileName>> {
+        self.module_types.write().clear();
+        self.dts_modules.clear();
+        self.started.clear();
+        self.declared_modules.clear();
+        self.module_declarations.clear();
+        self.augmentations.clear();
+        self.scanned_for_globals.clear();
+
+        self.errors.lock().clear();
+        let mut files = take(&mut *self.checked_files.lock());
+        files.sort();
+        files.dedup();
+        files
+    }
+
+    /// Merges augmentations of the module `id` into `ty`.
+    fn apply_augmentations(&self, id: ModuleId, ty: Type) -> Type {
+        match self.augmentations.get(&id) {
+            Some(augmentations) => augmentations.values().fold(ty, merge_modules),
+            None => ty,
+        }
+    }
+
+    /// Analyzes files contributing to the global scope or augmenting other
+    /// modules before other modules, so that merged declarations are visible
+    /// to all modules regardless of the order of analysis.
+    ///
+    /// Each file is checked only once until it's invalidated.
+    fn analyze_global_declarations(&self, entry: &Arc<FileName>) {
+        let mut files = self.module_loader.files();
+        files.retain(|file| !self.scanned_for_globals.contains(file));
+        files.sort();
+
+        for file in files {
+            self.scanned_for_globals.insert(file.clone());
+
+            let records = match self.module_loader.load_module(&file, false) {
+                Ok(v) => v,
+                Err(err) => {
+                    warn!("Failed to load `{}`: {:?}", file, err);
+                    continue;
+                }
+            };
+
+            if declares_globals(&records.entry) {
+                self.analyze_module(Some(entry.clone()), file);
+            }
+        }
     }
 
     /// Analyzes one module.
@@ -168,7 +540,7 @@ impl Checker {
                 let mut mutations;
                 {
                     let mut a = Analyzer::root(
-                        self.env.clone(),
+                        self.env(),
                         self.cm.clone(),
                         modules_in_group.comments.clone(),
                         Box::new(&mut storage),
@@ -197,6 +569,9 @@ impl Checker {
                     let mut lock = self.errors.lock();
                     lock.extend(storage.take_errors());
                 }
+                self.checked_files
+                    .lock()
+                    .extend(modules_in_group.modules.iter().map(|record| record.filename.clone()));
                 {
                     let mut lock = self.module_types.write();
                     for (module_id, data) in storage.info {
@@ -274,6 +649,25 @@ impl Checker {
         );
 
         let record = records.modules.into_iter().next().unwrap();
+        self.checked_files.lock().push(path.clone());
+
+        // `SourceMap::get_source_file` returns the first file registered with the
+        // name, which is stale if the file was parsed again.
+        let fm = self.cm.lookup_char_pos(record.ast.span.lo).file;
+
+        let cache_key = if self.cache.is_some() && !is_dts {
+            self.cache_key(&path, &fm, &record.ast, &records.comments)
+        } else {
+            None
+        };
+        if let Some(key) = &cache_key {
+            if let Some(ty) = self.load_from_cache(module_id, &path, &fm, record.top_level_ctxt, key) {
+                let dur = Instant::now() - start;
+                log::trace!("[Timing] Loading {} from the module cache took {:?}", path, dur);
+
+                return ty;
+            }
+        }
 
         let mut module = RModule::from_orig(&mut node_id_gen, record.ast.clone());
 
@@ -289,7 +683,7 @@ impl Checker {
         {
             let start = Instant::now();
             let mut a = Analyzer::root(
-                self.env.clone(),
+                self.env(),
                 self.cm.clone(),
                 records.comments,
                 Box::new(&mut storage),
@@ -312,6 +706,10 @@ impl Checker {
             cleanup_module_for_dts(&mut module.body, &storage.info.exports);
         }
 
+        if let Some(key) = cache_key {
+            self.store_to_cache(&path, &fm, key, &module, &storage.info.errors);
+        }
+
         if early_error() {
             for err in storage.info.errors {
                 self.handler.struct_span_err(err.span(), &format!("{:?}", err)).emit();
@@ -343,6 +741,165 @@ impl Checker {
     }
 }
 
+/// Module cache
+impl Checker {
+    /// Hash of the content of the file, the options, the global scope and the
+    /// public api of its dependencies.
+    ///
+    /// Returns [None] if the module should not be cached.
+    fn cache_key(&self, path: &Arc<FileName>, fm: &SourceFile, module: &Module, comments: &StcComments) -> Option<String> {
+        let (_, references, deps) = find_modules_and_deps(comments, module);
+
+        let mut dep_hashes = references
+            .iter()
+            .chain(deps.iter())
+            .map(|dep| {
+                if let Some(id) = self.declared_modules.get(&**dep).as_deref().copied() {
+                    let ty = self.get_types(id)?;
+
+                    return Some(format!("{}:{}", dep, interface_hash(&ty)));
+                }
+
+                let records = self.module_loader.load_dep(path, dep).ok()?;
+                let ty = self.analyze_module(Some(path.clone()), records.entry.filename.clone());
+                let ty = self.apply_augmentations(records.entry.id, ty);
+
+                Some(format!("{}:{}", records.entry.filename, interface_hash(&ty)))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        dep_hashes.sort();
+
+        let env = self.env();
+        let options = format!(
+            "{:?}\n{:?}\n{:?}\n{:?}",
+            env.rule(),
+            env.builtin().libs(),
+            env.target(),
+            env.module()
+        );
+        let globals = ApiReport::new(&env.global_declarations()).to_text();
+
+        Some(hash(
+            format!(
+                "{}\n{}\n{}\n{}\n{}",
+                STC_VERSION,
+                hash(options.as_bytes()),
+                hash(globals.as_bytes()),
+                hash(fm.src.as_bytes()),
+                dep_hashes.join("\n")
+            )
+            .as_bytes(),
+        ))
+    }
+
+    fn load_from_cache(
+        &self,
+        module_id: ModuleId,
+        path: &Arc<FileName>,
+        fm: &SourceFile,
+        top_level_ctxt: SyntaxContext,
+        key: &str,
+    ) -> Option<Type> {
+        let real_path = match &**path {
+            FileName::Real(path) => path,
+            _ => return None,
+        };
+        let cached = self.cache.as_ref()?.load(real_path)?;
+        if cached.key != key {
+            return None;
+        }
+
+        let dts_fm = self
+            .cm
+            .new_source_file(FileName::Custom(format!("{} (cached .d.ts)", real_path.display())), cached.dts);
+        let mut parser = Parser::new(
+            Syntax::Typescript(TsConfig {
+                dts: true,
+                ..Default::default()
+            }),
+            SourceFileInput::from(&*dts_fm),
+            None,
+        );
+        let mut ast = match parser.parse_module() {
+            Ok(v) => v,
+            Err(err) => {
+                warn!("Failed to parse cached .d.ts of `{}`: {:?}", path, err);
+                return None;
+            }
+        };
+        ast.visit_mut_with(&mut swc_ecma_transforms_base::resolver(
+            self.env().shared().marks().unresolved_mark(),
+            top_level_ctxt.outer(),
+            true,
+        ));
+
+        let mut node_id_gen = NodeIdGenerator::default();
+        let module = RModule::from_orig(&mut node_id_gen, ast);
+
+        let mut storage = Single {
+            parent: None,
+            id: module_id,
+            top_level_ctxt,
+            path: path.clone(),
+            is_dts: true,
+            info: Default::default(),
+        };
+        {
+            let mut a = Analyzer::root(
+                self.env(),
+                self.cm.clone(),
+                Default::default(),
+                Box::new(&mut storage),
+                self,
+                self.debugger.clone(),
+            );
+
+            module.visit_with(&mut a);
+        }
+
+        self.errors.lock().extend(from_cached_diagnostics(fm, cached.diagnostics));
+
+        let type_info = Type::Module(stc_ts_types::Module {
+            span: module.span,
+            name: RTsModuleName::Str(RStr {
+                span: DUMMY_SP,
+                value: format!("{:?}", module_id).into(),
+                raw: None,
+            }),
+            exports: Box::new(storage.info.exports),
+            metadata: Default::default(),
+            tracker: Default::default(),
+        })
+        .freezed();
+
+        self.dts_modules.insert(module_id, module);
+
+        Some(type_info)
+    }
+
+    fn store_to_cache(&self, path: &Arc<FileName>, fm: &SourceFile, key: String, dts: &RModule, errors: &stc_ts_errors::Errors) {
+        let (cache, real_path) = match (&self.cache, &**path) {
+            (Some(cache), FileName::Real(path)) => (cache, path),
+            _ => return,
+        };
+        let errors = ErrorKind::flatten(errors.clone().into());
+        let diagnostics = match to_cached_diagnostics(fm, &errors) {
+            Some(v) => v,
+            // An error is reported for other file.
+            None => return,
+        };
+
+        cache.store(
+            real_path,
+            &CachedModule {
+                key,
+                dts: print_dts(&dts.clone().into_orig()),
+                diagnostics,
+            },
+        );
+    }
+}
+
 impl Load for Checker {
     fn module_id(&self, base: &Arc<FileName>, module_specifier: &str) -> Option<ModuleId> {
         if let Some(id) = self.declared_modules.get(module_specifier) {
@@ -375,7 +932,7 @@ impl Load for Checker {
 
         let data = self.analyze_module(Some(base.clone()), records.entry.filename.clone());
 
-        Ok(data)
+        Ok(self.apply_augmentations(records.entry.id, data))
     }
 
     fn load_non_circular_dep(&self, base: &Arc<FileName>, dep: &str) -> VResult<Type> {
@@ -391,10 +948,10 @@ impl Load for Checker {
 
         let data = self.analyze_module(Some(base.clone()), records.entry.filename.clone());
 
-        Ok(data)
+        Ok(self.apply_augmentations(records.entry.id, data))
     }
 
-    fn declare_module(&self, name: &JsWord, module: Type) -> ModuleId {
+    fn declare_module(&self, base: &Arc<FileName>, name: &JsWord, module: Type) -> ModuleId {
         module.assert_clone_cheap();
 
         let module_id = self
@@ -404,6 +961,13 @@ impl Load for Checker {
             .entry
             .id;
 
+        // An ambient module can be declared in multiple files.
+        let module = {
+            let mut declarations = self.module_declarations.entry(module_id).or_default();
+            add_declaration(&mut declarations, base, module);
+            merge_declarations(&declarations).unwrap()
+        };
+
         self.module_types.write().insert(module_id, Arc::new(OnceCell::from(module)));
 
         info!("Declaring module with type `{}`", name);
@@ -411,4 +975,59 @@ impl Load for Checker {
 
         module_id
     }
+
+    fn augment_module(&self, base: &Arc<FileName>, module_specifier: &str, module: Type) -> Option<ModuleId> {
+        module.assert_clone_cheap();
+
+        let records = self.module_loader.load_dep(base, module_specifier).ok()?;
+        let module_id = records.entry.id;
+
+        info!("Augmenting module `{}`", records.entry.filename);
+        add_declaration(&mut self.augmentations.entry(module_id).or_default(), base, module);
+
+        Some(module_id)
+    }
+}
+
+/// Returns `true` if `record` declares global types or variables, or augments
+/// other modules.
+fn declares_globals(record: &ModuleRecord) -> bool {
+    let is_script = record.ast.body.iter().all(|item| matches!(item, ModuleItem::Stmt(..)));
+    if record.is_dts && is_script {
+        return true;
+    }
+
+    record.ast.body.iter().any(|item| match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(m))) => m.global || (!is_script && matches!(m.id, TsModuleName::Str(..))),
+        _ => false,
+    })
+}
+
+/// The declaring file and the start of the declaration.
+type DeclarationKey = (Arc<FileName>, BytePos);
+
+/// Adds `module` declared in `file`.
+///
+/// Analyzing the same declaration again replaces the previous one instead of
+/// merging it twice.
+fn add_declaration(declarations: &mut IndexMap<DeclarationKey, Type>, file: &Arc<FileName>, module: Type) {
+    declarations.insert((file.clone(), module.span().lo), module);
+}
+
+/// Returns [None] if there's no declaration.
+fn merge_declarations(declarations: &IndexMap<DeclarationKey, Type>) -> Option<Type> {
+    declarations.values().cloned().reduce(|prev, module| merge_modules(prev, &module))
+}
+
+/// Merges exports of `new` into `prev`, if both of them are modules.
+fn merge_modules(prev: Type, new: &Type) -> Type {
+    match (prev.normalize(), new.normalize()) {
+        (Type::Module(prev), Type::Module(new)) => {
+            let mut exports = prev.exports.clone();
+            exports.merge((*new.exports).clone());
+
+            Type::Module(stc_ts_types::Module { exports, ..prev.clone() }).freezed()
+        }
+        _ => prev,
+    }
 }
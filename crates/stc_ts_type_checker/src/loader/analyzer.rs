@@ -1,4 +1,4 @@
-use stc_ts_utils::imports::find_imports_in_comments;
+use stc_ts_utils::imports::{find_imports_in_comments, ImportRef};
 use swc_atoms::JsWord;
 use swc_common::{comments::Comments, Span, Spanned};
 use swc_ecma_ast::*;
@@ -21,6 +21,22 @@ where
     (v.declared_modules, v.references, v.deps)
 }
 
+/// Returns `/// <reference lib="..." />` and `/// <reference
+/// no-default-lib="true" />` directives of `m`.
+pub(crate) fn find_lib_references<C>(comments: &C, m: &Module) -> Vec<ImportRef>
+where
+    C: Comments,
+{
+    if m.span.is_dummy() {
+        return vec![];
+    }
+
+    find_imports_in_comments(comments, m.span)
+        .into_iter()
+        .filter(|i| matches!(i, ImportRef::Lib(..) | ImportRef::NoDefaultLib))
+        .collect()
+}
+
 struct DepFinder<C>
 where
     C: Comments,
@@ -38,7 +54,7 @@ where
     fn check_comments(&mut self, span: Span) {
         let deps = find_imports_in_comments(&self.comments, span);
 
-        self.references.extend(deps.into_iter().map(|i| i.to_path()));
+        self.references.extend(deps.into_iter().filter_map(|i| i.to_path()));
     }
 }
 
@@ -0,0 +1,937 @@
+//! Support for JavaScript files.
+//!
+//! The analyzer only understands TypeScript syntax, so JSDoc annotations and
+//! CommonJS modules are converted to the equivalent TypeScript before the
+//! resolver runs.
+
+use std::mem::take;
+
+use fxhash::{FxHashMap, FxHashSet};
+use stc_ts_utils::StcComments;
+use swc_atoms::JsWord;
+use swc_common::{
+    comments::{Comment, CommentKind, Comments},
+    input::StringInput,
+    BytePos, FileName, Span, Spanned,
+};
+use swc_ecma_ast::*;
+use swc_ecma_parser::{Parser, Syntax};
+use swc_ecma_visit::{VisitMut, VisitMutWith};
+
+const JS_EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "cjs"];
+
+pub(crate) fn is_js_file(filename: &FileName) -> bool {
+    match filename {
+        FileName::Real(path) => path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| JS_EXTENSIONS.contains(&ext)),
+        _ => false,
+    }
+}
+
+/// Converts JSDoc annotations and CommonJS imports / exports of a JavaScript
+/// file.
+pub(crate) fn convert_js_module(m: &mut Module, comments: &StcComments) {
+    m.visit_mut_with(&mut JsDocConverter { comments });
+
+    let typedefs = find_typedefs(m, comments);
+
+    convert_common_js(m);
+
+    if typedefs.is_empty() {
+        return;
+    }
+
+    let export = m.body.iter().any(|item| {
+        matches!(
+            item,
+            ModuleItem::ModuleDecl(
+                ModuleDecl::ExportDecl(..)
+                    | ModuleDecl::ExportNamed(..)
+                    | ModuleDecl::ExportDefaultDecl(..)
+                    | ModuleDecl::ExportDefaultExpr(..)
+                    | ModuleDecl::ExportAll(..)
+            )
+        )
+    });
+
+    let typedefs = typedefs.into_iter().map(|alias| {
+        if export {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                span: alias.span,
+                decl: Decl::TsTypeAlias(alias),
+            }))
+        } else {
+            ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(alias)))
+        }
+    });
+
+    m.body.splice(0..0, typedefs);
+}
+
+/// A parsed JSDoc comment.
+#[derive(Debug, Default)]
+struct JsDoc {
+    tags: Vec<Tag>,
+}
+
+#[derive(Debug)]
+struct Tag {
+    span: Span,
+    name: String,
+    /// The type expression in braces, already converted to TypeScript.
+    ty: Option<(String, Span)>,
+    /// The rest of the tag, with the leading `*` of each line removed.
+    text: String,
+}
+
+impl JsDoc {
+    fn parse(c: &Comment) -> Self {
+        // `c.text` does not contain `/*`.
+        let base = c.span.lo + BytePos(2);
+        let text = &*c.text;
+
+        let mut starts = vec![];
+        let mut depth = 0usize;
+        let mut prev = None;
+        for (idx, ch) in text.char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                '@' if depth == 0 && prev.map_or(true, |c: char| c.is_whitespace() || c == '*') => starts.push(idx),
+                _ => {}
+            }
+            prev = Some(ch);
+        }
+
+        let tags = starts
+            .iter()
+            .enumerate()
+            .map(|(n, &start)| {
+                let end = starts.get(n + 1).copied().unwrap_or(text.len());
+                let lo = base + BytePos(start as u32);
+                Tag::parse(&text[start + 1..end], Span::new(lo, base + BytePos(end as u32), Default::default()))
+            })
+            .collect();
+
+        JsDoc { tags }
+    }
+
+    fn tags<'a>(&'a self, names: &'a [&str]) -> impl 'a + Iterator<Item = &'a Tag> {
+        self.tags.iter().filter(move |tag| names.contains(&&*tag.name))
+    }
+
+    fn tag(&self, names: &[&str]) -> Option<&Tag> {
+        self.tags(names).next()
+    }
+
+    fn type_of(&self, names: &[&str]) -> Option<Box<TsType>> {
+        self.tag(names).and_then(Tag::parse_type)
+    }
+
+    /// Returns the `@param` tag for `name`, and whether the parameter is
+    /// optional.
+    fn param(&self, name: &str) -> Option<(&Tag, bool)> {
+        self.tags(&["param", "arg", "argument"]).find_map(|tag| {
+            let (param, optional) = tag.param_name()?;
+            if param == name {
+                Some((tag, optional))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn type_params(&self) -> Option<Box<TsTypeParamDecl>> {
+        let mut params = vec![];
+
+        for tag in self.tags(&["template"]) {
+            let constraint = tag.parse_type();
+
+            for name in tag.text.split(',').map(str::trim) {
+                let name = name.split_whitespace().next().unwrap_or_default();
+                if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$') {
+                    break;
+                }
+
+                params.push(TsTypeParam {
+                    span: tag.span,
+                    name: Ident::new(name.into(), tag.span),
+                    constraint: constraint.clone(),
+                    default: None,
+                    is_in: false,
+                    is_out: false,
+                    is_const: false,
+                });
+            }
+        }
+
+        if params.is_empty() {
+            return None;
+        }
+
+        Some(Box::new(TsTypeParamDecl {
+            span: self.tag(&["template"]).map(|tag| tag.span).unwrap_or_default(),
+            params,
+        }))
+    }
+}
+
+impl Tag {
+    fn parse(s: &str, span: Span) -> Self {
+        let name_end = s.find(|c: char| !c.is_alphanumeric()).unwrap_or(s.len());
+        let name = s[..name_end].to_string();
+
+        let rest = &s[name_end..];
+        let trimmed = rest.trim_start();
+        let offset = (name_end + 1 + rest.len() - trimmed.len()) as u32;
+
+        let (ty, rest) = match trimmed.strip_prefix('{').and_then(|s| matching_brace(s).map(|end| (s, end))) {
+            Some((s, end)) => {
+                let lo = span.lo + BytePos(offset + 1);
+                let ty_span = Span::new(lo, lo + BytePos(end as u32), Default::default());
+
+                (Some((jsdoc_type_to_ts(&strip_stars(&s[..end])), ty_span)), &s[end + 1..])
+            }
+            None => (None, trimmed),
+        };
+
+        Tag {
+            span,
+            name,
+            ty,
+            text: strip_stars(rest),
+        }
+    }
+
+    fn parse_type(&self) -> Option<Box<TsType>> {
+        let (ty, span) = self.ty.as_ref()?;
+
+        parse_type(ty, *span)
+    }
+
+    /// `name`, `[name]` or `[name=default]`.
+    fn param_name(&self) -> Option<(&str, bool)> {
+        let name = self.text.split_whitespace().next()?;
+        let optional_ty = self.ty.as_ref().map_or(false, |(ty, _)| ty.ends_with("| undefined"));
+
+        match name.strip_prefix('[') {
+            Some(name) => {
+                let name = name.trim_end_matches(']');
+                Some((name.split('=').next()?.trim(), true))
+            }
+            None => Some((name, optional_ty)),
+        }
+    }
+}
+
+/// Returns the index of the `}` which closes the type expression.
+fn matching_brace(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (idx, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(idx),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn strip_stars(s: &str) -> String {
+    s.lines()
+        .map(|line| line.trim().trim_start_matches('*').trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Converts the JSDoc specific syntax of a type expression to TypeScript.
+fn jsdoc_type_to_ts(ty: &str) -> String {
+    let ty = ty.trim();
+
+    if ty == "*" || ty == "?" {
+        return "any".into();
+    }
+    if let Some(ty) = ty.strip_prefix("...") {
+        return format!("({})[]", jsdoc_type_to_ts(ty));
+    }
+    if let Some(ty) = ty.strip_prefix('!') {
+        return jsdoc_type_to_ts(ty);
+    }
+    if let Some(ty) = ty.strip_prefix('?').or_else(|| ty.strip_suffix('?')) {
+        return format!("({}) | null", jsdoc_type_to_ts(ty));
+    }
+    if let Some(ty) = ty.strip_suffix('=') {
+        return format!("({}) | undefined", jsdoc_type_to_ts(ty));
+    }
+
+    let ty = ty.replace(".<", "<").replace('*', "any");
+
+    let mut result = String::with_capacity(ty.len());
+    let mut word = String::new();
+    let mut chars = ty.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' || c == '$' || (c == '.' && !word.is_empty()) {
+            word.push(c);
+            if chars
+                .peek()
+                .map_or(false, |&c| c.is_alphanumeric() || c == '_' || c == '$' || c == '.')
+            {
+                continue;
+            }
+
+            let generic = chars.peek() == Some(&'<');
+            result.push_str(match &*word {
+                "String" => "string",
+                "Number" => "number",
+                "Boolean" => "boolean",
+                "function" => "Function",
+                "Object" if generic => "Record",
+                "Object" => "any",
+                word => word,
+            });
+            word.clear();
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Parses a TypeScript type, which was written at `span`.
+fn parse_type(ty: &str, span: Span) -> Option<Box<TsType>> {
+    const PREFIX: &str = "type __T = ";
+
+    let src = format!("{}{};", PREFIX, ty);
+    let start = BytePos(span.lo.0.saturating_sub(PREFIX.len() as u32));
+    let end = start + BytePos(src.len() as u32);
+
+    let mut parser = Parser::new(Syntax::Typescript(Default::default()), StringInput::new(&src, start, end), None);
+    let m = parser.parse_module().ok()?;
+    if !parser.take_errors().is_empty() {
+        return None;
+    }
+
+    let mut ty = match m.body.into_iter().next()? {
+        ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(alias))) => alias.type_ann,
+        _ => return None,
+    };
+
+    // The converted type may be longer than the original one.
+    ty.visit_mut_with(&mut ClampSpan { span });
+
+    Some(ty)
+}
+
+struct ClampSpan {
+    span: Span,
+}
+
+impl VisitMut for ClampSpan {
+    fn visit_mut_span(&mut self, span: &mut Span) {
+        span.lo = span.lo.max(self.span.lo).min(self.span.hi);
+        span.hi = span.hi.max(span.lo).min(self.span.hi);
+    }
+}
+
+fn type_ann(ty: Box<TsType>) -> Box<TsTypeAnn> {
+    Box::new(TsTypeAnn {
+        span: ty.span(),
+        type_ann: ty,
+    })
+}
+
+struct JsDocConverter<'a> {
+    comments: &'a StcComments,
+}
+
+impl JsDocConverter<'_> {
+    /// Returns the JSDoc comment attached to `pos`.
+    fn jsdoc_at(&self, pos: BytePos) -> Option<JsDoc> {
+        let comments = self.comments.get_leading(pos)?;
+        let c = comments
+            .iter()
+            .rev()
+            .find(|c| c.kind == CommentKind::Block && c.text.starts_with('*'))?;
+
+        Some(JsDoc::parse(c))
+    }
+
+    fn annotate_decl(&self, doc: &JsDoc, decl: &mut Decl) {
+        match decl {
+            Decl::Fn(f) => annotate_function(doc, &mut f.function),
+            Decl::Class(c) => annotate_class(doc, &mut c.class),
+            Decl::Var(v) => {
+                if let Some(d) = v.decls.first_mut() {
+                    annotate_var(doc, d);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl VisitMut for JsDocConverter<'_> {
+    fn visit_mut_module_item(&mut self, item: &mut ModuleItem) {
+        item.visit_mut_children_with(self);
+
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                if let Some(doc) = self.jsdoc_at(export.span.lo) {
+                    self.annotate_decl(&doc, &mut export.decl);
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                if let Some(doc) = self.jsdoc_at(export.span.lo) {
+                    match &mut export.decl {
+                        DefaultDecl::Fn(f) => annotate_function(&doc, &mut f.function),
+                        DefaultDecl::Class(c) => annotate_class(&doc, &mut c.class),
+                        DefaultDecl::TsInterfaceDecl(..) => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_mut_decl(&mut self, decl: &mut Decl) {
+        decl.visit_mut_children_with(self);
+
+        if let Some(doc) = self.jsdoc_at(decl.span().lo) {
+            self.annotate_decl(&doc, decl);
+        }
+    }
+
+    fn visit_mut_class_member(&mut self, member: &mut ClassMember) {
+        member.visit_mut_children_with(self);
+
+        let doc = match self.jsdoc_at(member.span().lo) {
+            Some(v) => v,
+            None => return,
+        };
+
+        match member {
+            ClassMember::Constructor(c) => {
+                let params = c.params.iter_mut().filter_map(|p| match p {
+                    ParamOrTsParamProp::Param(p) => Some(&mut p.pat),
+                    _ => None,
+                });
+                annotate_params(&doc, params);
+            }
+            ClassMember::Method(m) => annotate_function(&doc, &mut m.function),
+            ClassMember::PrivateMethod(m) => annotate_function(&doc, &mut m.function),
+            ClassMember::ClassProp(p) => {
+                if p.type_ann.is_none() {
+                    p.type_ann = doc.type_of(&["type"]).map(type_ann);
+                }
+            }
+            ClassMember::PrivateProp(p) => {
+                if p.type_ann.is_none() {
+                    p.type_ann = doc.type_of(&["type"]).map(type_ann);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `/** @type {T} */ (expr)` is a type assertion.
+    fn visit_mut_expr(&mut self, e: &mut Expr) {
+        e.visit_mut_children_with(self);
+
+        if let Expr::Paren(paren) = e {
+            if let Some(ty) = self.jsdoc_at(paren.span.lo).and_then(|doc| doc.type_of(&["type"])) {
+                *e = Expr::TsTypeAssertion(TsTypeAssertion {
+                    span: paren.span,
+                    expr: paren.expr.clone(),
+                    type_ann: ty,
+                });
+            }
+        }
+    }
+}
+
+fn annotate_var(doc: &JsDoc, d: &mut VarDeclarator) {
+    if let Pat::Ident(i) = &mut d.name {
+        if i.type_ann.is_none() {
+            i.type_ann = doc.type_of(&["type"]).map(type_ann);
+        }
+    }
+
+    let mut init = match d.init.as_deref_mut() {
+        Some(v) => v,
+        None => return,
+    };
+    while let Expr::Paren(p) = init {
+        init = &mut *p.expr;
+    }
+
+    match init {
+        Expr::Fn(f) => annotate_function(doc, &mut f.function),
+        Expr::Arrow(f) => {
+            annotate_params(doc, f.params.iter_mut());
+
+            if f.return_type.is_none() {
+                f.return_type = doc.type_of(&["returns", "return"]).map(type_ann);
+            }
+            if f.type_params.is_none() {
+                f.type_params = doc.type_params();
+            }
+        }
+        Expr::Class(c) => annotate_class(doc, &mut c.class),
+        _ => {}
+    }
+}
+
+fn annotate_function(doc: &JsDoc, f: &mut Function) {
+    annotate_params(doc, f.params.iter_mut().map(|p| &mut p.pat));
+
+    if f.return_type.is_none() {
+        f.return_type = doc.type_of(&["returns", "return"]).map(type_ann);
+    }
+    if f.type_params.is_none() {
+        f.type_params = doc.type_params();
+    }
+}
+
+fn annotate_params<'a>(doc: &JsDoc, params: impl Iterator<Item = &'a mut Pat>) {
+    let param_tags = doc.tags(&["param", "arg", "argument"]).collect::<Vec<_>>();
+
+    for (idx, pat) in params.enumerate() {
+        match pat {
+            Pat::Ident(i) => {
+                if let Some((tag, optional)) = doc.param(&i.id.sym) {
+                    if i.type_ann.is_none() {
+                        i.type_ann = tag.parse_type().map(type_ann);
+                    }
+                    i.id.optional |= optional;
+                }
+            }
+            Pat::Assign(a) => {
+                if let Pat::Ident(i) = &mut *a.left {
+                    if let Some((tag, _)) = doc.param(&i.id.sym) {
+                        if i.type_ann.is_none() {
+                            i.type_ann = tag.parse_type().map(type_ann);
+                        }
+                    }
+                }
+            }
+            Pat::Rest(r) => {
+                if let Pat::Ident(i) = &*r.arg {
+                    if let Some((tag, _)) = doc.param(&i.id.sym) {
+                        if r.type_ann.is_none() {
+                            r.type_ann = tag.parse_type().map(type_ann);
+                        }
+                    }
+                }
+            }
+            // Destructured parameters are matched by position.
+            Pat::Object(ObjectPat { type_ann, .. }) | Pat::Array(ArrayPat { type_ann, .. }) => {
+                if type_ann.is_none() {
+                    *type_ann = param_tags.get(idx).and_then(|tag| tag.parse_type()).map(self::type_ann);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn annotate_class(doc: &JsDoc, c: &mut Class) {
+    if c.type_params.is_none() {
+        c.type_params = doc.type_params();
+    }
+
+    if c.super_class.is_some() && c.super_type_params.is_none() {
+        if let Some(TsType::TsTypeRef(r)) = doc.type_of(&["extends", "augments"]).map(|ty| *ty) {
+            c.super_type_params = r.type_params;
+        }
+    }
+
+    for tag in doc.tags(&["implements"]) {
+        if let Some(TsType::TsTypeRef(r)) = tag.parse_type().map(|ty| *ty) {
+            c.implements.push(TsExprWithTypeArgs {
+                span: r.span,
+                expr: Box::new(entity_name_to_expr(r.type_name)),
+                type_args: r.type_params,
+            });
+        }
+    }
+}
+
+fn entity_name_to_expr(n: TsEntityName) -> Expr {
+    match n {
+        TsEntityName::Ident(i) => Expr::Ident(i),
+        TsEntityName::TsQualifiedName(q) => {
+            let q = *q;
+            Expr::Member(MemberExpr {
+                span: q.right.span,
+                obj: Box::new(entity_name_to_expr(q.left)),
+                prop: MemberProp::Ident(q.right),
+            })
+        }
+    }
+}
+
+/// Converts `@typedef` and `@callback` tags into type aliases.
+fn find_typedefs(m: &Module, comments: &StcComments) -> Vec<Box<TsTypeAliasDecl>> {
+    let mut docs = vec![];
+    for map in [&comments.leading, &comments.trailing] {
+        for entry in map.iter() {
+            for c in entry.value() {
+                if m.span.lo <= c.span.lo
+                    && c.span.hi <= m.span.hi
+                    && c.kind == CommentKind::Block
+                    && c.text.starts_with('*')
+                    && (c.text.contains("@typedef") || c.text.contains("@callback"))
+                {
+                    docs.push(JsDoc::parse(c));
+                }
+            }
+        }
+    }
+
+    let mut aliases = docs.iter().filter_map(typedef_to_alias).collect::<Vec<_>>();
+    aliases.sort_by_key(|alias| alias.span.lo);
+
+    aliases
+}
+
+fn typedef_to_alias(doc: &JsDoc) -> Option<Box<TsTypeAliasDecl>> {
+    let (tag, ty) = if let Some(tag) = doc.tag(&["typedef"]) {
+        let is_object = tag.ty.as_ref().map_or(true, |(ty, _)| ty == "any" || ty == "object");
+        let props = doc.tags(&["property", "prop"]).collect::<Vec<_>>();
+
+        let ty = if is_object && !props.is_empty() {
+            let members = props
+                .iter()
+                .filter_map(|prop| {
+                    let (name, optional) = prop.param_name()?;
+                    let (ty, _) = prop.ty.as_ref()?;
+                    Some(format!("{}{}: {};", name, if optional { "?" } else { "" }, ty))
+                })
+                .collect::<String>();
+            parse_type(&format!("{{ {} }}", members), tag.span)?
+        } else {
+            tag.parse_type()?
+        };
+
+        (tag, ty)
+    } else {
+        let tag = doc.tag(&["callback"])?;
+
+        let params = doc
+            .tags(&["param", "arg", "argument"])
+            .filter_map(|param| {
+                let (name, optional) = param.param_name()?;
+                let ty = param.ty.as_ref().map_or("any", |(ty, _)| ty.as_str());
+                Some(format!("{}{}: {}", name, if optional { "?" } else { "" }, ty))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let ret = doc
+            .tag(&["returns", "return"])
+            .and_then(|tag| tag.ty.as_ref())
+            .map_or("any", |(ty, _)| ty.as_str());
+
+        (tag, parse_type(&format!("({}) => {}", params, ret), tag.span)?)
+    };
+
+    let name = tag.text.split_whitespace().next()?;
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$') {
+        return None;
+    }
+
+    Some(Box::new(TsTypeAliasDecl {
+        span: tag.span,
+        declare: false,
+        id: Ident::new(name.into(), tag.span),
+        type_params: doc.type_params(),
+        type_ann: ty,
+    }))
+}
+
+/// Converts top-level `require` calls and assignments to `module.exports` /
+/// `exports`.
+fn convert_common_js(m: &mut Module) {
+    let mut names = FxHashSet::default();
+    for item in &m.body {
+        declared_names(item, &mut names);
+    }
+    let mut exports = FxHashMap::default();
+
+    for item in take(&mut m.body) {
+        let converted = match &item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => convert_require(var).map(|decl| vec![ModuleItem::ModuleDecl(decl)]),
+            ModuleItem::Stmt(Stmt::Expr(s)) => convert_exports(s, &mut names, &mut exports),
+            _ => None,
+        };
+
+        match converted {
+            Some(items) => m.body.extend(items),
+            None => m.body.push(item),
+        }
+    }
+}
+
+/// Adds the names declared by a top-level item to `names`.
+fn declared_names(item: &ModuleItem, names: &mut FxHashSet<JsWord>) {
+    let decl = match item {
+        ModuleItem::Stmt(Stmt::Decl(decl)) | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => decl,
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+            names.extend(import.specifiers.iter().map(|s| match s {
+                ImportSpecifier::Named(s) => s.local.sym.clone(),
+                ImportSpecifier::Default(s) => s.local.sym.clone(),
+                ImportSpecifier::Namespace(s) => s.local.sym.clone(),
+            }));
+            return;
+        }
+        _ => return,
+    };
+
+    match decl {
+        Decl::Class(c) => {
+            names.insert(c.ident.sym.clone());
+        }
+        Decl::Fn(f) => {
+            names.insert(f.ident.sym.clone());
+        }
+        Decl::Var(v) => {
+            for d in &v.decls {
+                pat_names(&d.name, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn pat_names(pat: &Pat, names: &mut FxHashSet<JsWord>) {
+    match pat {
+        Pat::Ident(i) => {
+            names.insert(i.id.sym.clone());
+        }
+        Pat::Array(a) => {
+            for elem in a.elems.iter().flatten() {
+                pat_names(elem, names);
+            }
+        }
+        Pat::Object(o) => {
+            for prop in &o.props {
+                match prop {
+                    ObjectPatProp::KeyValue(p) => pat_names(&p.value, names),
+                    ObjectPatProp::Assign(p) => {
+                        names.insert(p.key.sym.clone());
+                    }
+                    ObjectPatProp::Rest(p) => pat_names(&p.arg, names),
+                }
+            }
+        }
+        Pat::Assign(a) => pat_names(&a.left, names),
+        Pat::Rest(r) => pat_names(&r.arg, names),
+        _ => {}
+    }
+}
+
+/// - `const x = require('y')` => `import x = require('y')`
+/// - `const { a, b: c } = require('y')` => `import { a, b as c } from 'y'`
+fn convert_require(var: &VarDecl) -> Option<ModuleDecl> {
+    let d = match &*var.decls {
+        [d] => d,
+        _ => return None,
+    };
+
+    let src = match d.init.as_deref()? {
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            args,
+            ..
+        }) => match (&**callee, &**args) {
+            (Expr::Ident(callee), [ExprOrSpread { spread: None, expr }]) if &*callee.sym == "require" => match &**expr {
+                Expr::Lit(Lit::Str(src)) => src.clone(),
+                _ => return None,
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    match &d.name {
+        Pat::Ident(i) => Some(ModuleDecl::TsImportEquals(Box::new(TsImportEqualsDecl {
+            span: var.span,
+            declare: false,
+            is_export: false,
+            is_type_only: false,
+            id: i.id.clone(),
+            module_ref: TsModuleRef::TsExternalModuleRef(TsExternalModuleRef { span: src.span, expr: src }),
+        }))),
+        Pat::Object(obj) => {
+            let specifiers = obj
+                .props
+                .iter()
+                .map(|prop| match prop {
+                    ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                        let imported = match key {
+                            PropName::Ident(key) => ModuleExportName::Ident(key.clone()),
+                            PropName::Str(key) => ModuleExportName::Str(key.clone()),
+                            _ => return None,
+                        };
+
+                        match &**value {
+                            Pat::Ident(local) => Some(ImportSpecifier::Named(ImportNamedSpecifier {
+                                span: prop.span(),
+                                local: local.id.clone(),
+                                imported: Some(imported),
+                                is_type_only: false,
+                            })),
+                            _ => None,
+                        }
+                    }
+                    ObjectPatProp::Assign(AssignPatProp { span, key, value: None }) => Some(ImportSpecifier::Named(ImportNamedSpecifier {
+                        span: *span,
+                        local: key.clone(),
+                        imported: None,
+                        is_type_only: false,
+                    })),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            Some(ModuleDecl::Import(ImportDecl {
+                span: var.span,
+                specifiers,
+                src: Box::new(src),
+                type_only: false,
+                asserts: None,
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// - `module.exports = e` => `export = e`
+/// - `exports.foo = e` => `export var foo = e`, or `var _foo = e; export { _foo
+///   as foo }` if `foo` is already declared in the module.
+///
+/// `names` contains the names declared in the module, and `exports` maps each
+/// name exported by an earlier `exports.foo = e` to the variable declared for
+/// it, if any.
+fn convert_exports(s: &ExprStmt, names: &mut FxHashSet<JsWord>, exports: &mut FxHashMap<JsWord, Option<Ident>>) -> Option<Vec<ModuleItem>> {
+    let assign = match &*s.expr {
+        Expr::Assign(assign) if assign.op == AssignOp::Assign => assign,
+        _ => return None,
+    };
+
+    let left = match &assign.left {
+        PatOrExpr::Expr(e) => &**e,
+        PatOrExpr::Pat(p) => match &**p {
+            Pat::Expr(e) => &**e,
+            _ => return None,
+        },
+    };
+    let left = match left {
+        Expr::Member(left) => left,
+        _ => return None,
+    };
+
+    if is_module_exports(left) {
+        return Some(vec![ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(TsExportAssignment {
+            span: s.span,
+            expr: assign.right.clone(),
+        }))]);
+    }
+
+    let is_exports_obj = match &*left.obj {
+        Expr::Ident(obj) => &*obj.sym == "exports",
+        Expr::Member(obj) => is_module_exports(obj),
+        _ => false,
+    };
+    let prop = match &left.prop {
+        MemberProp::Ident(prop) if is_exports_obj => prop,
+        _ => return None,
+    };
+
+    // The name is already exported, so this is a reassignment of the export.
+    if let Some(var) = exports.get(&prop.sym) {
+        let expr = match var {
+            Some(var) => Box::new(Expr::Assign(AssignExpr {
+                span: assign.span,
+                op: AssignOp::Assign,
+                left: PatOrExpr::Pat(Box::new(Pat::Ident(var.clone().into()))),
+                right: assign.right.clone(),
+            })),
+            // We can't assign to a binding we did not declare.
+            None => assign.right.clone(),
+        };
+
+        return Some(vec![ModuleItem::Stmt(Stmt::Expr(ExprStmt { span: s.span, expr }))]);
+    }
+
+    if let Expr::Ident(orig) = &*assign.right {
+        exports.insert(prop.sym.clone(), None);
+
+        return Some(vec![export_named(s.span, orig.clone(), prop.clone())]);
+    }
+
+    let var = if names.contains(&prop.sym) {
+        let mut sym = JsWord::from(format!("_{}", prop.sym));
+        while names.contains(&sym) {
+            sym = format!("_{}", sym).into();
+        }
+        Ident::new(sym, prop.span)
+    } else {
+        Ident::new(prop.sym.clone(), prop.span)
+    };
+    names.insert(var.sym.clone());
+    exports.insert(prop.sym.clone(), Some(var.clone()));
+
+    let decl = Decl::Var(Box::new(VarDecl {
+        span: s.span,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: s.span,
+            name: Pat::Ident(var.clone().into()),
+            init: Some(assign.right.clone()),
+            definite: false,
+        }],
+    }));
+
+    if var.sym == prop.sym {
+        return Some(vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            span: s.span,
+            decl,
+        }))]);
+    }
+
+    Some(vec![ModuleItem::Stmt(Stmt::Decl(decl)), export_named(s.span, var, prop.clone())])
+}
+
+/// `export { orig as exported }`
+fn export_named(span: Span, orig: Ident, exported: Ident) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+        span,
+        specifiers: vec![ExportSpecifier::Named(ExportNamedSpecifier {
+            span,
+            exported: if orig.sym == exported.sym {
+                None
+            } else {
+                Some(ModuleExportName::Ident(exported))
+            },
+            orig: ModuleExportName::Ident(orig),
+            is_type_only: false,
+        })],
+        src: None,
+        type_only: false,
+        asserts: None,
+    }))
+}
+
+fn is_module_exports(e: &MemberExpr) -> bool {
+    let is_module = matches!(&*e.obj, Expr::Ident(obj) if &*obj.sym == "module");
+    let is_exports = matches!(&e.prop, MemberProp::Ident(prop) if &*prop.sym == "exports");
+
+    is_module && is_exports
+}
@@ -1,24 +1,32 @@
-use std::sync::{Arc, Mutex, RwLock};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock},
+};
 
 use anyhow::{bail, Context, Result};
 use auto_impl::auto_impl;
 use dashmap::{DashMap, DashSet};
-use fxhash::FxBuildHasher;
-use petgraph::algo::kosaraju_scc;
+use fxhash::{FxBuildHasher, FxHashSet};
+use petgraph::{
+    algo::kosaraju_scc,
+    EdgeDirection::{Incoming, Outgoing},
+};
 use rayon::prelude::*;
 use stc_ts_env::Env;
 use stc_ts_types::{module_id::ModuleIdGenerator, ModuleId};
-use stc_ts_utils::StcComments;
+use stc_ts_utils::{imports::package_of_types, StcComments};
 use swc_common::{input::SourceFileInput, FileName, SourceFile, SourceMap, Span, SyntaxContext, GLOBALS};
 use swc_ecma_ast::Module;
 use swc_ecma_loader::resolve::Resolve;
-use swc_ecma_parser::{Parser, Syntax, TsConfig};
+use swc_ecma_parser::{EsConfig, Parser, Syntax, TsConfig};
 use swc_ecma_visit::VisitMutWith;
 use swc_fast_graph::digraph::FastDiGraphMap;
 
-use self::analyzer::find_modules_and_deps;
+pub(crate) use self::js::is_js_file;
+use self::{analyzer::find_modules_and_deps, js::convert_js_module};
 
-mod analyzer;
+pub(crate) mod analyzer;
+mod js;
 pub mod store;
 
 pub struct ModuleRecord {
@@ -53,6 +61,16 @@ pub trait LoadModule: 'static + Send + Sync {
 
     /// Same constraints for [`LoadModule::load_module`] applies.
     fn load_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<Records>;
+
+    /// Returns all files loaded so far.
+    fn files(&self) -> Vec<Arc<FileName>>;
+
+    /// Drops the cached ast of `filename`, so that it will be parsed again by
+    /// the next call to [`LoadModule::load_module`].
+    ///
+    /// Returns the module itself and all modules which depend on it, directly
+    /// or transitively.
+    fn invalidate(&self, filename: &Arc<FileName>) -> Vec<(ModuleId, Arc<FileName>)>;
 }
 
 /// **NOTE**: [FileName::Custom] is not passed to this type.
@@ -71,6 +89,9 @@ where
     resolver: R,
     loader: L,
 
+    /// `typeRoots` of `tsconfig.json`.
+    type_roots: Vec<PathBuf>,
+
     /// TODO(kdu1): Split the
     comments: StcComments,
     loading_started: DashSet<Arc<FileName>, FxBuildHasher>,
@@ -93,6 +114,7 @@ where
             env,
             resolver,
             loader,
+            type_roots: Default::default(),
 
             comments: Default::default(),
             loading_started: Default::default(),
@@ -104,6 +126,15 @@ where
         }
     }
 
+    /// Searches `type_roots` before `node_modules/@types` for `/// <reference
+    /// types="..." />`, like `typeRoots` of `tsconfig.json`.
+    ///
+    /// `type_roots` should be absolute paths.
+    pub fn with_type_roots(mut self, type_roots: Vec<PathBuf>) -> Self {
+        self.type_roots = type_roots;
+        self
+    }
+
     fn load_recursively(&self, filename: &Arc<FileName>, calc_cycles: bool) -> Result<ModuleId> {
         let (id, _) = self.ids.generate(filename);
 
@@ -122,7 +153,7 @@ where
                     .chain(deps.iter().map(|v| (v, true)))
                     .map(|(dep, is_normal_dep)| {
                         GLOBALS.set(globals, || {
-                            let dep_path = Arc::new(self.resolver.resolve(filename, dep)?);
+                            let dep_path = Arc::new(self.resolve(filename, dep)?);
 
                             self.load_recursively(&dep_path, false).map(|v| (v, is_normal_dep))
                         })
@@ -135,7 +166,7 @@ where
                     .chain(deps.par_iter().map(|v| (v, true)))
                     .map(|(dep, is_normal_dep)| {
                         GLOBALS.set(globals, || {
-                            let dep_path = Arc::new(self.resolver.resolve(filename, dep)?);
+                            let dep_path = Arc::new(self.resolve(filename, dep)?);
 
                             self.load_recursively(&dep_path, false).map(|v| (v, is_normal_dep))
                         })
@@ -176,6 +207,29 @@ where
         Ok(id)
     }
 
+    /// Resolves `module_specifier` from `base`.
+    ///
+    /// `/// <reference types="foo" />` is resolved to `foo` in type roots or
+    /// `@types/foo`, and the package `foo` is used if it declares its own
+    /// types.
+    fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName> {
+        if let Some(name) = module_specifier.strip_prefix("@types/") {
+            for root in &self.type_roots {
+                if let Ok(v) = self.resolver.resolve(base, &root.join(name).to_string_lossy()) {
+                    return Ok(v);
+                }
+            }
+        }
+
+        match self.resolver.resolve(base, module_specifier) {
+            Ok(v) => Ok(v),
+            Err(err) => match package_of_types(module_specifier) {
+                Some(pkg) => self.resolver.resolve(base, &pkg),
+                None => Err(err),
+            },
+        }
+    }
+
     fn parse(&self, filename: &Arc<FileName>) -> Result<(Arc<ModuleRecord>, StcComments)> {
         if let Some(cached) = self.parse_cache.get(filename).as_deref().cloned() {
             return Ok(cached);
@@ -193,6 +247,11 @@ where
     fn parse_inner(&self, filename: &Arc<FileName>) -> Result<(Arc<ModuleRecord>, StcComments)> {
         let comments = self.comments.clone();
 
+        let is_js = is_js_file(filename);
+        if is_js && !self.env.rule().allow_js {
+            bail!("`{}` is a JavaScript file, but `allowJs` is not enabled", filename)
+        }
+
         let (fm, syntax) = match &**filename {
             FileName::Custom(..) => {
                 let fm = self.cm.new_source_file((**filename).clone(), String::new());
@@ -222,10 +281,17 @@ where
         };
 
         let mut parser = Parser::new(
-            Syntax::Typescript(TsConfig {
-                tsx: fm.name.to_string().contains("tsx"),
-                ..Default::default()
-            }),
+            if is_js {
+                Syntax::Es(EsConfig {
+                    jsx: true,
+                    ..Default::default()
+                })
+            } else {
+                Syntax::Typescript(TsConfig {
+                    tsx: fm.name.to_string().contains("tsx"),
+                    ..Default::default()
+                })
+            },
             SourceFileInput::from(&*fm),
             Some(&comments),
         );
@@ -249,6 +315,10 @@ where
         let (id, top_level_mark) = self.ids.generate(filename);
         let top_level_ctxt = SyntaxContext::empty().apply_mark(top_level_mark);
 
+        if is_js {
+            convert_js_module(&mut ast, &comments);
+        }
+
         ast.visit_mut_with(&mut swc_ecma_transforms_base::resolver(
             self.env.shared().marks().unresolved_mark(),
             top_level_mark,
@@ -311,12 +381,68 @@ where
 
     fn load_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<Records> {
         let filename = self
-            .resolver
             .resolve(base, module_specifier)
             .with_context(|| format!("failed to resolve `{}` from `{}`", module_specifier, base))?;
 
         self.load_module(&Arc::new(filename), false)
     }
+
+    fn files(&self) -> Vec<Arc<FileName>> {
+        self.parse_cache
+            .iter()
+            .map(|entry| entry.key().clone())
+            .filter(|filename| !matches!(&**filename, FileName::Custom(..)))
+            .collect()
+    }
+
+    fn invalidate(&self, filename: &Arc<FileName>) -> Vec<(ModuleId, Arc<FileName>)> {
+        let (id, _) = self.ids.generate(filename);
+
+        let invalidated = {
+            let g = self.dep_graph.read().unwrap();
+
+            let mut done = FxHashSet::default();
+            let mut queue = vec![id];
+            while let Some(cur) = queue.pop() {
+                if !done.insert(cur) {
+                    continue;
+                }
+
+                queue.extend(g.neighbors_directed(cur, Incoming));
+            }
+
+            done
+        };
+
+        {
+            // Dependencies of the file are added again while loading it.
+            let mut g = self.dep_graph.write().unwrap();
+            let deps = g.neighbors_directed(id, Outgoing).collect::<Vec<_>>();
+            for dep in deps {
+                g.remove_edge(id, dep);
+            }
+        }
+
+        {
+            // Cycles are calculated again while loading the entry.
+            let mut cycles = self.cycles.write().unwrap();
+            cycles.retain(|cycle| !cycle.iter().any(|id| invalidated.contains(id)));
+        }
+
+        // Only the file itself should be parsed again, but dependents should be visited
+        // again to load the new dependencies of the file.
+        self.parse_cache.remove(filename);
+
+        invalidated
+            .into_iter()
+            .map(|id| {
+                let path = self.ids.path(id);
+                self.loading_started.remove(&path);
+
+                (id, path)
+            })
+            .collect()
+    }
 }
 
 pub struct DefaultFileLoader;
@@ -329,6 +455,16 @@ impl LoadFile for DefaultFileLoader {
                     .load_file(path)
                     .with_context(|| format!("failed to load module `{}`", path.display()))?;
 
+                if is_js_file(filename) {
+                    return Ok((
+                        fm,
+                        Syntax::Es(EsConfig {
+                            jsx: true,
+                            ..Default::default()
+                        }),
+                    ));
+                }
+
                 let syntax = TsConfig {
                     dts: path.as_os_str().to_string_lossy().ends_with(".d.ts"),
                     tsx: path.extension().map(|v| v == "tsx").unwrap_or(false),
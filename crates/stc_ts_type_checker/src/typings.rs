@@ -17,9 +17,9 @@ impl Checker {
             return;
         }
 
-        let result = NodeResolver
+        let result = NodeResolver::new()
             .resolve_as_file(dir)
-            .or_else(|_| NodeResolver.resolve_as_directory(dir));
+            .or_else(|_| NodeResolver::new().resolve_as_directory(dir));
 
         if let Ok(entry) = result {
             let entry = Arc::new(FileName::Real(entry));
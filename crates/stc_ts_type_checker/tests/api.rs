@@ -0,0 +1,50 @@
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{
+    api::{diff, ApiReport},
+    loader::{DefaultFileLoader, ModuleLoader},
+    Checker,
+};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+use testing::{fixture, NormalizedOutput};
+
+/// Compares `old.ts` and `new.ts` in a directory, and stores the report of
+/// `new.ts` and the changes.
+#[fixture("tests/api/**/old.ts")]
+fn api(old: PathBuf) {
+    let new = old.with_file_name("new.ts");
+
+    let (report, changes) = testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es5"));
+        let checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            Box::new(ModuleLoader::new(cm, env, NodeResolver::new(), DefaultFileLoader)),
+        );
+
+        let old = ApiReport::load(&checker, Arc::new(FileName::Real(old.canonicalize().unwrap()))).expect("failed to load old.ts");
+        let new = ApiReport::load(&checker, Arc::new(FileName::Real(new.canonicalize().unwrap()))).expect("failed to load new.ts");
+
+        let changes = diff(&old, &new, |l, r| checker.is_assignable(l, r))
+            .into_iter()
+            .map(|change| format!("{}\n", change))
+            .collect::<String>();
+
+        Ok((new.to_text(), changes))
+    })
+    .unwrap();
+
+    NormalizedOutput::from(report)
+        .compare_to_file(old.with_file_name("report.txt"))
+        .unwrap();
+    NormalizedOutput::from(changes)
+        .compare_to_file(old.with_file_name("changes.txt"))
+        .unwrap();
+}
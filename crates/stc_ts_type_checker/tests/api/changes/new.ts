@@ -0,0 +1,29 @@
+// `Opts` is not exported, so it can be resolved only in this module.
+interface Opts {
+    a: string;
+    b?: number;
+}
+
+export function f(o: Opts): void {}
+
+export function g(a: string): void {}
+
+export function h(a: string, b: number): void {}
+
+export function k(): string | number {
+    return "";
+}
+
+export function m(a: string, b: number): void {}
+
+export declare function o(a: string): void;
+export declare function o(a: boolean): void;
+
+export const y: string = "";
+
+export interface Point {
+    x: number;
+    y?: number;
+}
+
+export type Id = number;
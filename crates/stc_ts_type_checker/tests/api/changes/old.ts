@@ -0,0 +1,26 @@
+interface Options {
+    a: string;
+}
+
+export function f(o: Options): void {}
+
+export function g(a: string | number): void {}
+
+export function h(a: string): void {}
+
+export function k(): string {
+    return "";
+}
+
+export function m(a: string, b?: number): void {}
+
+export declare function o(a: string): void;
+export declare function o(a: number): void;
+
+export const x: number = 1;
+
+export interface Point {
+    x: number;
+}
+
+export type Id = string;
@@ -0,0 +1,48 @@
+//! Tests for module augmentations, which need multiple files.
+
+mod common;
+
+use common::Project;
+
+/// Checks `index.ts` and returns `(line, code)` of errors in it.
+fn check(name: &str, files: &[(&str, &str)]) -> Vec<(usize, usize)> {
+    Project::new("augmentation", name, files).check("index.ts")
+}
+
+#[test]
+fn interface_of_package() {
+    let errors = check(
+        "interface_of_package",
+        &[
+            (
+                "node_modules/pkg/package.json",
+                "{ \"name\": \"pkg\", \"types\": \"index.d.ts\" }\n",
+            ),
+            ("node_modules/pkg/index.d.ts", "export interface Request {\n    url: string;\n}\n"),
+            (
+                "augment.ts",
+                "import 'pkg';
+
+declare module 'pkg' {
+    interface Request {
+        user: string;
+    }
+}
+",
+            ),
+            (
+                "index.ts",
+                "import './augment';
+import { Request } from 'pkg';
+
+declare const req: Request;
+export const url: string = req.url;
+export const user: string = req.user;
+export const bad: number = req.user;
+",
+            ),
+        ],
+    );
+
+    assert_eq!(errors, vec![(7, 2322)]);
+}
@@ -0,0 +1,232 @@
+//! Tests for the build mode.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use stc_ts_type_checker::build::{BuildStatus, Builder};
+
+fn create_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = env::temp_dir().join("stc-build").join(name);
+    let _ = fs::remove_dir_all(&dir);
+
+    for (name, content) in files {
+        write(&dir, name, content);
+    }
+
+    dir.canonicalize().unwrap()
+}
+
+fn write(dir: &Path, name: &str, content: &str) {
+    let path = dir.join(name);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Returns input files of the project, relative to `dir`.
+fn project_files(dir: &Path) -> Vec<String> {
+    testing::run_test2(false, |cm, handler| {
+        let builder = Builder::new(cm, Arc::new(handler), &dir.join("tsconfig.json")).unwrap();
+
+        Ok(builder.projects()[0]
+            .files
+            .iter()
+            .map(|file| file.strip_prefix(dir).unwrap().display().to_string())
+            .collect())
+    })
+    .unwrap()
+}
+
+fn build(config_path: &Path, force: bool) -> Vec<BuildStatus> {
+    testing::run_test2(false, |cm, handler| {
+        let builder = Builder::new(cm, Arc::new(handler), config_path).unwrap();
+
+        Ok(builder.build(force).into_iter().map(|result| result.status).collect())
+    })
+    .unwrap()
+}
+
+#[test]
+fn exclude_with_wildcards() {
+    let dir = create_dir(
+        "exclude_with_wildcards",
+        &[
+            ("tsconfig.json", r#"{ "exclude": ["**/*.test.ts", "*.spec.ts", "fixtures"] }"#),
+            ("index.ts", ""),
+            ("index.spec.ts", ""),
+            ("src/a.ts", ""),
+            ("src/a.test.ts", ""),
+            ("src/a.spec.ts", ""),
+            ("fixtures/b.ts", ""),
+        ],
+    );
+
+    assert_eq!(project_files(&dir), vec!["index.ts", "src/a.spec.ts", "src/a.ts"]);
+}
+
+#[test]
+fn include_with_wildcards() {
+    let dir = create_dir(
+        "include_with_wildcards",
+        &[
+            ("tsconfig.json", r#"{ "include": ["src/**/*.ts", "types"] }"#),
+            ("index.ts", ""),
+            ("src/a.ts", ""),
+            ("src/a.tsx", ""),
+            ("src/nested/b.ts", ""),
+            ("types/c.d.ts", ""),
+        ],
+    );
+
+    assert_eq!(project_files(&dir), vec!["src/a.ts", "src/nested/b.ts", "types/c.d.ts"]);
+}
+
+#[test]
+fn out_dir_is_excluded() {
+    let dir = create_dir(
+        "out_dir_is_excluded",
+        &[
+            ("tsconfig.json", r#"{ "compilerOptions": { "declarationDir": "dist" } }"#),
+            ("src/a.ts", ""),
+            ("dist/a.d.ts", ""),
+        ],
+    );
+
+    assert_eq!(project_files(&dir), vec!["src/a.ts"]);
+}
+
+#[test]
+fn emitted_declarations_are_excluded() {
+    let dir = create_dir(
+        "emitted_declarations_are_excluded",
+        &[
+            ("tsconfig.json", r#"{ "compilerOptions": { "composite": true } }"#),
+            ("a.ts", "export const a = 1;"),
+            ("types.d.ts", "declare const b: number;"),
+        ],
+    );
+    let config_path = dir.join("tsconfig.json");
+
+    assert_eq!(build(&config_path, false), vec![BuildStatus::Built]);
+    assert!(dir.join("a.d.ts").is_file());
+
+    assert_eq!(project_files(&dir), vec!["a.ts", "types.d.ts"]);
+    assert_eq!(build(&config_path, false), vec![BuildStatus::UpToDate]);
+}
+
+#[test]
+fn build_info_creates_declaration_dir() {
+    let dir = create_dir(
+        "build_info_creates_declaration_dir",
+        &[
+            ("tsconfig.json", r#"{ "compilerOptions": { "declarationDir": "dist" } }"#),
+            ("types.d.ts", "declare const a: number;"),
+        ],
+    );
+
+    assert_eq!(build(&dir.join("tsconfig.json"), false), vec![BuildStatus::Built]);
+    assert!(dir.join("dist/tsconfig.stcbuildinfo").is_file());
+}
+
+fn create_projects(name: &str, lib_source: &str) -> PathBuf {
+    create_dir(
+        name,
+        &[
+            (
+                "lib/tsconfig.json",
+                r#"{
+    "compilerOptions": { "composite": true, "rootDir": "src", "declarationDir": "dist" },
+    "include": ["src"]
+}"#,
+            ),
+            ("lib/src/index.ts", lib_source),
+            (
+                "app/tsconfig.json",
+                r#"{
+    "compilerOptions": { "rootDir": "src", "declarationDir": "dist" },
+    "include": ["src"],
+    "references": [{ "path": "../lib" }]
+}"#,
+            ),
+            (
+                "app/src/main.ts",
+                "import { greet } from '../../lib/src/index';\n\nexport const message: string = greet('stc');",
+            ),
+        ],
+    )
+}
+
+#[test]
+fn build_info_skips_up_to_date_projects() {
+    let dir = create_projects(
+        "build_info_skips_up_to_date_projects",
+        "export function greet(name: string): string {\n    return name;\n}",
+    );
+    let config_path = dir.join("app/tsconfig.json");
+
+    assert_eq!(build(&config_path, false), vec![BuildStatus::Built, BuildStatus::Built]);
+    assert!(dir.join("lib/dist/index.d.ts").is_file());
+    assert!(dir.join("lib/dist/tsconfig.stcbuildinfo").is_file());
+    assert!(dir.join("app/dist/main.d.ts").is_file());
+    assert!(dir.join("app/dist/tsconfig.stcbuildinfo").is_file());
+
+    assert_eq!(build(&config_path, false), vec![BuildStatus::UpToDate, BuildStatus::UpToDate]);
+    assert_eq!(build(&config_path, true), vec![BuildStatus::Built, BuildStatus::Built]);
+
+    write(
+        &dir,
+        "app/src/main.ts",
+        "import { greet } from '../../lib/src/index';\n\nexport const message = greet('stc');",
+    );
+    assert_eq!(build(&config_path, false), vec![BuildStatus::UpToDate, BuildStatus::Built]);
+
+    // Downstream projects are built again if an upstream project is changed.
+    write(
+        &dir,
+        "lib/src/index.ts",
+        "export function greet(name: string): string {\n    return `Hello, ${name}`;\n}",
+    );
+    assert_eq!(build(&config_path, false), vec![BuildStatus::Built, BuildStatus::Built]);
+}
+
+#[test]
+fn failed_upstream_skips_downstream() {
+    let dir = create_projects(
+        "failed_upstream_skips_downstream",
+        "export function greet(name: string): number {\n    return name;\n}",
+    );
+
+    assert_eq!(
+        build(&dir.join("app/tsconfig.json"), false),
+        vec![BuildStatus::Failed, BuildStatus::Skipped]
+    );
+    assert!(!dir.join("lib/dist/tsconfig.stcbuildinfo").exists());
+}
+
+#[test]
+fn downstream_uses_emitted_declarations() {
+    let dir = create_projects(
+        "downstream_uses_emitted_declarations",
+        "export function greet(name: string): string {\n    return name;\n}",
+    );
+    assert_eq!(
+        build(&dir.join("app/tsconfig.json"), false),
+        vec![BuildStatus::Built, BuildStatus::Built]
+    );
+
+    // The error in the source of the upstream project is not reported by the
+    // downstream project, because it uses `.d.ts` files.
+    write(&dir, "lib/dist/index.d.ts", "export declare function greet(name: string): number;");
+    write(
+        &dir,
+        "app/src/main.ts",
+        "import { greet } from '../../lib/src/index';\n\nexport const n: number = greet('stc');",
+    );
+
+    assert_eq!(
+        build(&dir.join("app/tsconfig.json"), false),
+        vec![BuildStatus::UpToDate, BuildStatus::Built]
+    );
+}
@@ -0,0 +1,91 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::{env, fs, path::PathBuf, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_errors::ErrorKind;
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{
+    loader::{DefaultFileLoader, ModuleLoader},
+    Checker,
+};
+use swc_common::{FileName, Spanned};
+use swc_ecma_ast::EsVersion;
+
+/// Files of a test, written to a temporary directory.
+pub struct Project {
+    dir: PathBuf,
+    rule: Rule,
+    lib: String,
+    type_roots: Vec<PathBuf>,
+}
+
+impl Project {
+    /// Writes `files` to `stc-{suite}/{name}` in the temporary directory.
+    pub fn new(suite: &str, name: &str, files: &[(&str, &str)]) -> Self {
+        let dir = env::temp_dir().join(format!("stc-{}", suite)).join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        Self {
+            dir: dir.canonicalize().unwrap(),
+            rule: Default::default(),
+            lib: "es5".into(),
+            type_roots: vec![],
+        }
+    }
+
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    pub fn lib(mut self, lib: &str) -> Self {
+        self.lib = lib.into();
+        self
+    }
+
+    /// `type_roots` are relative to the directory of the project.
+    pub fn type_roots(mut self, type_roots: &[&str]) -> Self {
+        self.type_roots = type_roots.iter().map(|root| self.dir.join(root)).collect();
+        self
+    }
+
+    /// Checks `entry` and returns `(line, code)` of errors.
+    pub fn check(self, entry: &str) -> Vec<(usize, usize)> {
+        let entry = Arc::new(FileName::Real(self.dir.join(entry)));
+        let Project { rule, lib, type_roots, .. } = self;
+
+        testing::run_test2(false, |cm, handler| {
+            let env = Env::simple(rule, EsVersion::latest(), ModuleConfig::None, &Lib::load(&lib));
+            let resolver = NodeResolver::new().allow_js(rule.allow_js);
+            let mut checker = Checker::new(
+                cm.clone(),
+                Arc::new(handler),
+                env.clone(),
+                None,
+                Box::new(ModuleLoader::new(cm.clone(), env, resolver, DefaultFileLoader).with_type_roots(type_roots)),
+            );
+
+            checker.check(entry);
+
+            let mut errors = ErrorKind::flatten(checker.take_errors())
+                .into_iter()
+                .map(|err| (cm.lookup_char_pos(err.span().lo).line, err.code()))
+                .collect::<Vec<_>>();
+            errors.sort_unstable();
+
+            Ok(errors)
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,103 @@
+//! Tests for `@ts-ignore`, `@ts-expect-error` and `@ts-nocheck` comments.
+
+mod common;
+
+use common::Project;
+
+/// Checks `content` as `index.ts` and returns `(line, code)` of errors.
+fn check(name: &str, content: &str) -> Vec<(usize, usize)> {
+    Project::new("directives", name, &[("index.ts", content)]).check("index.ts")
+}
+
+#[test]
+fn ts_ignore() {
+    let errors = check(
+        "ts_ignore",
+        "// @ts-ignore
+const a: string = 1;
+const b: string = 1;
+",
+    );
+
+    assert_eq!(errors, vec![(3, 2322)]);
+}
+
+#[test]
+fn ts_ignore_skips_blank_lines_and_comments() {
+    let errors = check(
+        "ts_ignore_skips_blank_lines_and_comments",
+        "// @ts-ignore
+
+// comment
+const a: string = 1;
+",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn unused_ts_expect_error() {
+    let errors = check(
+        "unused_ts_expect_error",
+        "// @ts-expect-error
+const a: string = 1;
+// @ts-expect-error
+const b: number = 1;
+",
+    );
+
+    assert_eq!(errors, vec![(3, 2578)]);
+}
+
+#[test]
+fn block_comments() {
+    let errors = check(
+        "block_comments",
+        "/** @ts-ignore */
+const a: string = 1;
+/* comment */
+const b: string = 1;
+/**
+ * Only the last line of a block comment is a directive.
+ * @ts-expect-error
+ */
+const c: string = 1;
+/*
+ * @ts-expect-error */
+const d: string = 1;
+/*
+@ts-expect-error */
+const e: string = 1;
+",
+    );
+
+    assert_eq!(errors, vec![(4, 2322), (9, 2322), (12, 2322)]);
+}
+
+#[test]
+fn ts_nocheck() {
+    let errors = check(
+        "ts_nocheck",
+        "// @ts-nocheck
+const a: string = 1;
+// @ts-expect-error
+const b: number = 1;
+",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn ts_nocheck_after_first_statement() {
+    let errors = check(
+        "ts_nocheck_after_first_statement",
+        "const a: string = 1;
+// @ts-nocheck
+const b: string = 1;
+",
+    );
+
+    assert_eq!(errors, vec![(1, 2322), (3, 2322)]);
+}
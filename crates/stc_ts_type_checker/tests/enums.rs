@@ -0,0 +1,34 @@
+//! Tests for enums which refer to enums declared in other files.
+
+mod common;
+
+use common::Project;
+
+/// Checks `index.ts` and returns `(line, code)` of errors in it.
+fn check(name: &str, files: &[(&str, &str)]) -> Vec<(usize, usize)> {
+    Project::new("enums", name, files).check("index.ts")
+}
+
+#[test]
+fn declare_const_enum_from_other_file() {
+    let errors = check(
+        "declare_const_enum_from_other_file",
+        &[
+            ("flags.d.ts", "export declare const enum Flags {\n    A = 1,\n    B = 2,\n}\n"),
+            (
+                "index.ts",
+                "import { Flags } from './flags';
+enum E {
+    X = Flags.A | Flags.B,
+    Y = `${Flags.B}`,
+}
+export const x: 3 = E.X;
+export const y: \"2\" = E.Y;
+export const z: 4 = E.X;
+",
+            ),
+        ],
+    );
+
+    assert_eq!(errors, vec![(8, 2322)]);
+}
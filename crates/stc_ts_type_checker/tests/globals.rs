@@ -0,0 +1,59 @@
+//! Tests for global declarations merged across files.
+
+mod common;
+
+use common::Project;
+
+/// Checks `index.ts` and returns `(line, code)` of errors in it.
+fn check(name: &str, files: &[(&str, &str)]) -> Vec<(usize, usize)> {
+    Project::new("globals", name, files).check("index.ts")
+}
+
+#[test]
+fn namespace_and_interface_split_across_files() {
+    let errors = check(
+        "namespace_and_interface_split_across_files",
+        &[
+            (
+                "a.d.ts",
+                "declare namespace NodeJS {
+    interface ProcessEnv {
+        A: string;
+    }
+}
+interface Window {
+    a: number;
+}
+",
+            ),
+            (
+                "b.d.ts",
+                "declare namespace NodeJS {
+    interface Global {
+        b: number;
+    }
+}
+interface Window {
+    b: string;
+}
+",
+            ),
+            (
+                "index.ts",
+                "/// <reference path=\"./a.d.ts\" />
+/// <reference path=\"./b.d.ts\" />
+declare const env: NodeJS.ProcessEnv;
+declare const g: NodeJS.Global;
+declare const w: Window;
+export const a: string = env.A;
+export const b: number = g.b;
+export const c: number = w.a;
+export const d: string = w.b;
+export const e: number = w.b;
+",
+            ),
+        ],
+    );
+
+    assert_eq!(errors, vec![(10, 2322)]);
+}
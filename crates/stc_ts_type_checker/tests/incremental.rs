@@ -0,0 +1,171 @@
+//! Tests for the on-disk module cache.
+
+use std::{env, fs, path::PathBuf, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_errors::ErrorKind;
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{
+    incremental::ModuleCache,
+    loader::{DefaultFileLoader, ModuleLoader},
+    Checker,
+};
+use swc_common::{FileName, Spanned};
+use swc_ecma_ast::EsVersion;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct CheckedError {
+    file: String,
+    line: usize,
+    code: usize,
+    restored: bool,
+}
+
+struct Project {
+    dir: PathBuf,
+}
+
+impl Project {
+    fn new(name: &str, files: &[(&str, &str)]) -> Self {
+        let dir = env::temp_dir().join("stc-incremental").join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+
+        let project = Project {
+            dir: dir.canonicalize().unwrap(),
+        };
+        for (name, content) in files {
+            project.write(name, content);
+        }
+        project
+    }
+
+    fn write(&self, name: &str, content: &str) {
+        fs::write(self.dir.join("src").join(name), content).unwrap();
+    }
+
+    /// Checks `src/index.ts` using the cache stored in the directory.
+    fn check(&self, rule: Rule, libs: &str) -> Vec<CheckedError> {
+        testing::run_test2(false, |cm, handler| {
+            let env = Env::simple(rule, EsVersion::latest(), ModuleConfig::None, &Lib::load(libs));
+            let mut checker = Checker::new(
+                cm.clone(),
+                Arc::new(handler),
+                env.clone(),
+                None,
+                Box::new(ModuleLoader::new(cm.clone(), env, NodeResolver::new(), DefaultFileLoader)),
+            );
+            checker.set_module_cache(ModuleCache::new(self.dir.join("cache")));
+
+            checker.check(Arc::new(FileName::Real(self.dir.join("src").join("index.ts"))));
+
+            let mut errors = ErrorKind::flatten(checker.take_errors())
+                .into_iter()
+                .map(|err| {
+                    let loc = cm.lookup_char_pos(err.span().lo);
+
+                    CheckedError {
+                        file: loc.file.name.to_string().replace(&self.dir.join("src").display().to_string(), ""),
+                        line: loc.line,
+                        code: err.code(),
+                        restored: matches!(&*err, ErrorKind::Restored { .. }),
+                    }
+                })
+                .collect::<Vec<_>>();
+            errors.sort();
+
+            Ok(errors)
+        })
+        .unwrap()
+    }
+}
+
+fn error(file: &str, line: usize, code: usize, restored: bool) -> CheckedError {
+    CheckedError {
+        file: format!("/{}", file),
+        line,
+        code,
+        restored,
+    }
+}
+
+#[test]
+fn restores_errors_of_unchanged_files() {
+    let project = Project::new(
+        "restores_errors_of_unchanged_files",
+        &[
+            ("a.ts", "export function a(): number {\n    return 1;\n}"),
+            ("index.ts", "import { a } from './a';\n\nconst s: string = a();"),
+        ],
+    );
+
+    assert_eq!(project.check(Default::default(), "es5"), vec![error("index.ts", 3, 2322, false)]);
+    assert_eq!(project.check(Default::default(), "es5"), vec![error("index.ts", 3, 2322, true)]);
+
+    // The public api of `a.ts` is not changed.
+    project.write("a.ts", "export function a(): number {\n    return 2;\n}");
+    assert_eq!(project.check(Default::default(), "es5"), vec![error("index.ts", 3, 2322, true)]);
+}
+
+#[test]
+fn invalidated_by_public_api_of_dependency() {
+    let project = Project::new(
+        "invalidated_by_public_api_of_dependency",
+        &[
+            ("a.ts", "export function a(): number {\n    return 1;\n}"),
+            (
+                "index.ts",
+                "import { a } from './a';\n\nconst s: string = a();\nconst n: number = 1;",
+            ),
+        ],
+    );
+
+    assert_eq!(project.check(Default::default(), "es5"), vec![error("index.ts", 3, 2322, false)]);
+
+    project.write("a.ts", "export function a(): string {\n    return '';\n}");
+    assert_eq!(project.check(Default::default(), "es5"), vec![]);
+}
+
+#[test]
+fn invalidated_by_rule() {
+    let project = Project::new("invalidated_by_rule", &[("index.ts", "const s: string = 1;")]);
+
+    assert_eq!(project.check(Default::default(), "es5"), vec![error("index.ts", 1, 2322, false)]);
+
+    let rule = Rule {
+        strict_null_checks: true,
+        ..Default::default()
+    };
+    assert_eq!(project.check(rule, "es5"), vec![error("index.ts", 1, 2322, false)]);
+    assert_eq!(project.check(rule, "es5"), vec![error("index.ts", 1, 2322, true)]);
+}
+
+#[test]
+fn invalidated_by_libs() {
+    let project = Project::new("invalidated_by_libs", &[("index.ts", "const s: string = 1;")]);
+
+    assert_eq!(project.check(Default::default(), "es5"), vec![error("index.ts", 1, 2322, false)]);
+    assert_eq!(project.check(Default::default(), "es2015"), vec![error("index.ts", 1, 2322, false)]);
+    assert_eq!(project.check(Default::default(), "es2015"), vec![error("index.ts", 1, 2322, true)]);
+}
+
+#[test]
+fn invalidated_by_global_declarations() {
+    let project = Project::new(
+        "invalidated_by_global_declarations",
+        &[
+            ("globals.d.ts", "interface Options {\n    a: string;\n}"),
+            (
+                "index.ts",
+                "/// <reference path=\"./globals.d.ts\" />\nexport const o: Options = { a: 1 };",
+            ),
+        ],
+    );
+
+    assert_eq!(project.check(Default::default(), "es5"), vec![error("index.ts", 2, 2322, false)]);
+
+    project.write("globals.d.ts", "interface Options {\n    a: number;\n}");
+    assert_eq!(project.check(Default::default(), "es5"), vec![]);
+}
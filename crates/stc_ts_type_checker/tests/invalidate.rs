@@ -0,0 +1,207 @@
+//! Tests for [Checker::invalidate], which is used by the watch mode.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_errors::ErrorKind;
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{
+    loader::{DefaultFileLoader, ModuleLoader},
+    Checker,
+};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Checks `index.ts`, replaces `file` with `new_content` and checks
+/// `index.ts` again.
+///
+/// Returns the error codes of both checks and invalidated files.
+fn check_twice(name: &str, files: &[(&str, &str)], file: &str, new_content: &str) -> (Vec<usize>, Vec<String>, Vec<usize>) {
+    let dir = create_dir(name);
+    for (name, content) in files {
+        fs::write(dir.join(name), content).unwrap();
+    }
+    let dir = dir.canonicalize().unwrap();
+    let entry = Arc::new(FileName::Real(dir.join("index.ts")));
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            Box::new(ModuleLoader::new(cm, env, NodeResolver::new(), DefaultFileLoader)),
+        );
+
+        checker.check(entry.clone());
+        let before = error_codes(&mut checker);
+
+        fs::write(dir.join(file), new_content).unwrap();
+        let invalidated = checker
+            .invalidate(&[Arc::new(FileName::Real(dir.join(file)))])
+            .into_iter()
+            .map(|path| relative_path(&dir, &path))
+            .collect();
+
+        checker.check(entry.clone());
+        let after = error_codes(&mut checker);
+
+        Ok((before, invalidated, after))
+    })
+    .unwrap()
+}
+
+fn create_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join("stc-invalidate").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn relative_path(dir: &Path, path: &FileName) -> String {
+    match path {
+        FileName::Real(path) => path.strip_prefix(dir).unwrap().display().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+fn error_codes(checker: &mut Checker) -> Vec<usize> {
+    let mut codes = ErrorKind::flatten(checker.take_errors())
+        .into_iter()
+        .map(|err| err.code())
+        .collect::<Vec<_>>();
+    codes.sort_unstable();
+    codes
+}
+
+#[test]
+fn dependents() {
+    let (before, invalidated, after) = check_twice(
+        "dependents",
+        &[
+            ("a.ts", "export const a: number = 1;"),
+            ("index.ts", "import { a } from './a';\nconst s: string = a;"),
+        ],
+        "a.ts",
+        "export const a: string = '';",
+    );
+
+    assert_eq!(before, vec![2322]);
+    assert_eq!(invalidated, vec!["a.ts", "index.ts"]);
+    assert_eq!(after, Vec::<usize>::new());
+}
+
+#[test]
+fn unrelated_file() {
+    let (before, invalidated, after) = check_twice(
+        "unrelated_file",
+        &[
+            ("a.ts", "export const a: number = 1;"),
+            ("b.ts", "export const b: number = 1;"),
+            (
+                "index.ts",
+                "import { a } from './a';\nimport { b } from './b';\nconst s: string = a;",
+            ),
+        ],
+        "b.ts",
+        "export const b: string = '';",
+    );
+
+    assert_eq!(before, vec![2322]);
+    assert_eq!(invalidated, vec!["b.ts", "index.ts"]);
+    assert_eq!(after, vec![2322]);
+}
+
+#[test]
+fn global_interface() {
+    let (before, invalidated, after) = check_twice(
+        "global_interface",
+        &[
+            ("globals.d.ts", "interface Options { a: string }"),
+            (
+                "index.ts",
+                "/// <reference path=\"./globals.d.ts\" />\nexport const o: Options = { a: 1 };",
+            ),
+        ],
+        "globals.d.ts",
+        "interface Options { a: number }",
+    );
+
+    assert_eq!(before, vec![2322]);
+    assert_eq!(invalidated, vec!["globals.d.ts", "index.ts"]);
+    // The previous declaration should not be merged.
+    assert_eq!(after, Vec::<usize>::new());
+}
+
+#[test]
+fn ambient_module() {
+    let (before, invalidated, after) = check_twice(
+        "ambient_module",
+        &[
+            ("types.d.ts", "declare module 'pkg' {\n    export const v: number;\n}"),
+            (
+                "index.ts",
+                "/// <reference path=\"./types.d.ts\" />\nimport { v } from 'pkg';\nconst s: string = v;",
+            ),
+        ],
+        "types.d.ts",
+        "declare module 'pkg' {\n    export const v: string;\n}",
+    );
+
+    assert_eq!(before, vec![2322]);
+    assert_eq!(invalidated, vec!["index.ts", "types.d.ts"]);
+    assert_eq!(after, Vec::<usize>::new());
+}
+
+#[test]
+fn augmentation() {
+    let (before, invalidated, after) = check_twice(
+        "augmentation",
+        &[
+            ("pkg.ts", "export interface Request {\n    url: string;\n}"),
+            (
+                "aug.ts",
+                "import './pkg';\ndeclare module './pkg' {\n    interface Request {\n        user: string;\n    }\n}\nexport {};",
+            ),
+            (
+                "index.ts",
+                "import './aug';\nimport { Request } from './pkg';\ndeclare const r: Request;\nconst n: number = r.user;",
+            ),
+        ],
+        "aug.ts",
+        "import './pkg';\ndeclare module './pkg' {\n    interface Request {\n        user: number;\n    }\n}\nexport {};",
+    );
+
+    assert_eq!(before, vec![2322]);
+    assert_eq!(invalidated, vec!["aug.ts", "index.ts", "pkg.ts"]);
+    // The augmentation of the previous version should be replaced.
+    assert_eq!(after, Vec::<usize>::new());
+}
+
+#[test]
+fn no_default_lib_removed() {
+    let (before, invalidated, after) = check_twice(
+        "no_default_lib_removed",
+        &[
+            ("a.ts", "/// <reference no-default-lib=\"true\" />\nexport const a = 1;"),
+            (
+                "index.ts",
+                "import { a } from './a';\nexport const n: number = a;\nexport const s: string = 'a'.toUpperCase();",
+            ),
+        ],
+        "a.ts",
+        "export const a = 1;",
+    );
+
+    assert_ne!(before, Vec::<usize>::new());
+    assert_eq!(invalidated, vec!["a.ts", "index.ts"]);
+    // The default libraries should be restored.
+    assert_eq!(after, Vec::<usize>::new());
+}
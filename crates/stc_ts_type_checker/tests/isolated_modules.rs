@@ -0,0 +1,71 @@
+//! Tests for `isolatedModules` and `verbatimModuleSyntax`, which need
+//! multiple files.
+
+mod common;
+
+use common::Project;
+use stc_ts_env::Rule;
+
+const TYPES: &str = "export interface T {}\nexport const v = 1;\n";
+
+/// Checks `index.ts` and returns `(line, code)` of errors in it.
+fn check(name: &str, rule: Rule, content: &str) -> Vec<(usize, usize)> {
+    Project::new("isolated-modules", name, &[("types.ts", TYPES), ("index.ts", content)])
+        .rule(rule)
+        .check("index.ts")
+}
+
+#[test]
+fn re_export_of_type() {
+    let errors = check(
+        "re_export_of_type",
+        Rule {
+            isolated_modules: true,
+            ..Default::default()
+        },
+        "import { T, v } from './types';
+export { T };
+export { v };
+export type { T as U };
+export { T as W } from './types';
+export { v as x } from './types';
+export type { T as Y } from './types';
+",
+    );
+
+    assert_eq!(errors, vec![(2, 1205), (5, 1205)]);
+}
+
+#[test]
+fn type_import_with_verbatim_module_syntax() {
+    let errors = check(
+        "type_import_with_verbatim_module_syntax",
+        Rule {
+            verbatim_module_syntax: true,
+            ..Default::default()
+        },
+        "import { T, v } from './types';
+import type { T as U } from './types';
+import { type T as W } from './types';
+export const x: T | U | W = v;
+",
+    );
+
+    assert_eq!(errors, vec![(1, 1484)]);
+}
+
+#[test]
+fn type_import_without_verbatim_module_syntax() {
+    let errors = check(
+        "type_import_without_verbatim_module_syntax",
+        Rule {
+            isolated_modules: true,
+            ..Default::default()
+        },
+        "import { T, v } from './types';
+export const x: T = v;
+",
+    );
+
+    assert_eq!(errors, vec![]);
+}
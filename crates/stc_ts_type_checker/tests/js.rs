@@ -0,0 +1,186 @@
+//! Tests for JavaScript files, which are converted to TypeScript by the
+//! loader.
+
+mod common;
+
+use common::Project;
+use stc_ts_env::Rule;
+
+/// Checks `entry` and returns `(line, code)` of errors.
+fn check(name: &str, rule: Rule, files: &[(&str, &str)], entry: &str) -> Vec<(usize, usize)> {
+    let mut errors = Project::new("js", name, files).rule(rule).check(entry);
+    errors.dedup();
+    errors
+}
+
+fn check_js() -> Rule {
+    Rule {
+        allow_js: true,
+        check_js: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn param_and_returns() {
+    let errors = check(
+        "param_and_returns",
+        check_js(),
+        &[(
+            "index.js",
+            "/**
+ * @param {number} a
+ * @param {string} [b]
+ * @returns {string}
+ */
+function f(a, b) {
+    return a;
+}
+f(1);
+f('x');
+",
+        )],
+        "index.js",
+    );
+
+    assert_eq!(errors, vec![(7, 2322), (10, 2345)]);
+}
+
+#[test]
+fn typedef_and_callback() {
+    let errors = check(
+        "typedef_and_callback",
+        check_js(),
+        &[(
+            "index.js",
+            "/**
+ * @typedef {Object} Point
+ * @property {number} x
+ * @property {number} [y]
+ */
+
+/**
+ * @callback Callback
+ * @param {string} s
+ * @returns {number}
+ */
+
+/** @type {Point} */
+const a = { x: 1 };
+/** @type {Point} */
+const b = { x: 'x' };
+
+/** @type {Callback} */
+const c = (s) => s.length;
+/** @type {Callback} */
+const d = (s) => s;
+",
+        )],
+        "index.js",
+    );
+
+    assert_eq!(errors, vec![(16, 2322), (21, 2322)]);
+}
+
+#[test]
+fn common_js() {
+    let errors = check(
+        "common_js",
+        Rule {
+            allow_js: true,
+            ..Default::default()
+        },
+        &[
+            ("a.js", "module.exports = { x: 1 };\n"),
+            (
+                "b.js",
+                "const a = require('./a');
+function foo() {
+    return 1;
+}
+exports.foo = 'foo';
+exports.foo = 'bar';
+exports.bar = foo;
+exports.x = a.x;
+",
+            ),
+            (
+                "index.ts",
+                "import { foo, bar, x } from './b';
+const s: string = foo;
+const n: number = bar;
+const m: string = x;
+",
+            ),
+        ],
+        "index.ts",
+    );
+
+    assert_eq!(errors, vec![(3, 2322), (4, 2322)]);
+}
+
+#[test]
+fn js_requires_allow_js() {
+    let errors = check(
+        "js_requires_allow_js",
+        Default::default(),
+        &[("a.js", "exports.a = 1;\n"), ("index.ts", "import { a } from './a';\n")],
+        "index.ts",
+    );
+
+    assert_eq!(errors, vec![(1, 2307)]);
+}
+
+#[test]
+fn not_checked_without_check_js() {
+    let errors = check(
+        "not_checked_without_check_js",
+        Rule {
+            allow_js: true,
+            ..Default::default()
+        },
+        &[("index.js", "/** @type {string} */\nconst s = 1;\n")],
+        "index.js",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn ts_check() {
+    let errors = check(
+        "ts_check",
+        Rule {
+            allow_js: true,
+            ..Default::default()
+        },
+        &[("index.js", "// @ts-check\n/** @type {string} */\nconst s = 1;\n")],
+        "index.js",
+    );
+
+    assert_eq!(errors, vec![(3, 2322)]);
+}
+
+#[test]
+fn ts_nocheck_with_check_js() {
+    let errors = check(
+        "ts_nocheck_with_check_js",
+        check_js(),
+        &[("index.js", "// @ts-nocheck\n/** @type {string} */\nconst s = 1;\n")],
+        "index.js",
+    );
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn check_js_without_directive() {
+    let errors = check(
+        "check_js_without_directive",
+        check_js(),
+        &[("index.js", "/** @type {string} */\nconst s = 1;\n")],
+        "index.js",
+    );
+
+    assert_eq!(errors, vec![(2, 2322)]);
+}
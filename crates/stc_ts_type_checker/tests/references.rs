@@ -0,0 +1,72 @@
+//! Tests for `/// <reference lib="..." />` and `/// <reference types="..." />`.
+
+mod common;
+
+use common::Project;
+
+/// Checks `index.ts` and returns `(line, code)` of errors in it.
+///
+/// `type_roots` are relative to the directory of `index.ts`.
+fn check(name: &str, files: &[(&str, &str)], type_roots: &[&str]) -> Vec<(usize, usize)> {
+    Project::new("references", name, files).type_roots(type_roots).check("index.ts")
+}
+
+#[test]
+fn lib_es2020() {
+    let errors = check(
+        "lib_es2020",
+        &[(
+            "index.ts",
+            "/// <reference lib=\"es2020\" />
+export const a: string = \"a\".padStart(3);
+export const b: number = \"a\".padStart(3);
+",
+        )],
+        &[],
+    );
+
+    assert_eq!(errors, vec![(3, 2322)]);
+}
+
+#[test]
+fn without_lib() {
+    let errors = check("without_lib", &[("index.ts", "export const a: string = \"a\".padStart(3);\n")], &[]);
+
+    assert_eq!(errors, vec![(1, 2339)]);
+}
+
+#[test]
+fn lib_with_global_augmentation() {
+    let errors = check(
+        "lib_with_global_augmentation",
+        &[
+            ("global.d.ts", "interface String {\n    extra(): number;\n}\n"),
+            (
+                "index.ts",
+                "/// <reference path=\"./global.d.ts\" />
+/// <reference lib=\"es2020\" />
+export const a: number = \"a\".extra();
+export const b: string = \"a\".padStart(3);
+export const c: string = \"a\".extra();
+",
+            ),
+        ],
+        &[],
+    );
+
+    assert_eq!(errors, vec![(5, 2322)]);
+}
+
+#[test]
+fn types_in_type_roots() {
+    let errors = check(
+        "types_in_type_roots",
+        &[
+            ("typings/foo/index.d.ts", "declare const foo: number;\n"),
+            ("index.ts", "/// <reference types=\"foo\" />\nexport const a: string = foo;\n"),
+        ],
+        &["typings"],
+    );
+
+    assert_eq!(errors, vec![(2, 2322)]);
+}
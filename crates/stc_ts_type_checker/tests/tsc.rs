@@ -593,12 +593,15 @@ impl Resolve for TestFileSystem {
         println!("resolve: {:?} {:?}", base, module_specifier);
 
         if !module_specifier.starts_with('.') {
-            return NodeResolver.resolve(base, module_specifier);
+            return NodeResolver::new().resolve(base, module_specifier);
         }
 
         if let Some(filename) = module_specifier.strip_prefix("./") {
             for (name, _) in self.files.iter() {
-                if format!("{}.ts", filename) == *name || format!("{}.tsx", filename) == *name || format!("{}.d.ts", filename) == *name {
+                if ["ts", "tsx", "d.ts", "js", "jsx"]
+                    .iter()
+                    .any(|ext| format!("{}.{}", filename, ext) == *name)
+                {
                     return Ok(FileName::Real(name.into()));
                 }
             }
@@ -14,7 +14,7 @@ use swc_ecma_ast::EsVersion;
 use swc_ecma_loader::resolve::Resolve;
 
 #[test]
-#[ignore = "Cross-file namespace is not supported"]
+#[ignore = "Errors in @types/node are not triaged since cross-file namespaces are merged"]
 fn test_node() {
     run_tests_for_types_pkg("@types/node/index.d.ts");
 }
@@ -44,7 +44,7 @@ fn run_tests_for_types_pkg(module_specifier: &str) {
             handler.clone(),
             env.clone(),
             None,
-            Box::new(ModuleLoader::new(cm, env, NodeResolver, DefaultFileLoader)),
+            Box::new(ModuleLoader::new(cm, env, NodeResolver::new(), DefaultFileLoader)),
         );
 
         checker.check(Arc::new(path));
@@ -0,0 +1,31 @@
+//! Tests for `noUnusedLocals` of modules which import each other.
+
+mod common;
+
+use common::Project;
+use stc_ts_env::Rule;
+
+#[test]
+fn circular_imports() {
+    let errors = Project::new(
+        "unused",
+        "circular_imports",
+        &[
+            (
+                "a.ts",
+                "import { b } from './index';\nexport const a = () => b;\nconst unusedA = 1;\n",
+            ),
+            (
+                "index.ts",
+                "import { a } from './a';\nconst unusedIndex = 1;\nexport const b = () => a;\n",
+            ),
+        ],
+    )
+    .rule(Rule {
+        no_unused_locals: true,
+        ..Default::default()
+    })
+    .check("index.ts");
+
+    assert_eq!(errors, vec![(2, 6133), (3, 6133)]);
+}
@@ -402,11 +402,9 @@ impl From<TypeParam> for RTsTypeParam {
             name: t.name.into(),
             constraint: t.constraint.map(From::from),
             default: t.default.map(From::from),
-
-            // TODO
-            is_in: false,
-            is_out: false,
-            is_const: false,
+            is_in: t.metadata.is_in,
+            is_out: t.metadata.is_out,
+            is_const: t.metadata.is_const,
         }
     }
 }
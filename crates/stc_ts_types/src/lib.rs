@@ -100,6 +100,24 @@ impl<V: ?Sized> FoldWith<V> for ModuleTypeData {
     }
 }
 
+impl ModuleTypeData {
+    /// Merges declarations of `other` into `self`.
+    ///
+    /// Unlike `+=`, types with the same name are kept together so that
+    /// declarations of a namespace split across multiple files are merged.
+    pub fn merge(&mut self, other: Self) {
+        for (name, types) in other.types {
+            self.types.entry(name).or_default().extend(types);
+        }
+        for (id, types) in other.private_types {
+            self.private_types.entry(id).or_default().extend(types);
+        }
+
+        self.vars.extend(other.vars);
+        self.private_vars.extend(other.private_vars);
+    }
+}
+
 impl TypeEq for ModuleTypeData {
     #[inline]
     fn type_eq(&self, _: &Self) -> bool {
@@ -283,7 +301,7 @@ impl Clone for Type {
 }
 
 #[cfg(target_pointer_width = "64")]
-assert_eq_size!(Type, [u8; 112]);
+assert_eq_size!(Type, [u8; 120]);
 
 impl TypeEq for Type {
     fn type_eq(&self, other: &Self) -> bool {
@@ -958,7 +976,7 @@ pub struct Mapped {
 }
 
 #[cfg(target_pointer_width = "64")]
-assert_eq_size!(Mapped, [u8; 104]);
+assert_eq_size!(Mapped, [u8; 112]);
 
 #[derive(Clone, PartialEq, Spanned, EqIgnoreSpan, TypeEq, Visit, Serialize, Deserialize)]
 pub struct Conditional {
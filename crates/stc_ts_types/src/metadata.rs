@@ -283,6 +283,13 @@ impl_traits!(ClassDefMetadata);
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TypeParamMetadata {
     pub common: CommonTypeMetadata,
+
+    /// `const T`
+    pub is_const: bool,
+    /// `in T`
+    pub is_in: bool,
+    /// `out T`
+    pub is_out: bool,
 }
 
 impl_traits!(TypeParamMetadata);
@@ -8,23 +8,55 @@ use swc_common::{
 pub enum ImportRef {
     /// path="foo"
     Path(JsWord),
-    /// type="foo"
+    /// types="foo"
     Types(JsWord),
+    /// lib="foo"
+    Lib(JsWord),
+    /// no-default-lib="true"
+    NoDefaultLib,
 
     /// ES6 import.
     Normal(JsWord),
 }
 
 impl ImportRef {
-    pub fn to_path(self) -> JsWord {
+    /// Returns the module specifier to load.
+    ///
+    /// `types="foo"` is resolved to `@types/foo`, and [ImportRef::Lib] and
+    /// [ImportRef::NoDefaultLib] do not refer to a module.
+    pub fn to_path(self) -> Option<JsWord> {
         match self {
-            ImportRef::Path(s) => format!("./{}", s).into(),
-            ImportRef::Types(s) => s,
-            ImportRef::Normal(s) => s,
+            ImportRef::Path(s) => Some(format!("./{}", s).into()),
+            ImportRef::Types(s) => Some(types_package_of(&s).into()),
+            ImportRef::Lib(..) | ImportRef::NoDefaultLib => None,
+            ImportRef::Normal(s) => Some(s),
         }
     }
 }
 
+/// Returns the name of `@types` package for `pkg`.
+///
+/// `@scope/foo` is mapped to `@types/scope__foo`.
+fn types_package_of(pkg: &str) -> String {
+    match pkg.strip_prefix('@') {
+        Some(scoped) => format!("@types/{}", scoped.replacen('/', "__", 1)),
+        None => format!("@types/{}", pkg),
+    }
+}
+
+/// Reverse of [ImportRef::to_path] for [ImportRef::Types].
+///
+/// Returns the name of the package declaring its own types if `specifier` is
+/// a module specifier for a `@types` package.
+pub fn package_of_types(specifier: &str) -> Option<String> {
+    let pkg = specifier.strip_prefix("@types/")?;
+
+    Some(match pkg.split_once("__") {
+        Some((scope, name)) => format!("@{}/{}", scope, name),
+        None => pkg.to_string(),
+    })
+}
+
 pub fn find_imports_in_comments<C>(comments: C, span: Span) -> Vec<ImportRef>
 where
     C: Comments,
@@ -43,25 +75,87 @@ where
             if c.kind != CommentKind::Line {
                 continue;
             }
-            if let Some(cmt_text) = c
-                .text
-                .trim()
-                .strip_prefix('/')
-                .map(|s| s.trim())
-                .and_then(|s| s.strip_prefix("<reference"))
-                .and_then(|s| s.strip_suffix("\" />"))
-                .map(|s| s.trim())
-            {
-                if let Some(path) = cmt_text.strip_prefix("path=\"") {
-                    deps.push(ImportRef::Path(path.into()));
-                } else if let Some(path) = cmt_text.strip_prefix("types=\"") {
-                    deps.push(ImportRef::Types(path.into()));
-                } else {
-                    // TODO: Handle lib, types
-                }
-            }
+
+            deps.extend(parse_reference(&c.text));
         }
     });
 
     deps
 }
+
+/// Parses the text of a line comment, like `/ <reference types="node" />`.
+fn parse_reference(text: &str) -> Option<ImportRef> {
+    let attrs = text
+        .trim()
+        .strip_prefix('/')
+        .map(|s| s.trim())
+        .and_then(|s| s.strip_prefix("<reference"))
+        .and_then(|s| s.strip_suffix('>'))
+        .map(|s| s.trim_end().trim_end_matches('/'))?;
+
+    let (name, value) = parse_attr(attrs)?;
+    match name {
+        "path" => Some(ImportRef::Path(value.into())),
+        "types" => Some(ImportRef::Types(value.into())),
+        "lib" => Some(ImportRef::Lib(value.to_ascii_lowercase().into())),
+        "no-default-lib" if value == "true" => Some(ImportRef::NoDefaultLib),
+        _ => None,
+    }
+}
+
+/// Parses the first attribute of a triple-slash directive, like `types="node"`.
+fn parse_attr(s: &str) -> Option<(&str, &str)> {
+    let (name, value) = s.trim().split_once('=')?;
+    let value = value.trim();
+    let quote = value.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let value = &value[1..];
+    let end = value.find(quote)?;
+
+    Some((name.trim(), &value[..end]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_attr_with_quotes() {
+        assert_eq!(parse_attr(r#" types="node" "#), Some(("types", "node")));
+        assert_eq!(parse_attr(" types='node' "), Some(("types", "node")));
+        assert_eq!(parse_attr(r#"path = "./a.d.ts""#), Some(("path", "./a.d.ts")));
+        assert_eq!(parse_attr(r#"types="node'"#), None);
+        assert_eq!(parse_attr("types=node"), None);
+    }
+
+    #[test]
+    fn parse_reference_without_space() {
+        assert_eq!(
+            parse_reference(r#"/ <reference types="node" />"#),
+            Some(ImportRef::Types("node".into()))
+        );
+        assert_eq!(
+            parse_reference(r#"/<reference types='node'/>"#),
+            Some(ImportRef::Types("node".into()))
+        );
+        assert_eq!(
+            parse_reference(r#"/ <reference lib="ES2020"/>"#),
+            Some(ImportRef::Lib("es2020".into()))
+        );
+        assert_eq!(
+            parse_reference(r#"/ <reference no-default-lib="true"/>"#),
+            Some(ImportRef::NoDefaultLib)
+        );
+        assert_eq!(parse_reference(r#"/ <reference no-default-lib="false" />"#), None);
+        assert_eq!(parse_reference(r#" <reference types="node" />"#), None);
+    }
+
+    #[test]
+    fn types_package() {
+        assert_eq!(types_package_of("node"), "@types/node");
+        assert_eq!(types_package_of("@scope/foo"), "@types/scope__foo");
+
+        assert_eq!(package_of_types("@types/node"), Some("node".into()));
+        assert_eq!(package_of_types("@types/scope__foo"), Some("@scope/foo".into()));
+        assert_eq!(package_of_types("node"), None);
+    }
+}
@@ -0,0 +1,51 @@
+//! Sets `STC_BUILD_ID`, which identifies the build for on-disk caches.
+
+use std::{
+    env,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+fn main() {
+    // Every crate of the workspace affects the cached results.
+    println!("cargo:rerun-if-changed=..");
+    println!("cargo:rerun-if-env-changed=STC_BUILD_ID");
+
+    let id = env::var("STC_BUILD_ID")
+        .ok()
+        .filter(|id| !id.is_empty())
+        .or_else(git_id)
+        .unwrap_or_else(timestamp);
+
+    println!("cargo:rustc-env=STC_BUILD_ID={}", id);
+}
+
+/// Hash of `HEAD`. Builds of a modified tree are distinguished by the build
+/// time.
+fn git_id() -> Option<String> {
+    let hash = git(&["rev-parse", "HEAD"])?;
+    let status = git(&["status", "--porcelain", "--untracked-files=no"])?;
+
+    if status.is_empty() {
+        Some(hash)
+    } else {
+        Some(format!("{}-dirty-{}", hash, timestamp()))
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
+
+fn timestamp() -> String {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default()
+        .to_string()
+}
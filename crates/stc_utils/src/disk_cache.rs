@@ -0,0 +1,74 @@
+//! Location and helpers for on-disk caches.
+//!
+//! The cache directory is selected in the following order.
+//!
+//! 1. [set_cache_dir], used for `--cache-dir` of the cli.
+//! 2. `STC_CACHE_DIR` environment variable.
+//! 3. `$XDG_CACHE_HOME/stc`, or `$HOME/.cache/stc` (`%LOCALAPPDATA%\stc` on
+//! windows).
+//! 4. `stc` in the temporary directory.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+};
+
+use once_cell::sync::OnceCell;
+
+pub const CACHE_DIR_ENV: &str = "STC_CACHE_DIR";
+
+/// Version of stc. Caches created by other builds are ignored.
+///
+/// `STC_BUILD_ID` is the git hash of the build, set by `build.rs`.
+pub const STC_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "-", env!("STC_BUILD_ID"));
+
+static CACHE_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// Overrides the cache directory. This should be called before the first call
+/// to [cache_dir].
+///
+/// Returns `false` if the cache directory is already determined.
+pub fn set_cache_dir(dir: PathBuf) -> bool {
+    CACHE_DIR.set(dir).is_ok()
+}
+
+pub fn cache_dir() -> &'static Path {
+    CACHE_DIR.get_or_init(default_cache_dir)
+}
+
+fn default_cache_dir() -> PathBuf {
+    if let Some(dir) = env::var_os(CACHE_DIR_ENV).filter(|v| !v.is_empty()) {
+        return PathBuf::from(dir);
+    }
+
+    let base = if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CACHE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+    };
+
+    base.unwrap_or_else(env::temp_dir).join("stc")
+}
+
+/// Writes `data` to a temporary file and renames it to `path`, so other
+/// processes never read a partially written file.
+pub fn write_atomic(path: &Path, data: &[u8]) -> io::Result<()> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(dir)?;
+
+    let file_name = path.file_name().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let tmp = dir.join(format!(".{}.{}.{}.tmp", file_name, process::id(), COUNTER.fetch_add(1, Relaxed)));
+
+    fs::write(&tmp, data)?;
+    fs::rename(&tmp, path).map_err(|err| {
+        let _ = fs::remove_file(&tmp);
+        err
+    })
+}
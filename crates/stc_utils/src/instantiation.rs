@@ -0,0 +1,120 @@
+//! Limits for type instantiations, like `instantiationDepth` and
+//! `instantiationCount` of `tsc`.
+
+use std::{cell::RefCell, mem::replace};
+
+use swc_common::{Span, DUMMY_SP};
+
+/// Maximum depth of nested instantiations.
+pub const MAX_DEPTH: usize = 100;
+
+/// Maximum number of instantiations for a statement or an expression.
+pub const MAX_COUNT: usize = 5_000_000;
+
+/// Maximum number of iterations while evaluating a tail-recursive conditional
+/// type.
+pub const MAX_TAIL_RECURSION: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InstantiationTooDeepError {
+    pub span: Span,
+}
+
+struct Ctx {
+    /// Span of the statement or the expression being validated.
+    span: Span,
+    depth: usize,
+    count: usize,
+    /// `true` while validating an expression of the current statement.
+    in_expr: bool,
+}
+
+pub struct StartGuard {
+    prev_span: Span,
+    prev_in_expr: bool,
+}
+
+impl Drop for StartGuard {
+    fn drop(&mut self) {
+        // The count is not restored, as it's a total of the statement.
+        with_ctx(|v| {
+            v.span = self.prev_span;
+            v.in_expr = self.prev_in_expr;
+        })
+    }
+}
+
+/// Resets the instantiation count for the statement at `span`, like `tsc`
+/// does. The depth is not affected.
+pub fn start(span: Span) -> StartGuard {
+    with_ctx(|v| {
+        v.count = 0;
+        StartGuard {
+            prev_span: replace(&mut v.span, span),
+            prev_in_expr: replace(&mut v.in_expr, false),
+        }
+    })
+}
+
+/// Resets the instantiation count if the expression at `span` is not nested in
+/// another expression of the current statement.
+pub fn start_expr(span: Span) -> StartGuard {
+    with_ctx(|v| {
+        let prev_span = v.span;
+        if !v.in_expr {
+            v.span = span;
+            v.count = 0;
+        }
+        StartGuard {
+            prev_span,
+            prev_in_expr: replace(&mut v.in_expr, true),
+        }
+    })
+}
+
+pub struct TrackGuard {
+    _priv: (),
+}
+
+impl Drop for TrackGuard {
+    fn drop(&mut self) {
+        with_ctx(|v| v.depth -= 1)
+    }
+}
+
+/// Should be stored as a variable like `let _instantiation =
+/// instantiation::track()?;`.
+///
+/// The error points to the statement passed to [start] or the top-level
+/// expression passed to [start_expr].
+pub fn track() -> Result<TrackGuard, InstantiationTooDeepError> {
+    with_ctx(|v| {
+        if v.depth >= MAX_DEPTH || v.count >= MAX_COUNT {
+            tracing::error!("Type instantiation is too deep: {:?}", v.span);
+            return Err(InstantiationTooDeepError { span: v.span });
+        }
+
+        v.depth += 1;
+        v.count += 1;
+
+        Ok(TrackGuard { _priv: () })
+    })
+}
+
+/// Returns the error for a tail-recursive conditional type which exceeded
+/// [MAX_TAIL_RECURSION].
+pub fn too_deep() -> InstantiationTooDeepError {
+    with_ctx(|v| InstantiationTooDeepError { span: v.span })
+}
+
+fn with_ctx<T>(f: impl FnOnce(&mut Ctx) -> T) -> T {
+    thread_local! {
+        static CTX: RefCell<Ctx> = RefCell::new(Ctx {
+            span: DUMMY_SP,
+            depth: 0,
+            count: 0,
+            in_expr: false,
+        });
+    }
+    CTX.with(|ctx| f(&mut ctx.borrow_mut()))
+}
@@ -15,8 +15,10 @@ use once_cell::sync::Lazy;
 use swc_common::SyntaxContext;
 
 pub mod cache;
+pub mod disk_cache;
 pub mod error;
 pub mod ext;
+pub mod instantiation;
 pub mod panic_context;
 pub mod stack;
 